@@ -3,6 +3,9 @@ macro_rules! benchmark_suite {
         #[runtime::bench($rt)]
         async fn smoke() {}
 
+        #[runtime::bench($rt, iterations = 100)]
+        async fn smoke_batched() {}
+
         #[runtime::bench($rt)]
         async fn notify_self() {
             use futures::future::Future;
@@ -37,7 +40,7 @@ macro_rules! benchmark_suite {
                 .collect::<Vec<_>>();
 
             for task in tasks {
-                task.await;
+                task.await.unwrap();
             }
         }
 
@@ -48,7 +51,23 @@ macro_rules! benchmark_suite {
                 .collect::<Vec<_>>();
 
             for task in tasks {
-                task.await;
+                task.await.unwrap();
+            }
+        }
+
+        #[runtime::bench($rt)]
+        async fn tcp_addrs() {
+            use runtime::net::{TcpListener, TcpStream};
+
+            let mut listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let stream = TcpStream::connect(listener.local_addr().unwrap())
+                .await
+                .unwrap();
+            let _accepted = listener.accept().await.unwrap();
+
+            for _ in 0..1_000 {
+                test::black_box(stream.peer_addr().unwrap());
+                test::black_box(stream.local_addr().unwrap());
             }
         }
 
@@ -89,7 +108,7 @@ macro_rules! benchmark_suite {
                 .collect::<Vec<_>>();
 
             for task in tasks {
-                task.await;
+                task.await.unwrap();
             }
         }
     };
@@ -9,15 +9,15 @@
 
 use futures::prelude::*;
 use rand::Rng;
+use runtime::io::{AsyncBufReadExt, BufReader};
 use runtime::net::{TcpListener, TcpStream};
 use std::cmp::Ordering;
-use std::str;
 
 async fn play(stream: TcpStream) -> Result<(), failure::Error> {
     println!("Accepting from: {}", stream.peer_addr()?);
 
     let (reader, writer) = &mut stream.split();
-    let mut buf = vec![0u8; 1024];
+    let mut lines = BufReader::new(reader).lines();
 
     writer.write_all(b"Guess the number!\n").await?;
 
@@ -26,12 +26,10 @@ async fn play(stream: TcpStream) -> Result<(), failure::Error> {
     loop {
         writer.write_all(b"Please input your guess.\n").await?;
 
-        let len = reader.read(&mut buf).await?;
-        if len == 0 {
-            return Ok(());
-        }
-
-        let guess = str::from_utf8(&buf[..len])?;
+        let guess = match lines.next().await {
+            Some(line) => line?,
+            None => return Ok(()),
+        };
 
         let guess: u32 = match guess.trim().parse() {
             Ok(num) => num,
@@ -63,7 +61,7 @@ async fn main() -> Result<(), failure::Error> {
     incoming
         .try_for_each_concurrent(None, |stream| {
             async move {
-                runtime::spawn(play(stream)).await?;
+                runtime::spawn(play(stream)).await??;
                 Ok::<(), failure::Error>(())
             }
         })
@@ -8,12 +8,11 @@ use runtime::net::TcpListener;
 
 #[runtime::main]
 async fn main() -> std::io::Result<()> {
-    let mut listener = TcpListener::bind("127.0.0.1:8080")?;
+    let listener = TcpListener::bind("127.0.0.1:8080")?;
     println!("Listening on {}", listener.local_addr()?);
 
     // accept connections and process them in parallel
     listener
-        .incoming()
         .try_for_each_concurrent(None, |stream| {
             async move {
                 runtime::spawn(async move {
@@ -23,7 +22,7 @@ async fn main() -> std::io::Result<()> {
                     reader.copy_into(writer).await?;
                     Ok::<(), std::io::Error>(())
                 })
-                .await
+                .await?
             }
         })
         .await?;
@@ -9,10 +9,181 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
+/// The arguments accepted by the attributes: an optional runtime expression followed by options,
+/// e.g. `#[runtime::main(runtime_tokio::Tokio, threads = 4)]`.
+struct Args {
+    runtime: Option<syn::Expr>,
+    threads: Option<syn::LitInt>,
+    timeout_ms: Option<syn::LitInt>,
+    iterations: Option<syn::LitInt>,
+    start_paused: Option<syn::LitBool>,
+}
+
+impl Args {
+    /// The runtime to use, falling back to the default runtime if none was specified.
+    fn runtime(&self) -> Result<syn::Expr, TokenStream> {
+        if let Some(start_paused) = self.start_paused.as_ref().filter(|lit| lit.value) {
+            let message = if self.runtime.is_some() {
+                "start_paused selects its own runtime, and can't be combined with another one"
+            } else if cfg!(feature = "native") {
+                return Ok(syn::parse_str("runtime::native::TestClock::start_paused()").unwrap());
+            } else {
+                "start_paused requires the native runtime"
+            };
+            return Err(syn::Error::new_spanned(start_paused, message)
+                .to_compile_error()
+                .into());
+        }
+
+        match &self.runtime {
+            Some(rt) => Ok(rt.clone()),
+            None if cfg!(feature = "native") => {
+                Ok(syn::parse_str("runtime::native::Native").unwrap())
+            }
+            None => {
+                let tokens = quote_spanned! { proc_macro2::Span::call_site() =>
+                    compile_error!("async runtime needs to be specified if no default runtime is set");
+                };
+                Err(TokenStream::from(tokens))
+            }
+        }
+    }
+
+    /// Wrap the runtime expression so that it gets its own pool of worker threads if the `threads`
+    /// option was passed, rather than sizing the runtime for the whole process.
+    fn sized(&self, rt: syn::Expr) -> proc_macro2::TokenStream {
+        match &self.threads {
+            Some(threads) => quote! {
+                runtime::raw::WorkerThreads::with_worker_threads(&#rt, #threads)
+            },
+            None => quote! { #rt },
+        }
+    }
+}
+
+/// Reject an option that the attribute it was passed to doesn't support.
+fn reject<T: quote::ToTokens>(option: &Option<T>, message: &str) -> Result<(), TokenStream> {
+    match option {
+        Some(value) => Err(syn::Error::new_spanned(value, message)
+            .to_compile_error()
+            .into()),
+        None => Ok(()),
+    }
+}
+
+/// Returns the return type of a function if it's spelled as a `Result`.
+fn result_type(ret: &syn::ReturnType) -> Option<&syn::Type> {
+    match ret {
+        syn::ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(path) if path.qself.is_none() => path
+                .path
+                .segments
+                .last()
+                .filter(|segment| segment.ident == "Result")
+                .map(|_| &**ty),
+            _ => None,
+        },
+        syn::ReturnType::Default => None,
+    }
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut args = Args {
+            runtime: None,
+            threads: None,
+            timeout_ms: None,
+            iterations: None,
+            start_paused: None,
+        };
+
+        // Anything other than `name = value` at the start is the runtime expression.
+        let starts_with_option = input.peek(syn::Ident) && input.peek2(syn::Token![=]);
+        if !input.is_empty() && !starts_with_option {
+            args.runtime = Some(input.parse()?);
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        for opt in Punctuated::<Opt, syn::Token![,]>::parse_terminated(input)? {
+            let Opt { key, value } = opt;
+            if key == "start_paused" {
+                if args.start_paused.is_some() {
+                    let message = "the start_paused option can only be specified once";
+                    return Err(syn::Error::new_spanned(key, message));
+                }
+                match value {
+                    syn::Lit::Bool(value) => args.start_paused = Some(value),
+                    value => {
+                        let message = "the start_paused option must be `true` or `false`";
+                        return Err(syn::Error::new_spanned(value, message));
+                    }
+                }
+                continue;
+            }
+
+            let slot = if key == "threads" {
+                &mut args.threads
+            } else if key == "timeout_ms" {
+                &mut args.timeout_ms
+            } else if key == "iterations" {
+                &mut args.iterations
+            } else {
+                return Err(syn::Error::new_spanned(key, "unknown option"));
+            };
+
+            if slot.is_some() {
+                let message = format!("the {} option can only be specified once", key);
+                return Err(syn::Error::new_spanned(key, message));
+            }
+            let value = match value {
+                syn::Lit::Int(value) => value,
+                value => {
+                    let message = format!("the {} option must be an integer", key);
+                    return Err(syn::Error::new_spanned(value, message));
+                }
+            };
+            if value.base10_parse::<u64>()? == 0 && key != "timeout_ms" {
+                let message = format!("the {} option must be greater than zero", key);
+                return Err(syn::Error::new_spanned(value, message));
+            }
+            *slot = Some(value);
+        }
+
+        Ok(args)
+    }
+}
+
+/// A single `name = value` option.
+struct Opt {
+    key: syn::Ident,
+    value: syn::Lit,
+}
+
+impl Parse for Opt {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Opt { key, value })
+    }
+}
+
 /// Defines the async main function.
 ///
+/// The runtime to use can be passed as an argument, followed by these options:
+///
+/// - `threads = n`: give the runtime its own pool of `n` worker threads. Only runtimes that
+///   implement `runtime::raw::WorkerThreads` support it.
+///
+/// Other attributes on the function, such as `#[cfg]`, are applied to the generated `main`
+/// function. List them after `#[runtime::main]`, so they're passed to the macro.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -21,25 +192,44 @@ use syn::spanned::Spanned;
 ///     Ok(())
 /// }
 /// ```
+///
+/// ```ignore
+/// #[runtime::main(runtime_tokio::Tokio, threads = 4)]
+/// async fn main() -> std::io::Result<()> {
+///     Ok(())
+/// }
+/// ```
 #[cfg(not(test))] // NOTE: exporting main breaks tests, we should file an issue.
 #[proc_macro_attribute]
 pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let rt = if attr.is_empty() {
-        if cfg!(feature = "native") {
-            syn::parse_str("runtime::native::Native").unwrap()
-        } else {
-            let tokens = quote_spanned! { proc_macro2::Span::call_site() =>
-                compile_error!("async runtime needs to be specified if no default runtime is set");
-            };
-            return TokenStream::from(tokens);
-        }
-    } else {
-        syn::parse_macro_input!(attr as syn::Expr)
+    let args = syn::parse_macro_input!(attr as Args);
+    let unsupported = reject(
+        &args.timeout_ms,
+        "timeout_ms is only supported by #[runtime::test]",
+    )
+    .and_then(|_| {
+        reject(
+            &args.iterations,
+            "iterations is only supported by #[runtime::bench]",
+        )
+    })
+    .and_then(|_| {
+        reject(
+            &args.start_paused,
+            "start_paused is only supported by #[runtime::test]",
+        )
+    });
+    if let Err(tokens) = unsupported {
+        return tokens;
+    }
+    let rt = match args.runtime() {
+        Ok(rt) => args.sized(rt),
+        Err(tokens) => return tokens,
     };
     let input = syn::parse_macro_input!(item as syn::ItemFn);
 
     let ret = &input.sig.output;
-    let args = input.sig.inputs.iter();
+    let inputs = &input.sig.inputs;
     let name = &input.sig.ident;
     let body = &input.block;
     let attrs = &input.attrs;
@@ -52,22 +242,32 @@ pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     if input.sig.asyncness.is_none() {
-        let tokens = quote_spanned! { input.span() =>
+        let tokens = quote_spanned! { input.sig.fn_token.span() =>
           compile_error!("the async keyword is missing from the function declaration");
         };
         return TokenStream::from(tokens);
     }
 
+    if !inputs.is_empty() {
+        let tokens = quote_spanned! { inputs.span() =>
+          compile_error!("the main function cannot accept arguments");
+        };
+        return TokenStream::from(tokens);
+    }
+
+    // Attributes like `#[cfg]` are meant for the `main` function the program runs, so they go on
+    // the outer function rather than the async one it wraps.
     let result = quote! {
+        #(#attrs)*
         fn main() #ret {
-            #(#attrs)*
-            async fn main(#(#args),*) #ret {
+            async fn main() #ret {
                 #body
             }
 
-            runtime::raw::enter(#rt, async {
-                main().await
-            })
+            // The runtime lives for the rest of the program; leaking it is free for the unit
+            // structs most runtimes are.
+            let rt: &'static _ = Box::leak(Box::new(#rt));
+            runtime::raw::block_on(rt, main())
         }
     };
 
@@ -76,6 +276,19 @@ pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Creates an async unit test.
 ///
+/// The runtime to use can be passed as an argument, followed by these options:
+///
+/// - `threads = n`: give the runtime its own pool of `n` worker threads. Only runtimes that
+///   implement `runtime::raw::WorkerThreads` support it.
+/// - `timeout_ms = n`: fail the test if it hasn't completed after `n` milliseconds.
+/// - `start_paused = true`: run the test on a native runtime whose clock is paused, and jumps to
+///   the next timer's deadline whenever all tasks are idle. Delays complete as soon as nothing else
+///   is left to do, so tests that wait on timers run instantly and deterministically. A
+///   `timeout_ms` is measured against the paused clock too. This option selects the runtime, so
+///   it can't be combined with a runtime argument.
+///
+/// A test returning a `Result` fails with the `Debug` output of the error if it returns `Err`.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -84,19 +297,32 @@ pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     Ok(())
 /// }
 /// ```
+///
+/// ```ignore
+/// #[runtime::test(timeout_ms = 5000)]
+/// async fn main() -> std::io::Result<()> {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```ignore
+/// #[runtime::test(start_paused = true)]
+/// async fn main() {
+///     runtime::time::sleep(std::time::Duration::from_secs(3600)).await;
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let rt = if attr.is_empty() {
-        if cfg!(feature = "native") {
-            syn::parse_str("runtime::native::Native").unwrap()
-        } else {
-            let tokens = quote_spanned! { proc_macro2::Span::call_site() =>
-                compile_error!("async runtime needs to be specified if no default runtime is set");
-            };
-            return TokenStream::from(tokens);
-        }
-    } else {
-        syn::parse_macro_input!(attr as syn::Expr)
+    let args = syn::parse_macro_input!(attr as Args);
+    if let Err(tokens) = reject(
+        &args.iterations,
+        "iterations is only supported by #[runtime::bench]",
+    ) {
+        return tokens;
+    }
+    let rt = match args.runtime() {
+        Ok(rt) => args.sized(rt),
+        Err(tokens) => return tokens,
     };
     let input = syn::parse_macro_input!(item as syn::ItemFn);
 
@@ -106,18 +332,48 @@ pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attrs = &input.attrs;
 
     if input.sig.asyncness.is_none() {
-        let tokens = quote_spanned! { input.span() =>
+        let tokens = quote_spanned! { input.sig.fn_token.span() =>
           compile_error!("the async keyword is missing from the function declaration");
         };
         return TokenStream::from(tokens);
     }
 
-    let result = quote! {
-      #[test]
-      #(#attrs)*
-      fn #name() #ret {
-        runtime::raw::enter(#rt, async { #body })
-      }
+    let run = match args.timeout_ms {
+        Some(timeout_ms) => quote! {{
+            let timeout = std::time::Duration::from_millis(#timeout_ms);
+            let res = runtime::raw::enter(#rt, async move {
+                runtime::time::FutureExt::timeout(async { #body }, timeout).await
+            });
+            match res {
+                Ok(ret) => ret,
+                Err(_) => panic!("test timed out after {}ms", #timeout_ms),
+            }
+        }},
+        None => quote! {
+            runtime::raw::enter(#rt, async { #body })
+        },
+    };
+
+    // Tests returning a `Result` panic with the error's `Debug` output, so that they fail with a
+    // readable message whatever the error type is.
+    let result = match result_type(ret) {
+        Some(ty) => quote! {
+          #[test]
+          #(#attrs)*
+          fn #name() {
+            let res: #ty = #run;
+            if let Err(e) = res {
+                panic!("{:?}", e);
+            }
+          }
+        },
+        None => quote! {
+          #[test]
+          #(#attrs)*
+          fn #name() #ret {
+            #run
+          }
+        },
     };
 
     result.into()
@@ -125,6 +381,13 @@ pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Creates an async benchmark.
 ///
+/// The runtime to use can be passed as an argument, followed by these options:
+///
+/// - `threads = n`: give the runtime its own pool of `n` worker threads. Only runtimes that
+///   implement `runtime::raw::WorkerThreads` support it.
+/// - `iterations = n`: run the body `n` times per measured iteration, so that the cost of
+///   entering the runtime is spread out over the batch. The reported time covers the whole batch.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -134,23 +397,36 @@ pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// #[runtime::test]
 /// async fn spawn_and_await() {
-///   runtime::spawn(async {}).await;
+///   runtime::spawn(async {}).await.unwrap();
+/// }
+///
+/// #[runtime::bench(iterations = 100)]
+/// async fn spawn_and_await_batched() {
+///   runtime::spawn(async {}).await.unwrap();
 /// }
 /// ```
 #[proc_macro_attribute]
 pub fn bench(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let rt = if attr.is_empty() {
-        if cfg!(feature = "native") {
-            syn::parse_str("runtime::native::Native").unwrap()
-        } else {
-            let tokens = quote_spanned! { proc_macro2::Span::call_site() =>
-                compile_error!("async runtime needs to be specified if no default runtime is set");
-            };
-            return TokenStream::from(tokens);
-        }
-    } else {
-        syn::parse_macro_input!(attr as syn::Expr)
+    let args = syn::parse_macro_input!(attr as Args);
+    let unsupported = reject(
+        &args.timeout_ms,
+        "timeout_ms is only supported by #[runtime::test]",
+    )
+    .and_then(|_| {
+        reject(
+            &args.start_paused,
+            "start_paused is only supported by #[runtime::test]",
+        )
+    });
+    if let Err(tokens) = unsupported {
+        return tokens;
+    }
+    let rt = match args.runtime() {
+        Ok(rt) => args.sized(rt),
+        Err(tokens) => return tokens,
     };
+    let threads = args.threads.is_some();
+    let iterations = args.iterations;
     let input = syn::parse_macro_input!(item as syn::ItemFn);
 
     let args = &input.sig.inputs;
@@ -159,7 +435,7 @@ pub fn bench(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attrs = &input.attrs;
 
     if input.sig.asyncness.is_none() {
-        let tokens = quote_spanned! { input.span() =>
+        let tokens = quote_spanned! { input.sig.fn_token.span() =>
           compile_error!("the async keyword is missing from the function declaration");
         };
         return TokenStream::from(tokens);
@@ -172,12 +448,32 @@ pub fn bench(attr: TokenStream, item: TokenStream) -> TokenStream {
         return TokenStream::from(tokens);
     }
 
+    // A runtime with its own thread pool is only created once, rather than for every iteration.
+    let (setup, rt) = if threads {
+        (Some(quote! { let rt = #rt; }), quote! { rt.clone() })
+    } else {
+        (None, rt)
+    };
+    let run = match iterations {
+        Some(iterations) => quote! {
+            runtime::raw::enter(#rt, async {
+                for _ in 0..#iterations {
+                    let _ = async { #body }.await;
+                }
+            })
+        },
+        None => quote! {
+            let _ = runtime::raw::enter(#rt, async { #body });
+        },
+    };
+
     let result = quote! {
       #[bench]
       #(#attrs)*
       fn #name(b: &mut test::Bencher) {
+        #setup
         b.iter(|| {
-          let _ = runtime::raw::enter(#rt, async { #body });
+          #run
         });
       }
     };
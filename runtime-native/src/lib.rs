@@ -17,4 +17,4 @@ pub use wasm32::Native;
 #[cfg(not(target_arch = "wasm32"))]
 mod not_wasm32;
 #[cfg(not(target_arch = "wasm32"))]
-pub use not_wasm32::Native;
+pub use not_wasm32::{Native, NativeThreadPool, TestClock};
@@ -1,36 +1,106 @@
+use futures::executor::ThreadPool;
+use futures::future::BoxFuture;
 use futures::prelude::*;
-use futures::{future::BoxFuture, task::SpawnError};
-use futures_timer::{Delay as AsyncDelay, Interval as AsyncInterval};
+use futures::task::SpawnError;
+use futures_timer::Delay as AsyncDelay;
 use lazy_static::lazy_static;
 
 use std::io;
 use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
 use std::pin::Pin;
 use std::time::{Duration, Instant};
 
+mod blocking;
+mod evented;
 mod tcp;
+mod test_clock;
+mod thread_pool;
 mod time;
 mod udp;
+#[cfg(unix)]
+mod unix;
 
+use blocking::BlockingPool;
 use tcp::{TcpListener, TcpStream};
 use time::{Delay, Interval};
 use udp::UdpSocket;
+#[cfg(unix)]
+use unix::{UnixDatagram, UnixListener, UnixStream};
+
+pub use test_clock::TestClock;
+pub use thread_pool::NativeThreadPool;
 
 lazy_static! {
-    static ref JULIEX_THREADPOOL: juliex::ThreadPool = {
-        juliex::ThreadPool::with_setup(|| {
-            runtime_raw::set_runtime(&Native);
-        })
+    static ref NATIVE_WORKER_THREADS: usize =
+        runtime_raw::configured_worker_threads().unwrap_or_else(|| num_cpus::get() * 2);
+    static ref THREAD_POOL: ThreadPool = {
+        ThreadPool::builder()
+            .pool_size(*NATIVE_WORKER_THREADS)
+            .after_start(|_| {
+                runtime_raw::set_runtime(&Native);
+            })
+            .create()
+            .expect("cannot start the Native thread pool")
     };
+    static ref BLOCKING_POOL: BlockingPool = BlockingPool::new();
+    static ref TASK_COUNTERS: runtime_raw::TaskCounters = runtime_raw::TaskCounters::new();
 }
 
 /// The Native runtime.
+///
+/// Futures are polled on a thread pool shared by the whole process, with twice as many threads
+/// as there are CPUs unless configured otherwise through `runtime_raw::set_worker_threads` or the
+/// `RUNTIME_THREADS` environment variable. Use [`with_threads`] for a pool of a given size.
+///
+/// [`with_threads`]: #method.with_threads
 #[derive(Debug)]
 pub struct Native;
 
+impl Native {
+    /// Create a Native runtime with its own thread pool of `threads` worker threads.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `threads` is zero.
+    pub fn with_threads(threads: usize) -> NativeThreadPool {
+        NativeThreadPool::new(threads)
+    }
+}
+
+impl runtime_raw::WorkerThreads for Native {
+    type Runtime = NativeThreadPool;
+
+    fn with_worker_threads(&self, threads: usize) -> NativeThreadPool {
+        NativeThreadPool::new(threads)
+    }
+}
+
 impl runtime_raw::Runtime for Native {
     fn spawn_boxed(&self, fut: BoxFuture<'static, ()>) -> Result<(), SpawnError> {
-        JULIEX_THREADPOOL.spawn_boxed(fut.into());
+        THREAD_POOL.spawn_ok(TASK_COUNTERS.track(fut));
+        Ok(())
+    }
+
+    fn worker_threads(&self) -> Option<usize> {
+        Some(*NATIVE_WORKER_THREADS)
+    }
+
+    fn metrics(&self) -> runtime_raw::RuntimeMetrics {
+        runtime_raw::RuntimeMetrics {
+            num_workers: self.worker_threads(),
+            num_spawned_tasks: Some(TASK_COUNTERS.spawned()),
+            num_pending_tasks: Some(TASK_COUNTERS.pending()),
+        }
+    }
+
+    fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        TASK_COUNTERS.wait_idle()
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) -> Result<(), SpawnError> {
+        BLOCKING_POOL.spawn(f);
         Ok(())
     }
 
@@ -51,16 +121,113 @@ impl runtime_raw::Runtime for Native {
         &self,
         addr: &SocketAddr,
     ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
-        let romio_listener = romio::TcpListener::bind(&addr)?;
-        Ok(Box::pin(TcpListener { romio_listener }))
+        Ok(Box::pin(TcpListener::bind(addr)?))
+    }
+
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        addr: &SocketAddr,
+        backlog: i32,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener(addr, backlog)?;
+        Ok(Box::pin(TcpListener::from_std(listener)?))
+    }
+
+    fn bind_tcp_listener_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener_reuseport(addr)?;
+        Ok(Box::pin(TcpListener::from_std(listener)?))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn bind_tcp_listener_device(
+        &self,
+        addr: &SocketAddr,
+        interface: &str,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener_device(addr, interface)?;
+        Ok(Box::pin(TcpListener::from_std(listener)?))
+    }
+
+    fn tcp_listener_from_std(
+        &self,
+        listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Ok(Box::pin(TcpListener::from_std(listener)?))
     }
 
     fn bind_udp_socket(
         &self,
         addr: &SocketAddr,
     ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
-        let romio_socket = romio::UdpSocket::bind(&addr)?;
-        Ok(Box::pin(UdpSocket { romio_socket }))
+        Ok(Box::pin(UdpSocket::bind(addr)?))
+    }
+
+    fn udp_socket_from_std(
+        &self,
+        socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Ok(Box::pin(UdpSocket::from_std(socket)?))
+    }
+
+    fn bind_udp_socket_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        let socket = udp::bind_std_socket_reuseport(addr)?;
+        self.udp_socket_from_std(socket)
+    }
+
+    #[cfg(unix)]
+    fn connect_unix_stream(
+        &self,
+        path: &Path,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::UnixStream>>>> {
+        let romio_connect = romio::uds::UnixStream::connect(path);
+        let connect = romio_connect.map(|res| {
+            res.map(|romio_stream| {
+                Box::pin(UnixStream { romio_stream }) as Pin<Box<dyn runtime_raw::UnixStream>>
+            })
+        });
+        connect.boxed()
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_listener(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixListener>>> {
+        let romio_listener = romio::uds::UnixListener::bind(path)?;
+        Ok(Box::pin(UnixListener { romio_listener }))
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_datagram(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Ok(Box::pin(UnixDatagram::bind(path)?))
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_pair(
+        &self,
+    ) -> io::Result<(
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+    )> {
+        let (a, b) = UnixDatagram::pair()?;
+        Ok((Box::pin(a), Box::pin(b)))
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_from_std(
+        &self,
+        socket: std::os::unix::net::UnixDatagram,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Ok(Box::pin(UnixDatagram::from_std(socket)?))
     }
 
     fn new_delay(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Delay>> {
@@ -74,7 +241,10 @@ impl runtime_raw::Runtime for Native {
     }
 
     fn new_interval(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
-        let async_interval = AsyncInterval::new(dur);
-        Box::pin(Interval { async_interval })
+        Box::pin(Interval::new(self.now() + dur, dur))
+    }
+
+    fn new_interval_at(&self, at: Instant, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Box::pin(Interval::new(at, dur))
     }
 }
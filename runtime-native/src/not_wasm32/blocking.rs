@@ -0,0 +1,74 @@
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The maximum number of threads running blocking functions at the same time.
+const MAX_THREADS: usize = 512;
+
+/// How long an idle thread waits for new work before exiting.
+const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+/// A thread pool for blocking functions, separate from the executor's threads.
+///
+/// Threads are spawned on demand and exit after having been idle for a while.
+pub(crate) struct BlockingPool {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<Job>,
+    threads: usize,
+    idle: usize,
+}
+
+impl BlockingPool {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            condvar: Condvar::new(),
+        }
+    }
+
+    pub(crate) fn spawn(&'static self, job: Job) {
+        let mut state = self.state.lock().unwrap();
+        state.queue.push_back(job);
+        if state.idle >= state.queue.len() {
+            self.condvar.notify_one();
+        } else if state.threads < MAX_THREADS {
+            state.threads += 1;
+            thread::Builder::new()
+                .name("runtime-blocking".into())
+                .spawn(move || self.run())
+                .expect("cannot spawn a blocking thread");
+        }
+    }
+
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                // A panicking job drops its result sender, which is how the caller learns about it.
+                let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state.idle += 1;
+            let (guard, timeout) = self.condvar.wait_timeout(state, KEEP_ALIVE).unwrap();
+            state = guard;
+            state.idle -= 1;
+
+            if timeout.timed_out() && state.queue.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
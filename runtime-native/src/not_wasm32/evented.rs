@@ -0,0 +1,51 @@
+use futures::ready;
+use mio::Evented;
+use romio::raw::PollEvented;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Run a nonblocking read operation once `io` is readable.
+///
+/// If the operation would block, the readiness is cleared so the task is woken up the next time
+/// `io` becomes readable.
+pub(crate) fn poll_read_with<E, T>(
+    io: &mut PollEvented<E>,
+    cx: &mut Context<'_>,
+    op: impl FnOnce(&E) -> io::Result<T>,
+) -> Poll<io::Result<T>>
+where
+    E: Evented,
+{
+    ready!(Pin::new(&mut *io).poll_read_ready(cx))?;
+    match op(io.get_ref()) {
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+            Pin::new(io).clear_read_ready(cx)?;
+            Poll::Pending
+        }
+        res => Poll::Ready(res),
+    }
+}
+
+/// Run a nonblocking write operation once `io` is writable.
+///
+/// If the operation would block, the readiness is cleared so the task is woken up the next time
+/// `io` becomes writable.
+pub(crate) fn poll_write_with<E, T>(
+    io: &mut PollEvented<E>,
+    cx: &mut Context<'_>,
+    op: impl FnOnce(&E) -> io::Result<T>,
+) -> Poll<io::Result<T>>
+where
+    E: Evented,
+{
+    ready!(io.poll_write_ready(cx))?;
+    match op(io.get_ref()) {
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+            Pin::new(io).clear_write_ready(cx)?;
+            Poll::Pending
+        }
+        res => Poll::Ready(res),
+    }
+}
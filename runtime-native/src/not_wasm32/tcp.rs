@@ -1,10 +1,16 @@
 use futures::prelude::*;
-use romio::raw::{AsyncReadReady, AsyncReady, AsyncWriteReady};
+use futures::ready;
+use romio::raw::{AsyncReadReady, AsyncWriteReady, PollEvented};
+
+use std::convert::TryFrom;
 
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
+
+use super::evented::poll_read_with;
 
 #[derive(Debug)]
 pub(crate) struct TcpStream {
@@ -13,7 +19,25 @@ pub(crate) struct TcpStream {
 
 #[derive(Debug)]
 pub(crate) struct TcpListener {
-    pub romio_listener: romio::tcp::TcpListener,
+    io: PollEvented<mio::net::TcpListener>,
+}
+
+impl TcpListener {
+    pub(crate) fn bind(addr: &SocketAddr) -> io::Result<Self> {
+        let listener = mio::net::TcpListener::bind(addr)?;
+        Ok(TcpListener {
+            io: PollEvented::new(listener),
+        })
+    }
+
+    /// Register a standard library listener with the reactor. romio's own listener can only be
+    /// created by binding an address, so the accept loop is implemented here.
+    pub(crate) fn from_std(listener: std::net::TcpListener) -> io::Result<Self> {
+        let listener = mio::net::TcpListener::from_std(listener)?;
+        Ok(TcpListener {
+            io: PollEvented::new(listener),
+        })
+    }
 }
 
 impl runtime_raw::TcpStream for TcpStream {
@@ -30,7 +54,7 @@ impl runtime_raw::TcpStream for TcpStream {
     }
 
     fn take_error(&self) -> io::Result<Option<io::Error>> {
-        Ok(None)
+        socket2::SockRef::from(&self.romio_stream).take_error()
     }
 
     fn local_addr(&self) -> io::Result<SocketAddr> {
@@ -45,6 +69,44 @@ impl runtime_raw::TcpStream for TcpStream {
         self.romio_stream.shutdown(how)
     }
 
+    fn nodelay(&self) -> io::Result<bool> {
+        self.romio_stream.nodelay()
+    }
+
+    fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.romio_stream.set_nodelay(nodelay)
+    }
+
+    fn ttl(&self) -> io::Result<u32> {
+        self.romio_stream.ttl()
+    }
+
+    fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.romio_stream.set_ttl(ttl)
+    }
+
+    fn tos(&self) -> io::Result<u32> {
+        socket2::SockRef::from(&self.romio_stream).tos()
+    }
+
+    fn set_tos(&self, tos: u32) -> io::Result<()> {
+        socket2::SockRef::from(&self.romio_stream).set_tos(tos)
+    }
+
+    fn linger(&self) -> io::Result<Option<Duration>> {
+        self.romio_stream.linger()
+    }
+
+    fn set_linger(&self, dur: Option<Duration>) -> io::Result<()> {
+        self.romio_stream.set_linger(dur)
+    }
+
+    fn try_clone(&self) -> io::Result<Pin<Box<dyn runtime_raw::TcpStream>>> {
+        let socket = socket2::SockRef::from(&self.romio_stream).try_clone()?;
+        let romio_stream = romio::TcpStream::try_from(std::net::TcpStream::from(socket))?;
+        Ok(Box::pin(TcpStream { romio_stream }))
+    }
+
     #[cfg(unix)]
     fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
         use std::os::unix::io::AsRawFd;
@@ -56,9 +118,9 @@ impl AsyncRead for TcpStream {
     fn poll_read(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
-        mut buf: &mut [u8],
+        buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
-        Pin::new(&mut self.romio_stream).poll_read(cx, &mut buf)
+        Pin::new(&mut self.romio_stream).poll_read(cx, buf)
     }
 }
 
@@ -68,7 +130,7 @@ impl AsyncWrite for TcpStream {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        Pin::new(&mut self.romio_stream).poll_write(cx, &buf)
+        Pin::new(&mut self.romio_stream).poll_write(cx, buf)
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
@@ -82,24 +144,89 @@ impl AsyncWrite for TcpStream {
 
 impl runtime_raw::TcpListener for TcpListener {
     fn local_addr(&self) -> io::Result<SocketAddr> {
-        self.romio_listener.local_addr()
+        self.io.get_ref().local_addr()
+    }
+
+    fn ttl(&self) -> io::Result<u32> {
+        self.io.get_ref().ttl()
+    }
+
+    fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.io.get_ref().set_ttl(ttl)
     }
 
     fn poll_accept(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
-    ) -> Poll<io::Result<Pin<Box<dyn runtime_raw::TcpStream>>>> {
-        Pin::new(&mut self.romio_listener)
-            .poll_ready(cx)
-            .map_ok(|(romio_stream, _)| {
-                Box::pin(TcpStream { romio_stream }) as Pin<Box<dyn runtime_raw::TcpStream>>
-            })
+    ) -> Poll<io::Result<(Pin<Box<dyn runtime_raw::TcpStream>>, SocketAddr)>> {
+        let (std_stream, addr) = ready!(poll_read_with(&mut self.io, cx, |listener| {
+            listener.accept_std()
+        }))?;
+        let romio_stream = romio::TcpStream::try_from(std_stream)?;
+        let stream = Box::pin(TcpStream { romio_stream });
+        Poll::Ready(Ok((stream as Pin<Box<dyn runtime_raw::TcpStream>>, addr)))
     }
 
     /// Extracts the raw file descriptor.
     #[cfg(unix)]
     fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
         use std::os::unix::io::AsRawFd;
-        self.romio_listener.as_raw_fd()
+        self.io.get_ref().as_raw_fd()
     }
 }
+
+/// Bind a standard library listener, using `backlog` as the size of the pending connection queue.
+pub(crate) fn bind_std_listener(
+    addr: &SocketAddr,
+    backlog: i32,
+) -> io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(*addr), Type::STREAM, None)?;
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&(*addr).into())?;
+    socket.listen(backlog)?;
+    Ok(socket.into())
+}
+
+/// Bind a standard library listener with `SO_REUSEADDR` and, where the platform supports it,
+/// `SO_REUSEPORT` set.
+pub(crate) fn bind_std_listener_reuseport(addr: &SocketAddr) -> io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(*addr), Type::STREAM, None)?;
+    set_reuseport(&socket)?;
+    socket.bind(&(*addr).into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
+
+/// Set `SO_REUSEADDR` on a socket that hasn't been bound yet, and `SO_REUSEPORT` where the
+/// platform supports it.
+pub(crate) fn set_reuseport(socket: &socket2::Socket) -> io::Result<()> {
+    socket.set_reuse_address(true)?;
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    socket.set_reuse_port(true)?;
+    Ok(())
+}
+
+/// Bind a standard library listener to the network interface named `interface`, by setting
+/// `SO_BINDTODEVICE` before binding to `addr`.
+///
+/// Like the listeners created by `bind`, the socket has `SO_REUSEADDR` set, so the address can be
+/// bound again right after a previous listener on it was closed.
+#[cfg(target_os = "linux")]
+pub(crate) fn bind_std_listener_device(
+    addr: &SocketAddr,
+    interface: &str,
+) -> io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(*addr), Type::STREAM, None)?;
+    socket.set_reuse_address(true)?;
+    socket.bind_device(Some(interface.as_bytes()))?;
+    socket.bind(&(*addr).into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
@@ -0,0 +1,545 @@
+use futures::future::{BoxFuture, LocalBoxFuture};
+use futures::prelude::*;
+use futures::task::{waker_ref, ArcWake, Context, Poll, SpawnError, Waker};
+use runtime_raw::{InstantTick, MissedTickBehavior, Runtime};
+
+use std::collections::BTreeMap;
+use std::io;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::Native;
+
+/// The Native runtime, with a clock that only moves forward when it's advanced.
+///
+/// Futures run on the Native thread pool and do I/O through it, but the delays and intervals they
+/// create measure time against the test clock instead of the system clock. Calling [`advance`]
+/// moves the clock forward and completes the delays whose deadlines it passes, so time-based logic
+/// can be tested without waiting for real time to pass.
+///
+/// A runtime created with [`start_paused`] also advances the clock by itself: whenever all of its
+/// tasks are idle, the clock jumps forward to the next deadline. Timer-heavy code then runs as
+/// fast as it can be polled, and always observes the same sequence of instants.
+///
+/// Clones share the same clock.
+///
+/// [`advance`]: #method.advance
+/// [`start_paused`]: #method.start_paused
+///
+/// # Examples
+///
+/// ```
+/// use runtime_native::TestClock;
+/// use runtime_raw::Runtime;
+/// use std::time::Duration;
+///
+/// let clock = TestClock::new();
+/// let handle = clock.clone();
+/// runtime_raw::enter(clock, async move {
+///     let start = handle.now();
+///     let delay = handle.new_delay(Duration::from_secs(10));
+///     handle.advance(Duration::from_secs(10));
+///     assert_eq!(delay.await, start + Duration::from_secs(10));
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct TestClock {
+    clock: Arc<Clock>,
+}
+
+#[derive(Debug)]
+struct Clock {
+    state: Mutex<State>,
+    /// A `'static` handle to the runtime, which is leaked the first time a future is spawned.
+    runtime: Mutex<Option<&'static TestClock>>,
+    /// Whether the clock advances to the next deadline by itself once every task is idle.
+    auto_advance: bool,
+    /// The number of tasks that are being polled or have been woken up, plus the number of
+    /// blocking functions that are running. The runtime is idle when this drops to zero.
+    active: AtomicUsize,
+}
+
+impl Clock {
+    /// Decrement the number of active tasks, and advance the clock to the next deadline if it
+    /// drops to zero and the clock advances by itself.
+    fn release(&self) {
+        if self.active.fetch_sub(1, Ordering::SeqCst) != 1 || !self.auto_advance {
+            return;
+        }
+        let due = {
+            let mut state = self.state.lock().unwrap();
+            let next = match state.timers.keys().next() {
+                Some(&(deadline, _)) => deadline,
+                None => return,
+            };
+            if next > state.now {
+                state.now = next;
+            }
+            state.take_due()
+        };
+        for waker in due.values() {
+            waker.wake_by_ref();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    now: Instant,
+    /// The wakers of the pending timers, ordered by deadline.
+    timers: BTreeMap<(Instant, u64), Waker>,
+    next_id: u64,
+}
+
+impl State {
+    /// Remove the timers that are due at the current time, and return their wakers.
+    fn take_due(&mut self) -> BTreeMap<(Instant, u64), Waker> {
+        let later = self.timers.split_off(&(self.now, u64::MAX));
+        std::mem::replace(&mut self.timers, later)
+    }
+}
+
+impl TestClock {
+    /// Create a runtime whose clock starts at the current time, and stays there until advanced.
+    pub fn new() -> Self {
+        Self::with_auto_advance(false)
+    }
+
+    /// Create a runtime whose clock starts at the current time, and advances to the next deadline
+    /// whenever all of its tasks are idle.
+    ///
+    /// A task waiting for I/O counts as idle, so the clock may advance while a read or a write is
+    /// in progress. This is the runtime used by `#[runtime::test(start_paused = true)]`.
+    pub fn start_paused() -> Self {
+        Self::with_auto_advance(true)
+    }
+
+    fn with_auto_advance(auto_advance: bool) -> Self {
+        let state = State {
+            now: Instant::now(),
+            timers: BTreeMap::new(),
+            next_id: 0,
+        };
+        TestClock {
+            clock: Arc::new(Clock {
+                state: Mutex::new(state),
+                runtime: Mutex::new(None),
+                auto_advance,
+                active: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Get the current time of the clock.
+    pub fn now(&self) -> Instant {
+        self.clock.state.lock().unwrap().now
+    }
+
+    /// Move the clock forward by `dur`, waking up the delays and intervals that are due by then.
+    pub fn advance(&self, dur: Duration) {
+        let due = {
+            let mut state = self.clock.state.lock().unwrap();
+            state.now += dur;
+            state.take_due()
+        };
+        for waker in due.values() {
+            waker.wake_by_ref();
+        }
+    }
+
+    fn timer(&self, deadline: Instant) -> Timer {
+        let mut state = self.clock.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        Timer {
+            clock: self.clock.clone(),
+            deadline,
+            id,
+        }
+    }
+
+    /// Get a `'static` handle to the runtime, for it to be set as the current runtime.
+    fn as_static(&self) -> &'static TestClock {
+        let mut runtime = self.clock.runtime.lock().unwrap();
+        runtime.get_or_insert_with(|| Box::leak(Box::new(self.clone())))
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A future polled with the test clock set as the current runtime, so that the delays it creates
+/// use the test clock. It's counted as active while it's being polled or has been woken up.
+struct Task<F> {
+    runtime: &'static TestClock,
+    waker: Arc<TaskWaker>,
+    fut: F,
+}
+
+impl<F> Task<F> {
+    fn new(runtime: &'static TestClock, fut: F) -> Self {
+        // The task is active until it's polled for the first time.
+        runtime.clock.active.fetch_add(1, Ordering::SeqCst);
+        let waker = TaskWaker {
+            clock: runtime.clock.clone(),
+            scheduled: AtomicBool::new(true),
+            waker: Mutex::new(None),
+        };
+        Task {
+            runtime,
+            waker: Arc::new(waker),
+            fut,
+        }
+    }
+}
+
+impl<F: Future + Unpin> Future for Task<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        {
+            let mut waker = this.waker.waker.lock().unwrap();
+            match &*waker {
+                Some(waker) if waker.will_wake(cx.waker()) => {}
+                _ => *waker = Some(cx.waker().clone()),
+            }
+        }
+
+        // The task stays active while it's being polled, using up the count of the wake-up that
+        // led to this poll, if any.
+        if !this.waker.scheduled.swap(false, Ordering::SeqCst) {
+            this.runtime.clock.active.fetch_add(1, Ordering::SeqCst);
+        }
+        let waker = waker_ref(&this.waker);
+        let mut cx = Context::from_waker(&waker);
+        let fut = &mut this.fut;
+        let res = runtime_raw::with_runtime(this.runtime, || fut.poll_unpin(&mut cx));
+        if res.is_ready() {
+            this.waker.finish();
+        }
+        this.runtime.clock.release();
+        res
+    }
+}
+
+impl<F> Drop for Task<F> {
+    fn drop(&mut self) {
+        self.waker.finish();
+    }
+}
+
+/// Wakes up a task, counting it as active until it's polled.
+struct TaskWaker {
+    clock: Arc<Clock>,
+    /// Whether the task has been woken up since it was last polled.
+    scheduled: AtomicBool,
+    /// The executor's waker, or `None` if the task hasn't been polled yet or has completed.
+    waker: Mutex<Option<Waker>>,
+}
+
+impl TaskWaker {
+    /// Stop counting the task as active, now that it's never going to be polled again.
+    fn finish(&self) {
+        self.waker.lock().unwrap().take();
+        if self.scheduled.swap(false, Ordering::SeqCst) {
+            self.clock.release();
+        }
+    }
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        let waker = arc_self.waker.lock().unwrap();
+        if let Some(waker) = &*waker {
+            if !arc_self.scheduled.swap(true, Ordering::SeqCst) {
+                arc_self.clock.active.fetch_add(1, Ordering::SeqCst);
+            }
+            waker.wake_by_ref();
+        }
+    }
+}
+
+/// Counts a blocking function as active while it runs.
+struct Blocking {
+    clock: Arc<Clock>,
+}
+
+impl Drop for Blocking {
+    fn drop(&mut self) {
+        self.clock.release();
+    }
+}
+
+/// A deadline on the test clock.
+#[derive(Debug)]
+struct Timer {
+    clock: Arc<Clock>,
+    deadline: Instant,
+    id: u64,
+}
+
+impl Timer {
+    /// Returns the current time once the deadline has passed, registering the waker until then.
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        let mut state = self.clock.state.lock().unwrap();
+        if state.now >= self.deadline {
+            state.timers.remove(&(self.deadline, self.id));
+            return Poll::Ready(state.now);
+        }
+        let waker = state
+            .timers
+            .entry((self.deadline, self.id))
+            .or_insert_with(|| cx.waker().clone());
+        if !waker.will_wake(cx.waker()) {
+            *waker = cx.waker().clone();
+        }
+        Poll::Pending
+    }
+
+    fn now(&self) -> Instant {
+        self.clock.state.lock().unwrap().now
+    }
+
+    fn reset(&mut self, deadline: Instant) {
+        self.clock
+            .state
+            .lock()
+            .unwrap()
+            .timers
+            .remove(&(self.deadline, self.id));
+        self.deadline = deadline;
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.clock.state.lock() {
+            state.timers.remove(&(self.deadline, self.id));
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Delay {
+    timer: Timer,
+}
+
+impl runtime_raw::Delay for Delay {}
+
+impl Future for Delay {
+    type Output = Instant;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.timer.poll(cx)
+    }
+}
+
+#[derive(Debug)]
+struct Interval {
+    timer: Timer,
+    period: Duration,
+    missed_tick_behavior: MissedTickBehavior,
+}
+
+impl runtime_raw::Interval for Interval {
+    fn set_missed_tick_behavior(mut self: Pin<&mut Self>, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    fn reset(mut self: Pin<&mut Self>) {
+        let deadline = self.timer.now() + self.period;
+        self.timer.reset(deadline);
+    }
+
+    fn poll_tick(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<InstantTick>> {
+        let now = futures::ready!(self.timer.poll(cx));
+        let deadline = self.timer.deadline;
+        let tick = InstantTick::new(deadline, now, self.period);
+        let next = self
+            .missed_tick_behavior
+            .next_deadline(deadline, now, self.period);
+        self.timer.reset(next);
+        Poll::Ready(Ok(tick))
+    }
+}
+
+impl Stream for Interval {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        runtime_raw::Interval::poll_tick(self, cx).map(|tick| tick.ok().map(|tick| tick.when))
+    }
+}
+
+impl Runtime for TestClock {
+    fn spawn_boxed(&self, fut: BoxFuture<'static, ()>) -> Result<(), SpawnError> {
+        let runtime = self.as_static();
+        Native.spawn_boxed(Task::new(runtime, fut).boxed())
+    }
+
+    fn block_on_boxed(&'static self, fut: LocalBoxFuture<'_, ()>) {
+        futures::executor::block_on(Task::new(self, fut))
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) -> Result<(), SpawnError> {
+        self.clock.active.fetch_add(1, Ordering::SeqCst);
+        let blocking = Blocking {
+            clock: self.clock.clone(),
+        };
+        Native.spawn_blocking(Box::new(move || {
+            let _blocking = blocking;
+            f()
+        }))
+    }
+
+    fn metrics(&self) -> runtime_raw::RuntimeMetrics {
+        Native.metrics()
+    }
+
+    fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        Native.wait_idle()
+    }
+
+    fn now(&self) -> Instant {
+        TestClock::now(self)
+    }
+
+    fn connect_tcp_stream(
+        &self,
+        addr: &SocketAddr,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::TcpStream>>>> {
+        Native.connect_tcp_stream(addr)
+    }
+
+    fn bind_tcp_listener(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener(addr)
+    }
+
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        addr: &SocketAddr,
+        backlog: i32,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener_with_backlog(addr, backlog)
+    }
+
+    fn bind_tcp_listener_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener_reuseport(addr)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn bind_tcp_listener_device(
+        &self,
+        addr: &SocketAddr,
+        interface: &str,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener_device(addr, interface)
+    }
+
+    fn tcp_listener_from_std(
+        &self,
+        listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.tcp_listener_from_std(listener)
+    }
+
+    fn bind_udp_socket(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Native.bind_udp_socket(addr)
+    }
+
+    fn udp_socket_from_std(
+        &self,
+        socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Native.udp_socket_from_std(socket)
+    }
+
+    fn bind_udp_socket_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Native.bind_udp_socket_reuseport(addr)
+    }
+
+    #[cfg(unix)]
+    fn connect_unix_stream(
+        &self,
+        path: &Path,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::UnixStream>>>> {
+        Native.connect_unix_stream(path)
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_listener(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixListener>>> {
+        Native.bind_unix_listener(path)
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_datagram(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Native.bind_unix_datagram(path)
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_pair(
+        &self,
+    ) -> io::Result<(
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+    )> {
+        Native.unix_datagram_pair()
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_from_std(
+        &self,
+        socket: std::os::unix::net::UnixDatagram,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Native.unix_datagram_from_std(socket)
+    }
+
+    fn new_delay(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Delay>> {
+        self.new_delay_at(TestClock::now(self) + dur)
+    }
+
+    fn new_delay_at(&self, at: Instant) -> Pin<Box<dyn runtime_raw::Delay>> {
+        Box::pin(Delay {
+            timer: self.timer(at),
+        })
+    }
+
+    fn new_interval(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        self.new_interval_at(TestClock::now(self) + dur, dur)
+    }
+
+    fn new_interval_at(&self, at: Instant, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Box::pin(Interval {
+            timer: self.timer(at),
+            period: dur,
+            missed_tick_behavior: MissedTickBehavior::default(),
+        })
+    }
+}
@@ -0,0 +1,223 @@
+use futures::executor::ThreadPool;
+use futures::future::BoxFuture;
+use futures::task::SpawnError;
+use runtime_raw::Runtime;
+
+use std::io;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::Native;
+
+/// The Native runtime, with a fixed number of worker threads.
+///
+/// Unlike [`Native`], which shares a single global thread pool, every `NativeThreadPool` created
+/// with [`Native::with_threads`] starts its own pool the first time a future is spawned on it.
+/// I/O, timers and blocking functions are handled by the Native runtime. Clones share the same
+/// pool.
+///
+/// [`Native`]: struct.Native.html
+/// [`Native::with_threads`]: struct.Native.html#method.with_threads
+#[derive(Debug, Clone)]
+pub struct NativeThreadPool {
+    threads: usize,
+    pool: Arc<Mutex<Option<ThreadPool>>>,
+    tasks: runtime_raw::TaskCounters,
+}
+
+impl NativeThreadPool {
+    /// Create a runtime with `threads` worker threads.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `threads` is zero.
+    pub fn new(threads: usize) -> Self {
+        assert!(
+            threads > 0,
+            "the number of worker threads must be greater than zero"
+        );
+        Self {
+            threads,
+            pool: Arc::new(Mutex::new(None)),
+            tasks: runtime_raw::TaskCounters::new(),
+        }
+    }
+}
+
+impl runtime_raw::WorkerThreads for NativeThreadPool {
+    type Runtime = NativeThreadPool;
+
+    fn with_worker_threads(&self, threads: usize) -> NativeThreadPool {
+        NativeThreadPool::new(threads)
+    }
+}
+
+impl Runtime for NativeThreadPool {
+    fn spawn_boxed(&self, fut: BoxFuture<'static, ()>) -> Result<(), SpawnError> {
+        let mut pool = self.pool.lock().unwrap();
+        if pool.is_none() {
+            // The worker threads need a `'static` reference to the runtime, so a handle to the
+            // pool is leaked once when it's started.
+            let this: &'static NativeThreadPool = Box::leak(Box::new(self.clone()));
+            let started = ThreadPool::builder()
+                .pool_size(self.threads)
+                .after_start(move |_| {
+                    runtime_raw::set_runtime(this);
+                })
+                .create()
+                .map_err(|_| SpawnError::shutdown())?;
+            *pool = Some(started);
+        }
+
+        pool.as_ref().unwrap().spawn_ok(self.tasks.track(fut));
+        Ok(())
+    }
+
+    fn worker_threads(&self) -> Option<usize> {
+        Some(self.threads)
+    }
+
+    fn metrics(&self) -> runtime_raw::RuntimeMetrics {
+        runtime_raw::RuntimeMetrics {
+            num_workers: self.worker_threads(),
+            num_spawned_tasks: Some(self.tasks.spawned()),
+            num_pending_tasks: Some(self.tasks.pending()),
+        }
+    }
+
+    fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        self.tasks.wait_idle()
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) -> Result<(), SpawnError> {
+        Native.spawn_blocking(f)
+    }
+
+    fn connect_tcp_stream(
+        &self,
+        addr: &SocketAddr,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::TcpStream>>>> {
+        Native.connect_tcp_stream(addr)
+    }
+
+    fn bind_tcp_listener(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener(addr)
+    }
+
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        addr: &SocketAddr,
+        backlog: i32,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener_with_backlog(addr, backlog)
+    }
+
+    fn bind_tcp_listener_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener_reuseport(addr)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn bind_tcp_listener_device(
+        &self,
+        addr: &SocketAddr,
+        interface: &str,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.bind_tcp_listener_device(addr, interface)
+    }
+
+    fn tcp_listener_from_std(
+        &self,
+        listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Native.tcp_listener_from_std(listener)
+    }
+
+    fn bind_udp_socket(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Native.bind_udp_socket(addr)
+    }
+
+    fn udp_socket_from_std(
+        &self,
+        socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Native.udp_socket_from_std(socket)
+    }
+
+    fn bind_udp_socket_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Native.bind_udp_socket_reuseport(addr)
+    }
+
+    #[cfg(unix)]
+    fn connect_unix_stream(
+        &self,
+        path: &Path,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::UnixStream>>>> {
+        Native.connect_unix_stream(path)
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_listener(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixListener>>> {
+        Native.bind_unix_listener(path)
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_datagram(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Native.bind_unix_datagram(path)
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_pair(
+        &self,
+    ) -> io::Result<(
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+    )> {
+        Native.unix_datagram_pair()
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_from_std(
+        &self,
+        socket: std::os::unix::net::UnixDatagram,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Native.unix_datagram_from_std(socket)
+    }
+
+    fn new_delay(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Delay>> {
+        Native.new_delay(dur)
+    }
+
+    fn new_delay_at(&self, at: Instant) -> Pin<Box<dyn runtime_raw::Delay>> {
+        Native.new_delay_at(at)
+    }
+
+    fn new_interval(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Native.new_interval(dur)
+    }
+
+    fn new_interval_at(&self, at: Instant, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Native.new_interval_at(at, dur)
+    }
+}
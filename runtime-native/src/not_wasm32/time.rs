@@ -1,9 +1,13 @@
+use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use futures::prelude::*;
-use futures_timer::{Delay as AsyncDelay, Interval as AsyncInterval};
+use futures_timer::Delay as AsyncDelay;
+use runtime_raw::{InstantTick, MissedTickBehavior, Runtime};
+
+use super::Native;
 
 #[derive(Debug)]
 pub(crate) struct Delay {
@@ -24,17 +28,50 @@ impl Future for Delay {
 
 #[derive(Debug)]
 pub(crate) struct Interval {
-    pub(crate) async_interval: AsyncInterval,
+    async_delay: AsyncDelay,
+    deadline: Instant,
+    period: Duration,
+    missed_tick_behavior: MissedTickBehavior,
 }
 
-impl runtime_raw::Interval for Interval {}
+impl Interval {
+    pub(crate) fn new(at: Instant, period: Duration) -> Self {
+        Self {
+            async_delay: AsyncDelay::new_at(at),
+            deadline: at,
+            period,
+            missed_tick_behavior: MissedTickBehavior::default(),
+        }
+    }
+}
+
+impl runtime_raw::Interval for Interval {
+    fn set_missed_tick_behavior(mut self: Pin<&mut Self>, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    fn reset(mut self: Pin<&mut Self>) {
+        self.deadline = Native.now() + self.period;
+        self.async_delay = AsyncDelay::new_at(self.deadline);
+    }
+
+    fn poll_tick(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<InstantTick>> {
+        futures::ready!(Pin::new(&mut self.async_delay).poll(cx))?;
+        let now = Instant::now();
+        let tick = InstantTick::new(self.deadline, now, self.period);
+        self.deadline = self
+            .missed_tick_behavior
+            .next_deadline(self.deadline, now, self.period);
+        self.async_delay = AsyncDelay::new_at(self.deadline);
+        Poll::Ready(Ok(tick))
+    }
+}
 
 impl Stream for Interval {
     type Item = Instant;
 
     #[inline]
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        futures::ready!(Pin::new(&mut self.async_interval).poll_next(cx)).unwrap();
-        Poll::Ready(Some(Instant::now()))
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        runtime_raw::Interval::poll_tick(self, cx).map(|tick| tick.ok().map(|tick| tick.when))
     }
 }
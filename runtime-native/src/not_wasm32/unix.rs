@@ -0,0 +1,189 @@
+use futures::prelude::*;
+use romio::raw::{AsyncReady, PollEvented};
+
+use std::io;
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::evented::{poll_read_with, poll_write_with};
+
+#[derive(Debug)]
+pub(crate) struct UnixStream {
+    pub romio_stream: romio::uds::UnixStream,
+}
+
+#[derive(Debug)]
+pub(crate) struct UnixListener {
+    pub romio_listener: romio::uds::UnixListener,
+}
+
+#[derive(Debug)]
+pub(crate) struct UnixDatagram {
+    io: PollEvented<mio_uds::UnixDatagram>,
+}
+
+impl UnixDatagram {
+    pub(crate) fn bind(path: &Path) -> io::Result<Self> {
+        mio_uds::UnixDatagram::bind(path).map(Self::new)
+    }
+
+    pub(crate) fn pair() -> io::Result<(Self, Self)> {
+        let (a, b) = mio_uds::UnixDatagram::pair()?;
+        Ok((Self::new(a), Self::new(b)))
+    }
+
+    /// Register a standard library socket with the reactor.
+    pub(crate) fn from_std(socket: std::os::unix::net::UnixDatagram) -> io::Result<Self> {
+        mio_uds::UnixDatagram::from_datagram(socket).map(Self::new)
+    }
+
+    fn new(socket: mio_uds::UnixDatagram) -> Self {
+        UnixDatagram {
+            io: PollEvented::new(socket),
+        }
+    }
+}
+
+impl runtime_raw::UnixStream for UnixStream {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.romio_stream.local_addr()
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.romio_stream.peer_addr()
+    }
+
+    fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()> {
+        self.romio_stream.shutdown(how)
+    }
+
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        use std::os::unix::io::AsRawFd;
+        self.romio_stream.as_raw_fd()
+    }
+}
+
+impl AsyncRead for UnixStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.romio_stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for UnixStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.romio_stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.romio_stream).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.romio_stream).poll_close(cx)
+    }
+}
+
+impl runtime_raw::UnixListener for UnixListener {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.romio_listener.local_addr()
+    }
+
+    fn poll_accept(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<(Pin<Box<dyn runtime_raw::UnixStream>>, SocketAddr)>> {
+        Pin::new(&mut self.romio_listener)
+            .poll_ready(cx)
+            .map_ok(|(romio_stream, addr)| {
+                let stream = Box::pin(UnixStream { romio_stream });
+                (stream as Pin<Box<dyn runtime_raw::UnixStream>>, addr)
+            })
+    }
+
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        use std::os::unix::io::AsRawFd;
+        self.romio_listener.as_raw_fd()
+    }
+}
+
+impl runtime_raw::UnixDatagram for UnixDatagram {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.io.get_ref().local_addr()
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.io.get_ref().peer_addr()
+    }
+
+    fn connect(&self, path: &Path) -> io::Result<()> {
+        self.io.get_ref().connect(path)
+    }
+
+    fn poll_send_to(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        path: &Path,
+    ) -> Poll<io::Result<usize>> {
+        poll_write_with(&mut self.io, cx, |socket| socket.send_to(buf, path))
+    }
+
+    fn poll_recv_from(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        poll_read_with(&mut self.io, cx, |socket| socket.recv_from(buf))
+    }
+
+    fn poll_send(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_with(&mut self.io, cx, |socket| socket.send(buf))
+    }
+
+    fn poll_recv(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_read_with(&mut self.io, cx, |socket| recv(socket, buf))
+    }
+
+    fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()> {
+        self.io.get_ref().shutdown(how)
+    }
+
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        use std::os::unix::io::AsRawFd;
+        self.io.get_ref().as_raw_fd()
+    }
+}
+
+/// Receive a datagram, returning end-of-file once the read half of the socket has been shut down.
+fn recv(socket: &mio_uds::UnixDatagram, buf: &mut [u8]) -> io::Result<usize> {
+    match socket.recv(buf) {
+        #[cfg(target_os = "linux")]
+        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+            use std::os::unix::io::AsRawFd;
+            if runtime_raw::shutdown::is_read_shutdown(socket.as_raw_fd())? {
+                Ok(0)
+            } else {
+                Err(err)
+            }
+        }
+        res => res,
+    }
+}
@@ -15,6 +15,17 @@ impl runtime_raw::Runtime for Native {
         Ok(())
     }
 
+    fn capabilities(&self) -> runtime_raw::Capabilities {
+        // Only spawning is supported; blocking functions run in place on the executor.
+        runtime_raw::Capabilities::none()
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) -> Result<(), SpawnError> {
+        // Threads aren't available, so run in place.
+        f();
+        Ok(())
+    }
+
     fn connect_tcp_stream(
         &self,
         _addr: &SocketAddr,
@@ -29,6 +40,36 @@ impl runtime_raw::Runtime for Native {
         panic!("Binding TCP listeners is currently not supported in wasm");
     }
 
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        _addr: &SocketAddr,
+        _backlog: i32,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        panic!("Binding TCP listeners is currently not supported in wasm");
+    }
+
+    fn bind_tcp_listener_reuseport(
+        &self,
+        _addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        panic!("Binding TCP listeners is currently not supported in wasm");
+    }
+
+    fn tcp_listener_from_std(
+        &self,
+        _listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        panic!("Converting TCP listeners is currently not supported in wasm");
+    }
+
+    fn resolve(
+        &self,
+        resolve: Box<dyn FnOnce() -> io::Result<Vec<SocketAddr>> + Send>,
+    ) -> BoxFuture<'static, io::Result<Vec<SocketAddr>>> {
+        // Threads aren't available, so resolve in place.
+        Box::pin(futures::future::ready(resolve()))
+    }
+
     fn bind_udp_socket(
         &self,
         _addr: &SocketAddr,
@@ -36,6 +77,20 @@ impl runtime_raw::Runtime for Native {
         panic!("Binding UDP sockets is currently not supported in wasm");
     }
 
+    fn udp_socket_from_std(
+        &self,
+        _socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        panic!("Converting UDP sockets is currently not supported in wasm");
+    }
+
+    fn bind_udp_socket_reuseport(
+        &self,
+        _addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        panic!("Binding UDP sockets is currently not supported in wasm");
+    }
+
     fn new_delay(&self, _dur: Duration) -> Pin<Box<dyn runtime_raw::Delay>> {
         panic!("Timers are currently not supported in wasm");
     }
@@ -47,4 +102,8 @@ impl runtime_raw::Runtime for Native {
     fn new_interval(&self, _dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
         panic!("Timers are currently not supported in wasm");
     }
+
+    fn new_interval_at(&self, _at: Instant, _dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        panic!("Timers are currently not supported in wasm");
+    }
 }
@@ -14,32 +14,65 @@
 )]
 
 use futures::executor;
-use futures::future::BoxFuture;
+use futures::future::{BoxFuture, LocalBoxFuture};
 use futures::prelude::*;
-use futures::task::SpawnError;
+use futures::task::{Context, Poll, SpawnError, Waker};
 
 use std::cell::Cell;
+use std::env;
+use std::fmt;
 use std::io;
 use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+#[cfg(target_os = "linux")]
+pub mod mmsg;
+#[cfg(target_os = "linux")]
+pub mod shutdown;
 mod tcp;
 mod time;
 mod udp;
+#[cfg(unix)]
+mod unix;
 
 pub use tcp::*;
 pub use time::*;
 pub use udp::*;
+#[cfg(unix)]
+pub use unix::*;
+
+/// A pair of connected `UnixDatagram`s.
+#[cfg(unix)]
+type UnixDatagramPair = (Pin<Box<dyn UnixDatagram>>, Pin<Box<dyn UnixDatagram>>);
 
 thread_local! {
     static RUNTIME: Cell<Option<&'static dyn Runtime>> = Cell::new(None);
+    /// The address of the counters tracking the future being polled on this thread, if any.
+    static TRACKED: Cell<usize> = const { Cell::new(0) };
 }
 
 /// Get the current runtime.
+///
+/// # Panics
+///
+/// This function panics if no runtime has been set on the current thread. Use
+/// [`try_current_runtime`] to handle that case instead.
+///
+/// [`try_current_runtime`]: fn.try_current_runtime.html
 #[inline]
 pub fn current_runtime() -> &'static dyn Runtime {
-    RUNTIME.with(|r| r.get().expect("the runtime has not been set"))
+    try_current_runtime().expect("the runtime has not been set")
+}
+
+/// Get the current runtime, or `None` if no runtime has been set on the current thread.
+#[inline]
+pub fn try_current_runtime() -> Option<&'static dyn Runtime> {
+    RUNTIME.with(|r| r.get())
 }
 
 /// Set the current runtime.
@@ -53,7 +86,112 @@ pub fn set_runtime(runtime: &'static dyn Runtime) {
     });
 }
 
+/// Set the current runtime until the returned guard is dropped.
+///
+/// Unlike [`set_runtime`], this can be called on threads that already have a runtime: the previous
+/// runtime, if any, is restored when the guard is dropped. Guards should be dropped in the reverse
+/// order they were created in.
+///
+/// [`set_runtime`]: fn.set_runtime.html
+pub fn set_runtime_scoped(runtime: &'static dyn Runtime) -> RuntimeGuard {
+    let previous = RUNTIME.with(|r| r.replace(Some(runtime)));
+    RuntimeGuard { previous }
+}
+
+/// Restores the previous runtime when dropped.
+///
+/// This is returned by [`set_runtime_scoped`].
+///
+/// [`set_runtime_scoped`]: fn.set_runtime_scoped.html
+#[must_use = "the runtime is reset as soon as the guard is dropped"]
+pub struct RuntimeGuard {
+    previous: Option<&'static dyn Runtime>,
+}
+
+impl Drop for RuntimeGuard {
+    fn drop(&mut self) {
+        RUNTIME.with(|r| r.set(self.previous));
+    }
+}
+
+impl fmt::Debug for RuntimeGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("RuntimeGuard { .. }")
+    }
+}
+
+/// Set the current runtime while `f` runs, and restore the previous one afterwards.
+///
+/// This is a shorthand for [`set_runtime_scoped`], used for example by [`Runtime::block_on_boxed`].
+///
+/// [`set_runtime_scoped`]: fn.set_runtime_scoped.html
+/// [`Runtime::block_on_boxed`]: trait.Runtime.html#method.block_on_boxed
+pub fn with_runtime<T>(runtime: &'static dyn Runtime, f: impl FnOnce() -> T) -> T {
+    let _guard = set_runtime_scoped(runtime);
+    f()
+}
+
+static WORKER_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Configure the number of worker threads runtimes should start.
+///
+/// This sizes the thread pools runtimes share across the whole process, so it must be called
+/// before the runtime starts its threads, which happens the first time a future is spawned on it.
+/// Runtimes that can't be sized ignore this setting. To give a single runtime its own number of
+/// threads, use [`WorkerThreads`] instead.
+///
+/// [`WorkerThreads`]: trait.WorkerThreads.html
+pub fn set_worker_threads(threads: usize) {
+    assert!(
+        threads > 0,
+        "the number of worker threads must be greater than zero"
+    );
+    WORKER_THREADS.store(threads, Ordering::SeqCst);
+}
+
+/// Get the number of worker threads configured through `set_worker_threads`, if any.
+///
+/// Falls back to the `RUNTIME_THREADS` environment variable when `set_worker_threads` hasn't been
+/// called. Values that aren't a number greater than zero are ignored.
+pub fn configured_worker_threads() -> Option<usize> {
+    match WORKER_THREADS.load(Ordering::SeqCst) {
+        0 => env::var("RUNTIME_THREADS")
+            .ok()
+            .and_then(|threads| threads.trim().parse().ok())
+            .filter(|&threads| threads > 0),
+        threads => Some(threads),
+    }
+}
+
+/// Run a future to completion on the calling thread, and return its output.
+///
+/// Every runtime follows the same contract:
+///
+/// - The future is polled on the calling thread, so it doesn't need to be `Send` or `'static`.
+/// - `runtime` is the current runtime while the future runs, so futures spawned from it run on
+///   `runtime`, and resources it creates use `runtime`.
+/// - The I/O resources and timers the future creates are driven by the same reactor and timer as
+///   the ones created by `runtime`'s tasks. They can be moved into spawned tasks, and keep working
+///   after this function returns.
+///
+/// This differs from [`enter`], which spawns the future onto the runtime's own threads.
+///
+/// [`enter`]: fn.enter.html
+pub fn block_on<F: Future>(runtime: &'static dyn Runtime, fut: F) -> F::Output {
+    let mut output = None;
+    let fut = async {
+        output = Some(fut.await);
+    };
+    with_runtime(runtime, || runtime.block_on_boxed(fut.boxed_local()));
+    output.expect("the runtime returned before the future has completed")
+}
+
 /// Runs a future inside a runtime and blocks on the result.
+///
+/// The future is spawned onto the runtime, so unlike [`block_on`] it has to be `Send` and runs on
+/// one of the runtime's threads.
+///
+/// [`block_on`]: fn.block_on.html
 pub fn enter<R, F, T>(rt: R, fut: F) -> T
 where
     R: Runtime,
@@ -72,11 +210,324 @@ where
     executor::block_on(rx).expect("the main future has panicked")
 }
 
+/// The features a runtime supports.
+///
+/// Runtimes don't have to implement every kind of I/O: calling the methods of an unsupported
+/// feature may panic. Runtimes report what they support through [`Runtime::capabilities`], so that
+/// code can check for a feature before using it.
+///
+/// [`Runtime::capabilities`]: trait.Runtime.html#method.capabilities
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// TCP streams and listeners.
+    pub tcp: bool,
+    /// UDP sockets.
+    pub udp: bool,
+    /// Unix domain sockets. Only ever supported on Unix platforms.
+    pub unix: bool,
+    /// Delays and intervals.
+    pub timers: bool,
+    /// Running blocking functions on a separate thread.
+    pub blocking: bool,
+}
+
+impl Capabilities {
+    /// All features supported on the target platform.
+    pub fn all() -> Self {
+        Self {
+            tcp: true,
+            udp: true,
+            unix: cfg!(unix),
+            timers: true,
+            blocking: true,
+        }
+    }
+
+    /// No features supported.
+    pub fn none() -> Self {
+        Self::default()
+    }
+}
+
+/// A snapshot of what a runtime is doing, for monitoring.
+///
+/// Every field is best-effort: runtimes report what they can keep track of cheaply, and leave the
+/// rest as `None`. Runtimes report their metrics through [`Runtime::metrics`].
+///
+/// [`Runtime::metrics`]: trait.Runtime.html#method.metrics
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RuntimeMetrics {
+    /// The number of worker threads futures are polled on.
+    pub num_workers: Option<usize>,
+    /// The number of tasks spawned on the runtime so far.
+    pub num_spawned_tasks: Option<u64>,
+    /// The number of spawned tasks that haven't completed yet.
+    pub num_pending_tasks: Option<usize>,
+}
+
+/// Counts the tasks spawned on a runtime, so that it can report them in its [`RuntimeMetrics`]
+/// and tell when they have all completed.
+///
+/// Clones share the same counters.
+///
+/// [`RuntimeMetrics`]: struct.RuntimeMetrics.html
+#[derive(Clone, Debug, Default)]
+pub struct TaskCounters {
+    inner: Arc<Counters>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    spawned: AtomicU64,
+    pending: AtomicUsize,
+    /// The number of pending tracked futures that are themselves waiting for the others.
+    waiting: AtomicUsize,
+    /// The tasks waiting for the pending tasks to complete.
+    idle: Mutex<IdleWaiters>,
+}
+
+/// The wakers of the futures returned by `TaskCounters::wait_idle`.
+#[derive(Debug, Default)]
+struct IdleWaiters {
+    /// Bumped every time the waiters are woken up because the runtime became idle.
+    generation: u64,
+    wakers: Vec<Waker>,
+}
+
+impl IdleWaiters {
+    fn wake_all(&mut self) {
+        self.generation += 1;
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl Counters {
+    /// The value `TRACKED` holds while a future tracked by these counters is polled.
+    fn id(self: &Arc<Self>) -> usize {
+        Arc::as_ptr(self) as usize
+    }
+
+    /// Whether every pending tracked future is waiting for the others.
+    fn is_idle(&self) -> bool {
+        self.pending.load(Ordering::SeqCst) <= self.waiting.load(Ordering::SeqCst)
+    }
+
+    /// Wake every waiter, if there's nothing left to wait for.
+    fn wake_if_idle(&self) {
+        if self.is_idle() {
+            self.idle.lock().unwrap().wake_all();
+        }
+    }
+}
+
+/// Counts a task as pending until it's dropped, whether it ran to completion or not.
+struct PendingGuard(Arc<Counters>);
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        self.0.pending.fetch_sub(1, Ordering::SeqCst);
+        self.0.wake_if_idle();
+    }
+}
+
+/// The future returned by `TaskCounters::wait_idle`.
+struct WaitIdle {
+    counters: Arc<Counters>,
+    /// Whether this is awaited from a tracked future, and counted in `Counters::waiting`.
+    waiting: bool,
+    /// The generation of `IdleWaiters` this registered its waker in, if any.
+    generation: Option<u64>,
+}
+
+impl WaitIdle {
+    fn stop_waiting(&mut self) {
+        if self.waiting {
+            self.waiting = false;
+            self.counters.waiting.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Future for WaitIdle {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.waiting && TRACKED.with(Cell::get) == self.counters.id() {
+            // The tracked future awaiting this can't complete first, so it isn't waited on.
+            self.waiting = true;
+            self.counters.waiting.fetch_add(1, Ordering::SeqCst);
+        }
+
+        // The waiters are locked before checking the counts, so that a task completing in between
+        // wakes this one up.
+        let counters = self.counters.clone();
+        let mut idle = counters.idle.lock().unwrap();
+        let woken = self.generation.is_some_and(|g| g != idle.generation);
+        if woken || counters.is_idle() {
+            // Waking the other waiters completes them as well, even once this one stops waiting
+            // and their tracked futures are no longer all waiting.
+            if !woken {
+                idle.wake_all();
+            }
+            drop(idle);
+            self.stop_waiting();
+            return Poll::Ready(());
+        }
+        if !idle.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            idle.wakers.push(cx.waker().clone());
+        }
+        self.generation = Some(idle.generation);
+        Poll::Pending
+    }
+}
+
+impl Drop for WaitIdle {
+    fn drop(&mut self) {
+        self.stop_waiting();
+    }
+}
+
+/// A future tracked by `TaskCounters`.
+struct Tracked {
+    fut: BoxFuture<'static, ()>,
+    guard: PendingGuard,
+}
+
+/// Restores `TRACKED` once a tracked future has been polled, even if it panicked.
+struct ResetTracked(usize);
+
+impl Drop for ResetTracked {
+    fn drop(&mut self) {
+        TRACKED.with(|tracked| tracked.set(self.0));
+    }
+}
+
+impl Future for Tracked {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let id = self.guard.0.id();
+        let _reset = ResetTracked(TRACKED.with(|tracked| tracked.replace(id)));
+        self.fut.as_mut().poll(cx)
+    }
+}
+
+impl TaskCounters {
+    /// Create counters starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a future that is about to be spawned.
+    ///
+    /// The returned future must be spawned in its place. It's counted as pending until it
+    /// completes or is dropped.
+    pub fn track(&self, fut: BoxFuture<'static, ()>) -> BoxFuture<'static, ()> {
+        self.inner.spawned.fetch_add(1, Ordering::SeqCst);
+        self.inner.pending.fetch_add(1, Ordering::SeqCst);
+        let guard = PendingGuard(self.inner.clone());
+        Tracked { fut, guard }.boxed()
+    }
+
+    /// Wait until every tracked future has completed or been dropped.
+    ///
+    /// When this is awaited from tracked futures, those futures aren't waited on, since they can't
+    /// complete while they're waiting.
+    pub fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        WaitIdle {
+            counters: self.inner.clone(),
+            waiting: false,
+            generation: None,
+        }
+        .boxed()
+    }
+
+    /// Get the number of futures tracked so far.
+    pub fn spawned(&self) -> u64 {
+        self.inner.spawned.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of tracked futures that haven't completed or been dropped yet.
+    pub fn pending(&self) -> usize {
+        self.inner.pending.load(Ordering::SeqCst)
+    }
+}
+
 /// The runtime trait.
 pub trait Runtime: Send + Sync + 'static {
     /// Spawn a new future.
     fn spawn_boxed(&self, fut: BoxFuture<'static, ()>) -> Result<(), SpawnError>;
 
+    /// Run a future to completion on the calling thread.
+    ///
+    /// This is the object-safe hook behind [`block_on`], which should be used instead since it
+    /// returns the future's output and makes this runtime the current runtime. Runtimes
+    /// overriding it must uphold the rest of the contract documented there.
+    ///
+    /// The default implementation polls the future with `futures::executor::block_on`, which is
+    /// enough for runtimes whose reactor and timer run independently of the polling thread.
+    ///
+    /// [`block_on`]: fn.block_on.html
+    fn block_on_boxed(&'static self, fut: LocalBoxFuture<'_, ()>) {
+        executor::block_on(fut)
+    }
+
+    /// Get the current time according to the runtime's clock.
+    ///
+    /// Delays and intervals created through the runtime measure time against this clock. The
+    /// default implementation returns `Instant::now()`; runtimes whose clock can be controlled,
+    /// for testing, override it along with the timer methods.
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Get the number of worker threads the runtime polls futures on, if known.
+    fn worker_threads(&self) -> Option<usize> {
+        None
+    }
+
+    /// Get a snapshot of the runtime's metrics.
+    ///
+    /// The default implementation only reports the number of workers, from [`worker_threads`].
+    ///
+    /// [`worker_threads`]: #method.worker_threads
+    fn metrics(&self) -> RuntimeMetrics {
+        RuntimeMetrics {
+            num_workers: self.worker_threads(),
+            ..RuntimeMetrics::default()
+        }
+    }
+
+    /// Wait until every task spawned on the runtime has completed.
+    ///
+    /// Runtimes counting their tasks with [`TaskCounters`] forward this to
+    /// [`TaskCounters::wait_idle`]. The default implementation is ready right away, for runtimes
+    /// that don't keep track of their tasks.
+    ///
+    /// [`TaskCounters`]: struct.TaskCounters.html
+    /// [`TaskCounters::wait_idle`]: struct.TaskCounters.html#method.wait_idle
+    fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        future::ready(()).boxed()
+    }
+
+    /// Get the features this runtime supports.
+    ///
+    /// The default implementation reports every feature as supported. Runtimes that panic in some
+    /// of their methods should override it.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::all()
+    }
+
+    /// Run a blocking function without blocking the runtime's executor.
+    ///
+    /// The default implementation calls `f` on a newly spawned thread.
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) -> Result<(), SpawnError> {
+        std::thread::spawn(f);
+        Ok(())
+    }
+
     /// Create a new `TcpStream`.
     ///
     /// This method is defined on the `Runtime` trait because defining it on
@@ -92,12 +543,141 @@ pub trait Runtime: Send + Sync + 'static {
     /// `TcpListener` would prevent it from being a trait object.
     fn bind_tcp_listener(&self, addr: &SocketAddr) -> io::Result<Pin<Box<dyn TcpListener>>>;
 
+    /// Create a new `TcpListener` with the given size of the pending connection queue.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `TcpListener` would prevent it from being a trait object.
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        addr: &SocketAddr,
+        backlog: i32,
+    ) -> io::Result<Pin<Box<dyn TcpListener>>>;
+
+    /// Create a new `TcpListener` with `SO_REUSEADDR` and `SO_REUSEPORT` set before binding.
+    ///
+    /// Platforms without `SO_REUSEPORT`, such as Windows, only set `SO_REUSEADDR`.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `TcpListener` would prevent it from being a trait object.
+    fn bind_tcp_listener_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn TcpListener>>>;
+
+    /// Create a new `TcpListener` bound to the network interface named `interface`, by setting
+    /// `SO_BINDTODEVICE` before binding.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `TcpListener` would prevent it from being a trait object.
+    ///
+    /// The default implementation returns an error, for runtimes that can't bind to a network
+    /// interface.
+    #[cfg(target_os = "linux")]
+    fn bind_tcp_listener_device(
+        &self,
+        _addr: &SocketAddr,
+        _interface: &str,
+    ) -> io::Result<Pin<Box<dyn TcpListener>>> {
+        Err(io::Error::other(
+            "binding to a network interface is not supported by this runtime",
+        ))
+    }
+
+    /// Create a new `TcpListener` from a bound standard library listener.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `TcpListener` would prevent it from being a trait object.
+    fn tcp_listener_from_std(
+        &self,
+        listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn TcpListener>>>;
+
+    /// Resolve socket addresses without blocking the calling thread.
+    ///
+    /// `resolve` performs the (potentially blocking) address resolution when called. The default
+    /// implementation calls it through `spawn_blocking`.
+    fn resolve(
+        &self,
+        resolve: Box<dyn FnOnce() -> io::Result<Vec<SocketAddr>> + Send>,
+    ) -> BoxFuture<'static, io::Result<Vec<SocketAddr>>> {
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let spawned = self.spawn_blocking(Box::new(move || {
+            let _ = tx.send(resolve());
+        }));
+        if let Err(err) = spawned {
+            return future::ready(Err(io::Error::other(err))).boxed();
+        }
+        async move {
+            rx.await
+                .unwrap_or_else(|_| Err(io::Error::other("the resolver thread has panicked")))
+        }
+        .boxed()
+    }
+
     /// Create a new `UdpSocket`.
     ///
     /// This method is defined on the `Runtime` trait because defining it on
     /// `UdpSocket` would prevent it from being a trait object.
     fn bind_udp_socket(&self, addr: &SocketAddr) -> io::Result<Pin<Box<dyn UdpSocket>>>;
 
+    /// Create a new `UdpSocket` with `SO_REUSEADDR` and `SO_REUSEPORT` set before binding.
+    ///
+    /// Platforms without `SO_REUSEPORT`, such as Windows, only set `SO_REUSEADDR`.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `UdpSocket` would prevent it from being a trait object.
+    fn bind_udp_socket_reuseport(&self, addr: &SocketAddr) -> io::Result<Pin<Box<dyn UdpSocket>>>;
+
+    /// Create a new `UdpSocket` from a bound standard library socket.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `UdpSocket` would prevent it from being a trait object.
+    fn udp_socket_from_std(
+        &self,
+        socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn UdpSocket>>>;
+
+    /// Create a new `UnixStream` connected to the socket at `path`.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `UnixStream` would prevent it from being a trait object.
+    #[cfg(unix)]
+    fn connect_unix_stream(
+        &self,
+        path: &Path,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn UnixStream>>>>;
+
+    /// Create a new `UnixListener` bound to `path`.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `UnixListener` would prevent it from being a trait object.
+    #[cfg(unix)]
+    fn bind_unix_listener(&self, path: &Path) -> io::Result<Pin<Box<dyn UnixListener>>>;
+
+    /// Create a new `UnixDatagram` bound to `path`.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `UnixDatagram` would prevent it from being a trait object.
+    #[cfg(unix)]
+    fn bind_unix_datagram(&self, path: &Path) -> io::Result<Pin<Box<dyn UnixDatagram>>>;
+
+    /// Create an unnamed pair of connected `UnixDatagram`s.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `UnixDatagram` would prevent it from being a trait object.
+    #[cfg(unix)]
+    fn unix_datagram_pair(&self) -> io::Result<UnixDatagramPair>;
+
+    /// Create a new `UnixDatagram` from a standard library socket.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `UnixDatagram` would prevent it from being a trait object.
+    #[cfg(unix)]
+    fn unix_datagram_from_std(
+        &self,
+        socket: std::os::unix::net::UnixDatagram,
+    ) -> io::Result<Pin<Box<dyn UnixDatagram>>>;
+
     /// Create a new Future that wakes up after the given duration
     ///
     /// This method is defined on the `Runtime` trait because defining it on
@@ -115,4 +695,31 @@ pub trait Runtime: Send + Sync + 'static {
     /// This method is defined on the `Runtime` trait because defining it on
     /// `Interval` would prevent it from being a trait object.
     fn new_interval(&self, dur: Duration) -> Pin<Box<dyn Interval>>;
+
+    /// A stream representing notifications at a fixed interval, starting at the given time.
+    ///
+    /// This method is defined on the `Runtime` trait because defining it on
+    /// `Interval` would prevent it from being a trait object.
+    fn new_interval_at(&self, at: Instant, dur: Duration) -> Pin<Box<dyn Interval>>;
+}
+
+/// A runtime that can be created with its own number of worker threads.
+///
+/// This is what the `threads` option of the attributes uses, so that the option only sizes the
+/// runtime it's passed along with, rather than every runtime in the process like
+/// [`set_worker_threads`] does. Runtimes that can't be sized don't implement it, so the option is
+/// rejected at compile time for them.
+///
+/// [`set_worker_threads`]: fn.set_worker_threads.html
+pub trait WorkerThreads {
+    /// The runtime polling futures on its own pool of worker threads. Clones share the same pool.
+    type Runtime: Runtime + Clone;
+
+    /// Create a runtime like this one, which polls futures on its own pool of `threads` worker
+    /// threads.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `threads` is zero.
+    fn with_worker_threads(&self, threads: usize) -> Self::Runtime;
 }
@@ -0,0 +1,117 @@
+//! Sending and receiving several datagrams with a single system call.
+//!
+//! These helpers are meant for runtimes implementing [`UdpSocket::poll_send_batch`] and
+//! [`UdpSocket::poll_recv_batch`] on top of a nonblocking socket. They run `sendmmsg` and
+//! `recvmmsg` once, so an error of kind `WouldBlock` is returned as is.
+//!
+//! [`UdpSocket::poll_send_batch`]: ../trait.UdpSocket.html#method.poll_send_batch
+//! [`UdpSocket::poll_recv_batch`]: ../trait.UdpSocket.html#method.poll_recv_batch
+
+#![allow(unsafe_code)]
+
+use socket2::SockAddr;
+
+use std::io;
+use std::mem;
+use std::net::SocketAddr;
+use std::os::unix::io::RawFd;
+use std::ptr;
+
+/// Build the header of a message made of a single buffer.
+fn header(
+    iovec: &mut libc::iovec,
+    name: *mut libc::c_void,
+    namelen: libc::socklen_t,
+) -> libc::mmsghdr {
+    // `msghdr` has private padding fields on some targets, so it's zeroed and then filled in.
+    let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+    hdr.msg_name = name;
+    hdr.msg_namelen = namelen;
+    hdr.msg_iov = iovec;
+    hdr.msg_iovlen = 1;
+    libc::mmsghdr {
+        msg_hdr: hdr,
+        msg_len: 0,
+    }
+}
+
+/// Send every packet as a datagram with `sendmmsg`, returning the number of datagrams sent.
+pub fn send(fd: RawFd, packets: &[(&[u8], SocketAddr)]) -> io::Result<usize> {
+    let addrs: Vec<SockAddr> = packets
+        .iter()
+        .map(|(_, addr)| SockAddr::from(*addr))
+        .collect();
+    let mut iovecs: Vec<libc::iovec> = packets
+        .iter()
+        .map(|(buf, _)| libc::iovec {
+            iov_base: buf.as_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        })
+        .collect();
+    let mut msgs: Vec<libc::mmsghdr> = iovecs
+        .iter_mut()
+        .zip(&addrs)
+        .map(|(iovec, addr)| header(iovec, addr.as_ptr() as *mut libc::c_void, addr.len()))
+        .collect();
+
+    // The headers point into `iovecs` and `addrs`, which outlive the call. The kernel only
+    // reads from the buffers.
+    let n = unsafe { libc::sendmmsg(fd, msgs.as_mut_ptr(), msgs.len() as libc::c_uint, 0) };
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(n as usize)
+}
+
+/// Receive a datagram into each buffer with `recvmmsg`, returning the length and origin of
+/// the datagrams received.
+pub fn recv(fd: RawFd, bufs: &mut [&mut [u8]]) -> io::Result<Vec<(usize, SocketAddr)>> {
+    let mut addrs: Vec<libc::sockaddr_storage> = vec![unsafe { mem::zeroed() }; bufs.len()];
+    let mut iovecs: Vec<libc::iovec> = bufs
+        .iter_mut()
+        .map(|buf| libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        })
+        .collect();
+    let mut msgs: Vec<libc::mmsghdr> = iovecs
+        .iter_mut()
+        .zip(&mut addrs)
+        .map(|(iovec, addr)| {
+            let name = addr as *mut libc::sockaddr_storage as *mut libc::c_void;
+            let namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            header(iovec, name, namelen)
+        })
+        .collect();
+
+    // The headers point into `iovecs` and `addrs`, which outlive the call, and the buffers
+    // are borrowed mutably for the duration of the call.
+    let n = unsafe {
+        libc::recvmmsg(
+            fd,
+            msgs.as_mut_ptr(),
+            msgs.len() as libc::c_uint,
+            0,
+            ptr::null_mut(),
+        )
+    };
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    msgs[..n as usize]
+        .iter()
+        .zip(addrs)
+        .map(|(msg, addr)| {
+            // The kernel filled in the address and set its length.
+            let addr = unsafe { SockAddr::new(addr, msg.msg_hdr.msg_namelen) };
+            let addr = addr.as_socket().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "received a datagram from an address that isn't an IP address",
+                )
+            })?;
+            Ok((msg.msg_len as usize, addr))
+        })
+        .collect()
+}
@@ -0,0 +1,29 @@
+//! Detecting that the read half of a socket has been shut down.
+//!
+//! On Linux, receiving from a nonblocking datagram socket whose read half was shut down fails with
+//! `WouldBlock`, while a blocking receive returns end-of-file. The socket is still reported as
+//! readable, so runtimes implementing [`UnixDatagram::poll_recv`] check [`is_read_shutdown`] when
+//! a receive would block, and return end-of-file instead of waiting for a datagram that can't
+//! arrive.
+//!
+//! [`UnixDatagram::poll_recv`]: ../trait.UnixDatagram.html#tymethod.poll_recv
+//! [`is_read_shutdown`]: fn.is_read_shutdown.html
+
+#![allow(unsafe_code)]
+
+use std::io;
+use std::os::unix::io::RawFd;
+
+/// Returns whether the read half of the socket `fd` has been shut down.
+pub fn is_read_shutdown(fd: RawFd) -> io::Result<bool> {
+    let mut pollfd = libc::pollfd {
+        fd,
+        events: libc::POLLRDHUP,
+        revents: 0,
+    };
+    // A zero timeout only checks the current state of the socket.
+    if unsafe { libc::poll(&mut pollfd, 1, 0) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(pollfd.revents & libc::POLLRDHUP != 0)
+}
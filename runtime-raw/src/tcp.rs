@@ -5,6 +5,10 @@ use std::fmt::Debug;
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
+use std::time::Duration;
+
+/// An accepted stream, along with the address of the remote peer.
+type Accepted = (Pin<Box<dyn TcpStream>>, SocketAddr);
 
 /// A TcpStream for this Runtime
 pub trait TcpStream: AsyncRead + AsyncWrite + Debug + Send {
@@ -29,6 +33,40 @@ pub trait TcpStream: AsyncRead + AsyncWrite + Debug + Send {
     /// Shuts down the read, write, or both halves of this connection.
     fn shutdown(&self, how: std::net::Shutdown) -> std::io::Result<()>;
 
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    fn nodelay(&self) -> io::Result<bool>;
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    fn set_nodelay(&self, nodelay: bool) -> io::Result<()>;
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    fn ttl(&self) -> io::Result<u32>;
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    fn set_ttl(&self, ttl: u32) -> io::Result<()>;
+
+    /// Gets the value of the `IP_TOS` option for this socket.
+    fn tos(&self) -> io::Result<u32>;
+
+    /// Sets the value for the `IP_TOS` option on this socket.
+    fn set_tos(&self, tos: u32) -> io::Result<()>;
+
+    /// Reads the linger duration for this socket by getting the `SO_LINGER` option.
+    fn linger(&self) -> io::Result<Option<Duration>>;
+
+    /// Sets the linger duration of this socket by setting the `SO_LINGER` option.
+    fn set_linger(&self, dur: Option<Duration>) -> io::Result<()>;
+
+    /// Creates a new handle to the same socket, registered with the runtime independently.
+    ///
+    /// The default implementation returns an error, for runtimes that can't register an existing
+    /// socket.
+    fn try_clone(&self) -> io::Result<Pin<Box<dyn TcpStream>>> {
+        Err(io::Error::other(
+            "cloning TCP streams is not supported by this runtime",
+        ))
+    }
+
     /// Extracts the raw file descriptor.
     #[cfg(unix)]
     fn as_raw_fd(&self) -> std::os::unix::io::RawFd;
@@ -39,11 +77,16 @@ pub trait TcpListener: Debug + Send {
     /// Get the address the listener is listening on.
     fn local_addr(&self) -> io::Result<SocketAddr>;
 
+    /// Gets the value of the `IP_TTL` option for this socket.
+    fn ttl(&self) -> io::Result<u32>;
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    fn set_ttl(&self, ttl: u32) -> io::Result<()>;
+
     /// Check if the listener is ready to accept connections.
-    fn poll_accept(
-        self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-    ) -> Poll<io::Result<Pin<Box<dyn TcpStream>>>>;
+    ///
+    /// On success, returns the accepted stream and the address of the remote peer.
+    fn poll_accept(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<Accepted>>;
 
     /// Extracts the raw file descriptor.
     #[cfg(unix)]
@@ -1,6 +1,10 @@
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::future::Future;
-use std::time::Instant;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 use futures::Stream;
 
@@ -8,4 +12,86 @@ use futures::Stream;
 pub trait Delay: Future<Output = Instant> + Debug + Send {}
 
 /// A stream representing notifications at a fixed interval.
-pub trait Interval: Stream<Item = Instant> + Debug + Send {}
+pub trait Interval: Stream<Item = Instant> + Debug + Send {
+    /// Set the behavior used when one or more ticks have been missed.
+    fn set_missed_tick_behavior(self: Pin<&mut Self>, behavior: MissedTickBehavior);
+
+    /// Reset the interval so that the next tick fires one period from now.
+    fn reset(self: Pin<&mut Self>);
+
+    /// Poll for the next tick, reporting how many deadlines were missed before it fired.
+    ///
+    /// This advances the interval in the same way as polling the stream does. Resolves to an error
+    /// if the runtime's timer has failed, for example because it has shut down, in which case the
+    /// stream ends.
+    fn poll_tick(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<InstantTick>>;
+}
+
+/// A tick fired by an [`Interval`], along with the number of deadlines it fell behind by.
+///
+/// [`Interval`]: trait.Interval.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InstantTick {
+    /// The instant the tick fired at.
+    pub when: Instant,
+    /// The number of further deadlines that had already passed when the tick fired.
+    ///
+    /// This is zero unless the interval wasn't polled for at least a full period.
+    pub missed: u32,
+}
+
+impl InstantTick {
+    /// Describe a tick that was due at `deadline` and fired at `now`, for an interval of `period`.
+    pub fn new(deadline: Instant, now: Instant, period: Duration) -> Self {
+        let missed = now
+            .saturating_duration_since(deadline)
+            .as_nanos()
+            .checked_div(period.as_nanos())
+            .unwrap_or(0);
+        InstantTick {
+            when: now,
+            missed: u32::try_from(missed).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+/// The strategy an [`Interval`] uses to catch up after ticks have been missed.
+///
+/// A tick is missed when the stream isn't polled until at least one full period after its
+/// deadline, for example because the consumer was busy with other work.
+///
+/// [`Interval`]: trait.Interval.html
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MissedTickBehavior {
+    /// Fire all missed ticks immediately, one after the other, until the interval has caught up.
+    ///
+    /// This is the default.
+    #[default]
+    Burst,
+    /// Fire the missed tick immediately and schedule the next one a full period after it.
+    Delay,
+    /// Fire the missed tick immediately and skip ahead to the next deadline on the original
+    /// schedule.
+    Skip,
+}
+
+impl MissedTickBehavior {
+    /// Compute the deadline following `deadline`, given that it fired at `now`.
+    pub fn next_deadline(self, deadline: Instant, now: Instant, period: Duration) -> Instant {
+        let next = deadline + period;
+        if next > now {
+            return next;
+        }
+        match self {
+            MissedTickBehavior::Burst => next,
+            MissedTickBehavior::Delay => now + period,
+            MissedTickBehavior::Skip => {
+                let behind = (now - deadline)
+                    .as_nanos()
+                    .checked_rem(period.as_nanos())
+                    .unwrap_or(0);
+                now + period - Duration::from_nanos(behind as u64)
+            }
+        }
+    }
+}
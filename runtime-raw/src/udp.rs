@@ -1,5 +1,7 @@
+use futures::ready;
+
 use std::fmt::Debug;
-use std::io;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -12,6 +14,11 @@ pub trait UdpSocket: Debug + Send + Sync {
     /// which port was actually bound.
     fn local_addr(&self) -> io::Result<SocketAddr>;
 
+    /// Returns the remote address that this socket is connected to.
+    ///
+    /// Returns an error of kind `NotConnected` if the socket isn't connected.
+    fn peer_addr(&self) -> io::Result<SocketAddr>;
+
     /// Sends data on the IO interface to the specified target.
     ///
     /// On success, returns the number of bytes written.
@@ -32,6 +39,142 @@ pub trait UdpSocket: Debug + Send + Sync {
         buf: &mut [u8],
     ) -> Poll<io::Result<(usize, SocketAddr)>>;
 
+    /// Sends data from multiple buffers on the IO interface to the specified target, as a single
+    /// datagram.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// The default implementation copies the buffers into one before sending it with
+    /// `poll_send_to`.
+    fn poll_send_to_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+        receiver: &SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        let buf: Vec<u8> = bufs.iter().flat_map(|buf| buf.iter().copied()).collect();
+        self.poll_send_to(cx, &buf, receiver)
+    }
+
+    /// Receives a datagram from the IO interface into multiple buffers, filling them in order.
+    ///
+    /// On success, returns the number of bytes read and the target from whence
+    /// the data came.
+    ///
+    /// The default implementation receives into a single buffer with `poll_recv_from`, and copies
+    /// it into the buffers afterwards.
+    fn poll_recv_from_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        let mut buf = vec![0; bufs.iter().map(|buf| buf.len()).sum()];
+        let (len, addr) = ready!(self.poll_recv_from(cx, &mut buf))?;
+
+        let mut data = &buf[..len];
+        for dst in bufs {
+            if data.is_empty() {
+                break;
+            }
+            let n = dst.len().min(data.len());
+            dst[..n].copy_from_slice(&data[..n]);
+            data = &data[n..];
+        }
+        Poll::Ready(Ok((len, addr)))
+    }
+
+    /// Sends several datagrams, each to its own target.
+    ///
+    /// On success, returns the number of datagrams sent, which is smaller than the number of
+    /// packets if the socket stopped accepting datagrams part of the way through. Only returns
+    /// `Poll::Pending` or an error if no datagram was sent.
+    ///
+    /// The default implementation sends the datagrams one at a time with `poll_send_to`.
+    fn poll_send_batch(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        packets: &[(&[u8], SocketAddr)],
+    ) -> Poll<io::Result<usize>> {
+        let mut sent = 0;
+        for (buf, receiver) in packets {
+            match self.as_mut().poll_send_to(cx, buf, receiver) {
+                Poll::Ready(Ok(_)) => sent += 1,
+                Poll::Ready(Err(err)) if sent == 0 => return Poll::Ready(Err(err)),
+                Poll::Pending if sent == 0 => return Poll::Pending,
+                // The error comes up again on the next send.
+                Poll::Ready(Err(_)) | Poll::Pending => break,
+            }
+        }
+        Poll::Ready(Ok(sent))
+    }
+
+    /// Receives several datagrams, one into each buffer.
+    ///
+    /// On success, returns the number of bytes read and the origin of each datagram received, in
+    /// the order of the buffers they were received into. Returns as soon as at least one datagram
+    /// has been received, without waiting for the buffers to be filled. Only returns
+    /// `Poll::Pending` or an error if no datagram was received.
+    ///
+    /// The default implementation receives the datagrams one at a time with `poll_recv_from`.
+    fn poll_recv_batch(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [&mut [u8]],
+    ) -> Poll<io::Result<Vec<(usize, SocketAddr)>>> {
+        let mut received = Vec::new();
+        for buf in bufs.iter_mut() {
+            match self.as_mut().poll_recv_from(cx, buf) {
+                Poll::Ready(Ok(res)) => received.push(res),
+                Poll::Ready(Err(err)) if received.is_empty() => return Poll::Ready(Err(err)),
+                Poll::Pending if received.is_empty() => return Poll::Pending,
+                Poll::Ready(Err(_)) | Poll::Pending => break,
+            }
+        }
+        Poll::Ready(Ok(received))
+    }
+
+    /// Receives data from the IO interface without removing it from the queue.
+    ///
+    /// On success, returns the number of bytes read and the target from whence
+    /// the data came. A subsequent receive returns the same datagram.
+    fn poll_peek_from(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>>;
+
+    /// Connects the socket to a remote address, so that `poll_send` and `poll_recv` can be used.
+    ///
+    /// Datagrams from any other address are no longer received once connected.
+    fn connect(&self, addr: &SocketAddr) -> io::Result<()>;
+
+    /// Sends data on the IO interface to the connected remote address.
+    ///
+    /// On success, returns the number of bytes written.
+    fn poll_send(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>;
+
+    /// Receives data from the connected remote address.
+    ///
+    /// On success, returns the number of bytes read.
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Gets the value of the `SO_RCVBUF` option for this socket.
+    fn recv_buffer_size(&self) -> io::Result<usize>;
+
+    /// Sets the value of the `SO_RCVBUF` option for this socket.
+    fn set_recv_buffer_size(&self, size: usize) -> io::Result<()>;
+
+    /// Gets the value of the `SO_SNDBUF` option for this socket.
+    fn send_buffer_size(&self) -> io::Result<usize>;
+
+    /// Sets the value of the `SO_SNDBUF` option for this socket.
+    fn set_send_buffer_size(&self, size: usize) -> io::Result<()>;
+
     /// Gets the value of the `SO_BROADCAST` option for this socket.
     fn broadcast(&self) -> io::Result<bool>;
 
@@ -56,12 +199,30 @@ pub trait UdpSocket: Debug + Send + Sync {
     /// Sets the value of the `IPV6_MULTICAST_LOOP` option for this socket.
     fn set_multicast_loop_v6(&self, on: bool) -> io::Result<()>;
 
+    /// Gets the value of the `IP_MULTICAST_IF` option for this socket.
+    fn multicast_if_v4(&self) -> io::Result<Ipv4Addr>;
+
+    /// Sets the value of the `IP_MULTICAST_IF` option for this socket.
+    fn set_multicast_if_v4(&self, interface: &Ipv4Addr) -> io::Result<()>;
+
+    /// Gets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    fn multicast_if_v6(&self) -> io::Result<u32>;
+
+    /// Sets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    fn set_multicast_if_v6(&self, interface: u32) -> io::Result<()>;
+
     /// Gets the value of the `IP_TTL` option for this socket.
     fn ttl(&self) -> io::Result<u32>;
 
     /// Sets the value for the `IP_TTL` option on this socket.
     fn set_ttl(&self, ttl: u32) -> io::Result<()>;
 
+    /// Gets the value of the `IP_TOS` option for this socket.
+    fn tos(&self) -> io::Result<u32>;
+
+    /// Sets the value for the `IP_TOS` option on this socket.
+    fn set_tos(&self, tos: u32) -> io::Result<()>;
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     fn join_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()>;
 
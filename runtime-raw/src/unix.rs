@@ -0,0 +1,93 @@
+use futures::prelude::*;
+use futures::task::{Context, Poll};
+
+use std::fmt::Debug;
+use std::io;
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+
+/// An accepted stream, along with the address of the remote peer.
+type Accepted = (Pin<Box<dyn UnixStream>>, SocketAddr);
+
+/// A UnixStream for this Runtime
+pub trait UnixStream: AsyncRead + AsyncWrite + Debug + Send {
+    /// Returns the socket address of the local half of this connection.
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+
+    /// Returns the socket address of the remote half of this connection.
+    fn peer_addr(&self) -> io::Result<SocketAddr>;
+
+    /// Shuts down the read, write, or both halves of this connection.
+    fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()>;
+
+    /// Extracts the raw file descriptor.
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd;
+}
+
+/// A UnixListener for this Runtime
+pub trait UnixListener: Debug + Send {
+    /// Get the address the listener is listening on.
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+
+    /// Check if the listener is ready to accept connections.
+    ///
+    /// On success, returns the accepted stream and the address of the remote peer.
+    fn poll_accept(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<Accepted>>;
+
+    /// Extracts the raw file descriptor.
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd;
+}
+
+/// A UnixDatagram for this Runtime
+pub trait UnixDatagram: Debug + Send + Sync {
+    /// Returns the address of this socket.
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+
+    /// Returns the address of this socket's peer.
+    fn peer_addr(&self) -> io::Result<SocketAddr>;
+
+    /// Connects the socket to the socket at `path`, so that `poll_send` and `poll_recv` can be used.
+    fn connect(&self, path: &Path) -> io::Result<()>;
+
+    /// Sends data on the socket to the socket at `path`.
+    ///
+    /// On success, returns the number of bytes written.
+    fn poll_send_to(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        path: &Path,
+    ) -> Poll<io::Result<usize>>;
+
+    /// Receives data from the socket.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came.
+    fn poll_recv_from(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>>;
+
+    /// Sends data on the socket to the socket's peer.
+    ///
+    /// On success, returns the number of bytes written.
+    fn poll_send(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>;
+
+    /// Receives data from the socket's peer.
+    ///
+    /// On success, returns the number of bytes read.
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Shuts down the read, write, or both halves of this socket.
+    fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()>;
+
+    /// Extracts the raw file descriptor.
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd;
+}
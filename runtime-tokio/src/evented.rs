@@ -0,0 +1,53 @@
+use mio::{Evented, Ready};
+use tokio::reactor::PollEvented2;
+
+use std::io;
+use std::task::Poll;
+
+/// Run a nonblocking read operation once `io` is readable.
+///
+/// If the operation would block, the readiness is cleared so the task is woken up the next time
+/// `io` becomes readable. Like Tokio's own resources, this has to be called from a futures 0.1
+/// task.
+pub(crate) fn poll_read_with<E, T>(
+    io: &PollEvented2<E>,
+    op: impl FnOnce(&E) -> io::Result<T>,
+) -> Poll<io::Result<T>>
+where
+    E: Evented,
+{
+    if let futures01::Async::NotReady = io.poll_read_ready(Ready::readable())? {
+        return Poll::Pending;
+    }
+    match op(io.get_ref()) {
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+            io.clear_read_ready(Ready::readable())?;
+            Poll::Pending
+        }
+        res => Poll::Ready(res),
+    }
+}
+
+/// Run a nonblocking write operation once `io` is writable.
+///
+/// If the operation would block, the readiness is cleared so the task is woken up the next time
+/// `io` becomes writable. Like Tokio's own resources, this has to be called from a futures 0.1
+/// task.
+pub(crate) fn poll_write_with<E, T>(
+    io: &PollEvented2<E>,
+    op: impl FnOnce(&E) -> io::Result<T>,
+) -> Poll<io::Result<T>>
+where
+    E: Evented,
+{
+    if let futures01::Async::NotReady = io.poll_write_ready()? {
+        return Poll::Pending;
+    }
+    match op(io.get_ref()) {
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+            io.clear_write_ready()?;
+            Poll::Pending
+        }
+        res => Poll::Ready(res),
+    }
+}
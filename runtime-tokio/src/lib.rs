@@ -11,27 +11,69 @@
 )]
 
 use futures::{
+    channel::oneshot,
     compat::Future01CompatExt,
-    future::{BoxFuture, FutureExt, TryFutureExt},
+    future::{self, BoxFuture, FutureExt, LocalBoxFuture, TryFutureExt},
     task::SpawnError,
 };
 use lazy_static::lazy_static;
-use tokio::timer::{Delay as TokioDelay, Interval as TokioInterval};
 
 use std::io;
 use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
 use std::pin::Pin;
-use std::sync::{mpsc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
+use std::task::Poll;
 use std::thread;
 use std::time::{Duration, Instant};
 
+mod evented;
 mod tcp;
 mod time;
 mod udp;
+#[cfg(unix)]
+mod unix;
 
 use tcp::{TcpListener, TcpStream};
 use time::{Delay, Interval};
 use udp::UdpSocket;
+#[cfg(unix)]
+use unix::{UnixDatagram, UnixListener, UnixStream};
+
+lazy_static! {
+    static ref TOKIO_WORKER_THREADS: usize =
+        runtime_raw::configured_worker_threads().unwrap_or_else(num_cpus::get);
+    static ref TOKIO_TASK_COUNTERS: runtime_raw::TaskCounters = runtime_raw::TaskCounters::new();
+    static ref TOKIO_CURRENT_THREAD_TASK_COUNTERS: runtime_raw::TaskCounters =
+        runtime_raw::TaskCounters::new();
+}
+
+/// Block on a future on the calling thread, with the reactor and timer of one of `rt`'s threads.
+///
+/// The I/O and timers the future creates are then driven by that thread, like the ones created by
+/// `rt`'s tasks, so they keep working after this function returns.
+fn block_on_with_drivers(rt: &'static dyn runtime_raw::Runtime, fut: LocalBoxFuture<'_, ()>) {
+    let mut enter =
+        tokio_executor::enter().expect("cannot block on a future from within a Tokio runtime");
+
+    let (tx, rx) = mpsc::channel();
+    let probe = async move {
+        #[allow(deprecated)]
+        let reactor = tokio_reactor::Handle::current();
+        let _ = tx.send((reactor, tokio_timer::timer::Handle::current()));
+    };
+    rt.spawn_boxed(probe.boxed())
+        .expect("cannot spawn a future");
+    let (reactor, timer) = rx.recv().expect("the Tokio runtime has shut down");
+
+    tokio_reactor::with_default(&reactor, &mut enter, |enter| {
+        tokio_timer::with_default(&timer, enter, |_| {
+            // Tokio's resources expect to be polled from a futures 0.1 task.
+            let _ = futures01::Future::wait(fut.unit_error().compat());
+        })
+    })
+}
 
 /// The default Tokio runtime.
 #[derive(Debug)]
@@ -42,6 +84,7 @@ impl runtime_raw::Runtime for Tokio {
         lazy_static! {
             static ref TOKIO_RUNTIME: tokio::runtime::Runtime = {
                 tokio::runtime::Builder::new()
+                    .core_threads(*TOKIO_WORKER_THREADS)
                     .after_start(|| {
                         runtime_raw::set_runtime(&Tokio);
                     })
@@ -50,10 +93,44 @@ impl runtime_raw::Runtime for Tokio {
             };
         }
 
+        let fut = TOKIO_TASK_COUNTERS.track(fut);
         TOKIO_RUNTIME.executor().spawn(fut.unit_error().compat());
         Ok(())
     }
 
+    fn worker_threads(&self) -> Option<usize> {
+        Some(*TOKIO_WORKER_THREADS)
+    }
+
+    fn metrics(&self) -> runtime_raw::RuntimeMetrics {
+        runtime_raw::RuntimeMetrics {
+            num_workers: self.worker_threads(),
+            num_spawned_tasks: Some(TOKIO_TASK_COUNTERS.spawned()),
+            num_pending_tasks: Some(TOKIO_TASK_COUNTERS.pending()),
+        }
+    }
+
+    fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        TOKIO_TASK_COUNTERS.wait_idle()
+    }
+
+    fn block_on_boxed(&'static self, fut: LocalBoxFuture<'_, ()>) {
+        block_on_with_drivers(self, fut)
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) -> Result<(), SpawnError> {
+        let mut f = Some(f);
+        let blocking =
+            future::poll_fn(
+                move |_| match tokio_threadpool::blocking(|| (f.take().unwrap())()) {
+                    Ok(futures01::Async::Ready(())) => Poll::Ready(()),
+                    Ok(futures01::Async::NotReady) => Poll::Pending,
+                    Err(_) => panic!("the Tokio runtime is not running on a thread pool"),
+                },
+            );
+        self.spawn_boxed(blocking.boxed())
+    }
+
     fn connect_tcp_stream(
         &self,
         addr: &SocketAddr,
@@ -71,67 +148,497 @@ impl runtime_raw::Runtime for Tokio {
         &self,
         addr: &SocketAddr,
     ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
-        let tokio_listener = tokio::net::TcpListener::bind(&addr)?;
+        let tokio_listener = tokio::net::TcpListener::bind(addr)?;
         Ok(Box::pin(TcpListener { tokio_listener }))
     }
 
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        addr: &SocketAddr,
+        backlog: i32,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener(addr, backlog)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    fn bind_tcp_listener_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener_reuseport(addr)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn bind_tcp_listener_device(
+        &self,
+        addr: &SocketAddr,
+        interface: &str,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener_device(addr, interface)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    fn tcp_listener_from_std(
+        &self,
+        listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    fn bind_udp_socket(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Ok(Box::pin(UdpSocket::bind(addr)?))
+    }
+
+    fn udp_socket_from_std(
+        &self,
+        socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Ok(Box::pin(UdpSocket::from_std(socket)?))
+    }
+
+    fn bind_udp_socket_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        let socket = udp::bind_std_socket_reuseport(addr)?;
+        self.udp_socket_from_std(socket)
+    }
+
+    #[cfg(unix)]
+    fn connect_unix_stream(
+        &self,
+        path: &Path,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::UnixStream>>>> {
+        use futures01::Future;
+
+        let tokio_connect = tokio::net::UnixStream::connect(path);
+        let connect = tokio_connect.map(|tokio_stream| {
+            Box::pin(UnixStream { tokio_stream }) as Pin<Box<dyn runtime_raw::UnixStream>>
+        });
+        connect.compat().boxed()
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_listener(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixListener>>> {
+        let tokio_listener = tokio::net::UnixListener::bind(path)?;
+        Ok(Box::pin(UnixListener { tokio_listener }))
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_datagram(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        let tokio_datagram = tokio::net::UnixDatagram::bind(path)?;
+        Ok(Box::pin(UnixDatagram { tokio_datagram }))
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_pair(
+        &self,
+    ) -> io::Result<(
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+    )> {
+        let (a, b) = tokio::net::UnixDatagram::pair()?;
+        let a = Box::pin(UnixDatagram { tokio_datagram: a });
+        let b = Box::pin(UnixDatagram { tokio_datagram: b });
+        Ok((a, b))
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_from_std(
+        &self,
+        socket: std::os::unix::net::UnixDatagram,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        socket.set_nonblocking(true)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_datagram = tokio::net::UnixDatagram::from_std(socket, &handle)?;
+        Ok(Box::pin(UnixDatagram { tokio_datagram }))
+    }
+
+    fn new_delay(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Delay>> {
+        Box::pin(Delay::new(self.now() + dur))
+    }
+
+    fn new_delay_at(&self, at: Instant) -> Pin<Box<dyn runtime_raw::Delay>> {
+        Box::pin(Delay::new(at))
+    }
+
+    fn new_interval(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Box::pin(Interval::new(self.now(), dur))
+    }
+
+    fn new_interval_at(&self, at: Instant, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Box::pin(Interval::new(at, dur))
+    }
+}
+
+impl Tokio {
+    /// Create a multi-threaded Tokio runtime with its own thread pool of `threads` worker threads.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `threads` is zero.
+    pub fn with_threads(threads: usize) -> TokioThreadPool {
+        TokioThreadPool::new(threads)
+    }
+}
+
+impl runtime_raw::WorkerThreads for Tokio {
+    type Runtime = TokioThreadPool;
+
+    fn with_worker_threads(&self, threads: usize) -> TokioThreadPool {
+        TokioThreadPool::new(threads)
+    }
+}
+
+/// A multi-threaded Tokio runtime with a fixed number of worker threads.
+///
+/// Unlike [`Tokio`], which shares a single global thread pool, every `TokioThreadPool` created
+/// with [`Tokio::with_threads`] starts its own pool the first time a future is spawned on it.
+/// Clones share the same pool.
+///
+/// [`Tokio`]: struct.Tokio.html
+/// [`Tokio::with_threads`]: struct.Tokio.html#method.with_threads
+#[derive(Debug, Clone)]
+pub struct TokioThreadPool {
+    threads: usize,
+    runtime: Arc<Mutex<Option<tokio::runtime::Runtime>>>,
+    tasks: runtime_raw::TaskCounters,
+}
+
+impl TokioThreadPool {
+    /// Create a runtime with `threads` worker threads.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `threads` is zero.
+    pub fn new(threads: usize) -> Self {
+        assert!(
+            threads > 0,
+            "the number of worker threads must be greater than zero"
+        );
+        Self {
+            threads,
+            runtime: Arc::new(Mutex::new(None)),
+            tasks: runtime_raw::TaskCounters::new(),
+        }
+    }
+}
+
+impl runtime_raw::WorkerThreads for TokioThreadPool {
+    type Runtime = TokioThreadPool;
+
+    fn with_worker_threads(&self, threads: usize) -> TokioThreadPool {
+        TokioThreadPool::new(threads)
+    }
+}
+
+impl runtime_raw::Runtime for TokioThreadPool {
+    fn spawn_boxed(&self, fut: BoxFuture<'static, ()>) -> Result<(), SpawnError> {
+        let mut runtime = self.runtime.lock().unwrap();
+        if runtime.is_none() {
+            // The worker threads need a `'static` reference to the runtime, so a handle to the
+            // pool is leaked once when it's started.
+            let this: &'static TokioThreadPool = Box::leak(Box::new(self.clone()));
+            let pool = tokio::runtime::Builder::new()
+                .core_threads(self.threads)
+                .after_start(move || {
+                    runtime_raw::set_runtime(this);
+                })
+                .build()
+                .map_err(|_| SpawnError::shutdown())?;
+            *runtime = Some(pool);
+        }
+
+        let executor = runtime.as_ref().unwrap().executor();
+        executor.spawn(self.tasks.track(fut).unit_error().compat());
+        Ok(())
+    }
+
+    fn worker_threads(&self) -> Option<usize> {
+        Some(self.threads)
+    }
+
+    fn metrics(&self) -> runtime_raw::RuntimeMetrics {
+        runtime_raw::RuntimeMetrics {
+            num_workers: self.worker_threads(),
+            num_spawned_tasks: Some(self.tasks.spawned()),
+            num_pending_tasks: Some(self.tasks.pending()),
+        }
+    }
+
+    fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        self.tasks.wait_idle()
+    }
+
+    fn block_on_boxed(&'static self, fut: LocalBoxFuture<'_, ()>) {
+        block_on_with_drivers(self, fut)
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) -> Result<(), SpawnError> {
+        let mut f = Some(f);
+        let blocking =
+            future::poll_fn(
+                move |_| match tokio_threadpool::blocking(|| (f.take().unwrap())()) {
+                    Ok(futures01::Async::Ready(())) => Poll::Ready(()),
+                    Ok(futures01::Async::NotReady) => Poll::Pending,
+                    Err(_) => panic!("the Tokio runtime is not running on a thread pool"),
+                },
+            );
+        self.spawn_boxed(blocking.boxed())
+    }
+
+    // I/O and timers are registered with the reactor and timer of the thread they're created on,
+    // so they're the same as for the default runtime.
+
+    fn connect_tcp_stream(
+        &self,
+        addr: &SocketAddr,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::TcpStream>>>> {
+        Tokio.connect_tcp_stream(addr)
+    }
+
+    fn bind_tcp_listener(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Tokio.bind_tcp_listener(addr)
+    }
+
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        addr: &SocketAddr,
+        backlog: i32,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Tokio.bind_tcp_listener_with_backlog(addr, backlog)
+    }
+
+    fn bind_tcp_listener_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Tokio.bind_tcp_listener_reuseport(addr)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn bind_tcp_listener_device(
+        &self,
+        addr: &SocketAddr,
+        interface: &str,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Tokio.bind_tcp_listener_device(addr, interface)
+    }
+
+    fn tcp_listener_from_std(
+        &self,
+        listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        Tokio.tcp_listener_from_std(listener)
+    }
+
     fn bind_udp_socket(
         &self,
         addr: &SocketAddr,
     ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
-        let tokio_socket = tokio::net::UdpSocket::bind(&addr)?;
-        Ok(Box::pin(UdpSocket { tokio_socket }))
+        Tokio.bind_udp_socket(addr)
+    }
+
+    fn udp_socket_from_std(
+        &self,
+        socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Tokio.udp_socket_from_std(socket)
+    }
+
+    fn bind_udp_socket_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Tokio.bind_udp_socket_reuseport(addr)
+    }
+
+    #[cfg(unix)]
+    fn connect_unix_stream(
+        &self,
+        path: &Path,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::UnixStream>>>> {
+        Tokio.connect_unix_stream(path)
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_listener(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixListener>>> {
+        Tokio.bind_unix_listener(path)
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_datagram(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Tokio.bind_unix_datagram(path)
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_pair(
+        &self,
+    ) -> io::Result<(
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+    )> {
+        Tokio.unix_datagram_pair()
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_from_std(
+        &self,
+        socket: std::os::unix::net::UnixDatagram,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        Tokio.unix_datagram_from_std(socket)
     }
 
     fn new_delay(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Delay>> {
-        let tokio_delay = TokioDelay::new(Instant::now() + dur);
-        Box::pin(Delay { tokio_delay })
+        Tokio.new_delay(dur)
     }
 
     fn new_delay_at(&self, at: Instant) -> Pin<Box<dyn runtime_raw::Delay>> {
-        let tokio_delay = TokioDelay::new(at);
-        Box::pin(Delay { tokio_delay })
+        Tokio.new_delay_at(at)
     }
 
     fn new_interval(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
-        let tokio_interval = TokioInterval::new(Instant::now(), dur);
-        Box::pin(Interval { tokio_interval })
+        Tokio.new_interval(dur)
+    }
+
+    fn new_interval_at(&self, at: Instant, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Tokio.new_interval_at(at, dur)
     }
 }
 
 /// The single-threaded Tokio runtime based on `tokio-current-thread`.
+///
+/// The runtime runs on a background thread that is started the first time a future is spawned on
+/// it, and keeps running until [`shutdown`] is called.
+///
+/// [`shutdown`]: struct.TokioCurrentThread.html#method.shutdown
 #[derive(Debug)]
 pub struct TokioCurrentThread;
 
+lazy_static! {
+    static ref TOKIO_CURRENT_THREAD: Mutex<Option<CurrentThread>> = Mutex::new(None);
+}
+
+/// The background thread running the single-threaded Tokio runtime.
+#[derive(Debug)]
+struct CurrentThread {
+    handle: tokio::runtime::current_thread::Handle,
+    shutdown: oneshot::Sender<()>,
+    thread: thread::JoinHandle<()>,
+}
+
+impl CurrentThread {
+    fn start() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let (shutdown, shutdown_rx) = oneshot::channel();
+
+        let thread = thread::Builder::new()
+            .name("runtime-tokio-current-thread".into())
+            .spawn(move || {
+                let mut rt = tokio::runtime::current_thread::Runtime::new().unwrap();
+                let handle = rt.handle();
+                tx.send(handle).unwrap();
+
+                runtime_raw::set_runtime(&TokioCurrentThread);
+                // Futures that haven't completed yet are dropped along with the runtime.
+                let _ = rt.block_on(shutdown_rx.compat());
+            })
+            .expect("cannot spawn the Tokio current-thread runtime");
+
+        let handle = rx.recv().unwrap();
+        Self {
+            handle,
+            shutdown,
+            thread,
+        }
+    }
+}
+
+impl TokioCurrentThread {
+    /// Stop the background thread running this runtime, and wait for it to exit.
+    ///
+    /// Futures that haven't completed yet are dropped. Spawning a future afterwards starts a new
+    /// background thread.
+    ///
+    /// Returns `false` if the runtime wasn't running. When called from a future running on this
+    /// runtime, the thread is signalled to stop but not waited on, since it can't join itself.
+    pub fn shutdown() -> bool {
+        let current = TOKIO_CURRENT_THREAD.lock().unwrap().take();
+        let CurrentThread {
+            shutdown, thread, ..
+        } = match current {
+            Some(current) => current,
+            None => return false,
+        };
+
+        let _ = shutdown.send(());
+        if thread.thread().id() != thread::current().id() {
+            let _ = thread.join();
+        }
+        true
+    }
+}
+
 impl runtime_raw::Runtime for TokioCurrentThread {
     fn spawn_boxed(&self, fut: BoxFuture<'static, ()>) -> Result<(), SpawnError> {
-        lazy_static! {
-            static ref TOKIO_RUNTIME: Mutex<tokio::runtime::current_thread::Handle> = {
-                let (tx, rx) = mpsc::channel();
-
-                thread::spawn(move || {
-                    let mut rt = tokio::runtime::current_thread::Runtime::new().unwrap();
-                    let handle = rt.handle();
-                    tx.send(handle).unwrap();
-
-                    runtime_raw::set_runtime(&TokioCurrentThread);
-                    let forever = futures01::future::poll_fn(|| {
-                        Ok::<futures01::Async<()>, ()>(futures01::Async::NotReady)
-                    });
-                    rt.block_on(forever).unwrap();
-                });
-
-                let handle = rx.recv().unwrap();
-                Mutex::new(handle)
-            };
+        let mut current = TOKIO_CURRENT_THREAD.lock().unwrap();
+        current
+            .get_or_insert_with(CurrentThread::start)
+            .handle
+            .spawn(
+                TOKIO_CURRENT_THREAD_TASK_COUNTERS
+                    .track(fut)
+                    .unit_error()
+                    .compat(),
+            )
+            .map_err(|_| SpawnError::shutdown())
+    }
+
+    fn worker_threads(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn metrics(&self) -> runtime_raw::RuntimeMetrics {
+        runtime_raw::RuntimeMetrics {
+            num_workers: self.worker_threads(),
+            num_spawned_tasks: Some(TOKIO_CURRENT_THREAD_TASK_COUNTERS.spawned()),
+            num_pending_tasks: Some(TOKIO_CURRENT_THREAD_TASK_COUNTERS.pending()),
         }
+    }
 
-        TOKIO_RUNTIME
-            .lock()
-            .unwrap()
-            .spawn(fut.unit_error().compat())
-            .unwrap();
-        Ok(())
+    fn wait_idle(&self) -> BoxFuture<'static, ()> {
+        TOKIO_CURRENT_THREAD_TASK_COUNTERS.wait_idle()
+    }
+
+    fn block_on_boxed(&'static self, fut: LocalBoxFuture<'_, ()>) {
+        block_on_with_drivers(self, fut)
     }
 
     fn connect_tcp_stream(
@@ -151,7 +658,49 @@ impl runtime_raw::Runtime for TokioCurrentThread {
         &self,
         addr: &SocketAddr,
     ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
-        let tokio_listener = tokio::net::TcpListener::bind(&addr)?;
+        let tokio_listener = tokio::net::TcpListener::bind(addr)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    fn bind_tcp_listener_with_backlog(
+        &self,
+        addr: &SocketAddr,
+        backlog: i32,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener(addr, backlog)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    fn bind_tcp_listener_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener_reuseport(addr)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn bind_tcp_listener_device(
+        &self,
+        addr: &SocketAddr,
+        interface: &str,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let listener = tcp::bind_std_listener_device(addr, interface)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
+        Ok(Box::pin(TcpListener { tokio_listener }))
+    }
+
+    fn tcp_listener_from_std(
+        &self,
+        listener: std::net::TcpListener,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::TcpListener>>> {
+        let handle = tokio::reactor::Handle::default();
+        let tokio_listener = tokio::net::TcpListener::from_std(listener, &handle)?;
         Ok(Box::pin(TcpListener { tokio_listener }))
     }
 
@@ -159,22 +708,93 @@ impl runtime_raw::Runtime for TokioCurrentThread {
         &self,
         addr: &SocketAddr,
     ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
-        let tokio_socket = tokio::net::UdpSocket::bind(&addr)?;
-        Ok(Box::pin(UdpSocket { tokio_socket }))
+        Ok(Box::pin(UdpSocket::bind(addr)?))
+    }
+
+    fn udp_socket_from_std(
+        &self,
+        socket: std::net::UdpSocket,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        Ok(Box::pin(UdpSocket::from_std(socket)?))
+    }
+
+    fn bind_udp_socket_reuseport(
+        &self,
+        addr: &SocketAddr,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UdpSocket>>> {
+        let socket = udp::bind_std_socket_reuseport(addr)?;
+        self.udp_socket_from_std(socket)
+    }
+
+    #[cfg(unix)]
+    fn connect_unix_stream(
+        &self,
+        path: &Path,
+    ) -> BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::UnixStream>>>> {
+        use futures01::Future;
+
+        let tokio_connect = tokio::net::UnixStream::connect(path);
+        let connect = tokio_connect.map(|tokio_stream| {
+            Box::pin(UnixStream { tokio_stream }) as Pin<Box<dyn runtime_raw::UnixStream>>
+        });
+        connect.compat().boxed()
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_listener(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixListener>>> {
+        let tokio_listener = tokio::net::UnixListener::bind(path)?;
+        Ok(Box::pin(UnixListener { tokio_listener }))
+    }
+
+    #[cfg(unix)]
+    fn bind_unix_datagram(
+        &self,
+        path: &Path,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        let tokio_datagram = tokio::net::UnixDatagram::bind(path)?;
+        Ok(Box::pin(UnixDatagram { tokio_datagram }))
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_pair(
+        &self,
+    ) -> io::Result<(
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+        Pin<Box<dyn runtime_raw::UnixDatagram>>,
+    )> {
+        let (a, b) = tokio::net::UnixDatagram::pair()?;
+        let a = Box::pin(UnixDatagram { tokio_datagram: a });
+        let b = Box::pin(UnixDatagram { tokio_datagram: b });
+        Ok((a, b))
+    }
+
+    #[cfg(unix)]
+    fn unix_datagram_from_std(
+        &self,
+        socket: std::os::unix::net::UnixDatagram,
+    ) -> io::Result<Pin<Box<dyn runtime_raw::UnixDatagram>>> {
+        socket.set_nonblocking(true)?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_datagram = tokio::net::UnixDatagram::from_std(socket, &handle)?;
+        Ok(Box::pin(UnixDatagram { tokio_datagram }))
     }
 
     fn new_delay(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Delay>> {
-        let tokio_delay = TokioDelay::new(Instant::now() + dur);
-        Box::pin(Delay { tokio_delay })
+        Box::pin(Delay::new(self.now() + dur))
     }
 
     fn new_delay_at(&self, at: Instant) -> Pin<Box<dyn runtime_raw::Delay>> {
-        let tokio_delay = TokioDelay::new(at);
-        Box::pin(Delay { tokio_delay })
+        Box::pin(Delay::new(at))
     }
 
     fn new_interval(&self, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
-        let tokio_interval = TokioInterval::new(Instant::now(), dur);
-        Box::pin(Interval { tokio_interval })
+        Box::pin(Interval::new(self.now(), dur))
+    }
+
+    fn new_interval_at(&self, at: Instant, dur: Duration) -> Pin<Box<dyn runtime_raw::Interval>> {
+        Box::pin(Interval::new(at, dur))
     }
 }
@@ -1,12 +1,12 @@
 use futures::prelude::*;
 
 use futures::compat::Compat01As03;
-use futures01;
 
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub(crate) struct TcpStream {
@@ -19,23 +19,25 @@ pub(crate) struct TcpListener {
 }
 
 impl runtime_raw::TcpStream for TcpStream {
-    fn poll_write_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        match self.tokio_stream.poll_write_ready()? {
-            futures01::Async::Ready(_) => Poll::Ready(Ok(())),
-            futures01::Async::NotReady => Poll::Pending,
-        }
+    // Tokio registers interest with the current futures 0.1 task, so polling for readiness has to
+    // happen inside of a compat future for the task to be woken up.
+
+    fn poll_write_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let stream = &self.tokio_stream;
+        let mut ready = Compat01As03::new(futures01::future::poll_fn(|| stream.poll_write_ready()));
+        Pin::new(&mut ready).poll(cx).map_ok(|_| ())
     }
 
-    fn poll_read_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    fn poll_read_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let stream = &self.tokio_stream;
         let mask = mio::Ready::readable();
-        match self.tokio_stream.poll_read_ready(mask)? {
-            futures01::Async::Ready(_) => Poll::Ready(Ok(())),
-            futures01::Async::NotReady => Poll::Pending,
-        }
+        let mut ready =
+            Compat01As03::new(futures01::future::poll_fn(|| stream.poll_read_ready(mask)));
+        Pin::new(&mut ready).poll(cx).map_ok(|_| ())
     }
 
     fn take_error(&self) -> io::Result<Option<io::Error>> {
-        Ok(None)
+        socket2::SockRef::from(&self.tokio_stream).take_error()
     }
 
     fn local_addr(&self) -> io::Result<SocketAddr> {
@@ -50,6 +52,45 @@ impl runtime_raw::TcpStream for TcpStream {
         self.tokio_stream.shutdown(how)
     }
 
+    fn nodelay(&self) -> io::Result<bool> {
+        self.tokio_stream.nodelay()
+    }
+
+    fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.tokio_stream.set_nodelay(nodelay)
+    }
+
+    fn ttl(&self) -> io::Result<u32> {
+        self.tokio_stream.ttl()
+    }
+
+    fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.tokio_stream.set_ttl(ttl)
+    }
+
+    fn tos(&self) -> io::Result<u32> {
+        socket2::SockRef::from(&self.tokio_stream).tos()
+    }
+
+    fn set_tos(&self, tos: u32) -> io::Result<()> {
+        socket2::SockRef::from(&self.tokio_stream).set_tos(tos)
+    }
+
+    fn linger(&self) -> io::Result<Option<Duration>> {
+        self.tokio_stream.linger()
+    }
+
+    fn set_linger(&self, dur: Option<Duration>) -> io::Result<()> {
+        self.tokio_stream.set_linger(dur)
+    }
+
+    fn try_clone(&self) -> io::Result<Pin<Box<dyn runtime_raw::TcpStream>>> {
+        let socket = socket2::SockRef::from(&self.tokio_stream).try_clone()?;
+        let handle = tokio::reactor::Handle::default();
+        let tokio_stream = tokio::net::TcpStream::from_std(socket.into(), &handle)?;
+        Ok(Box::pin(TcpStream { tokio_stream }))
+    }
+
     #[cfg(unix)]
     fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
         use std::os::unix::io::AsRawFd;
@@ -61,10 +102,10 @@ impl AsyncRead for TcpStream {
     fn poll_read(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
-        mut buf: &mut [u8],
+        buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
         let mut stream = Compat01As03::new(&self.tokio_stream);
-        Pin::new(&mut stream).poll_read(cx, &mut buf)
+        Pin::new(&mut stream).poll_read(cx, buf)
     }
 }
 
@@ -75,12 +116,12 @@ impl AsyncWrite for TcpStream {
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
         let mut stream = Compat01As03::new(&self.tokio_stream);
-        Pin::new(&mut stream).poll_write(cx, &buf)
+        Pin::new(&mut stream).poll_write(cx, buf)
     }
 
-    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        let mut stream = Compat01As03::new(&self.tokio_stream);
-        Pin::new(&mut stream).poll_flush(cx)
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Tokio's `TcpStream` doesn't buffer writes, they go straight to the socket.
+        Poll::Ready(Ok(()))
     }
 
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
@@ -94,15 +135,23 @@ impl runtime_raw::TcpListener for TcpListener {
         self.tokio_listener.local_addr()
     }
 
+    fn ttl(&self) -> io::Result<u32> {
+        self.tokio_listener.ttl()
+    }
+
+    fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.tokio_listener.set_ttl(ttl)
+    }
+
     fn poll_accept(
         self: Pin<&mut Self>,
         _cx: &mut Context<'_>,
-    ) -> Poll<io::Result<Pin<Box<dyn runtime_raw::TcpStream>>>> {
+    ) -> Poll<io::Result<(Pin<Box<dyn runtime_raw::TcpStream>>, SocketAddr)>> {
         let listener = &mut self.get_mut().tokio_listener;
         match listener.poll_accept()? {
-            futures01::Async::Ready((tokio_stream, _)) => {
+            futures01::Async::Ready((tokio_stream, addr)) => {
                 let stream = Box::pin(TcpStream { tokio_stream });
-                Poll::Ready(Ok(stream))
+                Poll::Ready(Ok((stream, addr)))
             }
             futures01::Async::NotReady => Poll::Pending,
         }
@@ -114,3 +163,59 @@ impl runtime_raw::TcpListener for TcpListener {
         self.tokio_listener.as_raw_fd()
     }
 }
+
+/// Bind a standard library listener, using `backlog` as the size of the pending connection queue.
+pub(crate) fn bind_std_listener(
+    addr: &SocketAddr,
+    backlog: i32,
+) -> io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(*addr), Type::STREAM, None)?;
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&(*addr).into())?;
+    socket.listen(backlog)?;
+    Ok(socket.into())
+}
+
+/// Bind a standard library listener with `SO_REUSEADDR` and, where the platform supports it,
+/// `SO_REUSEPORT` set.
+pub(crate) fn bind_std_listener_reuseport(addr: &SocketAddr) -> io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(*addr), Type::STREAM, None)?;
+    set_reuseport(&socket)?;
+    socket.bind(&(*addr).into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
+
+/// Set `SO_REUSEADDR` on a socket that hasn't been bound yet, and `SO_REUSEPORT` where the
+/// platform supports it.
+pub(crate) fn set_reuseport(socket: &socket2::Socket) -> io::Result<()> {
+    socket.set_reuse_address(true)?;
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    socket.set_reuse_port(true)?;
+    Ok(())
+}
+
+/// Bind a standard library listener to the network interface named `interface`, by setting
+/// `SO_BINDTODEVICE` before binding to `addr`.
+///
+/// Like the listeners created by `bind`, the socket has `SO_REUSEADDR` set, so the address can be
+/// bound again right after a previous listener on it was closed.
+#[cfg(target_os = "linux")]
+pub(crate) fn bind_std_listener_device(
+    addr: &SocketAddr,
+    interface: &str,
+) -> io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(*addr), Type::STREAM, None)?;
+    socket.set_reuse_address(true)?;
+    socket.bind_device(Some(interface.as_bytes()))?;
+    socket.bind(&(*addr).into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
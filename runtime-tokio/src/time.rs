@@ -1,14 +1,31 @@
+use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use std::time::Instant;
+use std::thread;
+use std::time::{Duration, Instant};
 
+use futures::channel::oneshot;
 use futures::compat::Compat01As03;
 use futures::prelude::*;
-use tokio::timer::{Delay as TokioDelay, Interval as TokioInterval};
+use runtime_raw::{InstantTick, MissedTickBehavior, Runtime};
+use tokio::timer::Delay as TokioDelay;
+
+use crate::Tokio;
 
 #[derive(Debug)]
 pub(crate) struct Delay {
-    pub(crate) tokio_delay: TokioDelay,
+    tokio_delay: TokioDelay,
+    /// Set once the timer has failed, after which the delay is waited on by a thread of its own.
+    fallback: Option<oneshot::Receiver<()>>,
+}
+
+impl Delay {
+    pub(crate) fn new(at: Instant) -> Self {
+        Self {
+            tokio_delay: TokioDelay::new(at),
+            fallback: None,
+        }
+    }
 }
 
 impl runtime_raw::Delay for Delay {}
@@ -16,31 +33,77 @@ impl runtime_raw::Delay for Delay {}
 impl Future for Delay {
     type Output = Instant;
 
-    #[inline]
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let mut fut = Compat01As03::new(&mut self.tokio_delay);
-        futures::ready!(Pin::new(&mut fut).poll(cx)).unwrap();
+        if self.fallback.is_none() {
+            let mut fut = Compat01As03::new(&mut self.tokio_delay);
+            // The timer fails if it has shut down or has too many timers registered, see
+            // https://docs.rs/tokio/0.1.20/tokio/timer/struct.Error.html. A delay can't report
+            // that, so it sleeps on a thread instead to still fire at its deadline.
+            if futures::ready!(Pin::new(&mut fut).poll(cx)).is_ok() {
+                return Poll::Ready(Instant::now());
+            }
+            let deadline = self.tokio_delay.deadline();
+            let (tx, rx) = oneshot::channel();
+            thread::spawn(move || {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                let _ = tx.send(());
+            });
+            self.fallback = Some(rx);
+        }
+        let fallback = self.fallback.as_mut().unwrap();
+        let _ = futures::ready!(fallback.poll_unpin(cx));
         Poll::Ready(Instant::now())
     }
 }
 
 #[derive(Debug)]
 pub(crate) struct Interval {
-    pub(crate) tokio_interval: TokioInterval,
+    tokio_delay: TokioDelay,
+    period: Duration,
+    missed_tick_behavior: MissedTickBehavior,
+}
+
+impl Interval {
+    pub(crate) fn new(at: Instant, period: Duration) -> Self {
+        Self {
+            tokio_delay: TokioDelay::new(at),
+            period,
+            missed_tick_behavior: MissedTickBehavior::default(),
+        }
+    }
 }
 
-impl runtime_raw::Interval for Interval {}
+impl runtime_raw::Interval for Interval {
+    fn set_missed_tick_behavior(mut self: Pin<&mut Self>, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    fn reset(mut self: Pin<&mut Self>) {
+        let deadline = Tokio.now() + self.period;
+        self.tokio_delay.reset(deadline);
+    }
+
+    fn poll_tick(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<InstantTick>> {
+        let mut fut = Compat01As03::new(&mut self.tokio_delay);
+        // The timer fails if it has shut down or has too many timers registered, see
+        // https://docs.rs/tokio/0.1.20/tokio/timer/struct.Error.html. A failed delay keeps
+        // resolving to the error, so the interval never ticks again.
+        futures::ready!(Pin::new(&mut fut).poll(cx)).map_err(io::Error::other)?;
+        let now = Instant::now();
+        let tick = InstantTick::new(self.tokio_delay.deadline(), now, self.period);
+        let deadline =
+            self.missed_tick_behavior
+                .next_deadline(self.tokio_delay.deadline(), now, self.period);
+        self.tokio_delay.reset(deadline);
+        Poll::Ready(Ok(tick))
+    }
+}
 
 impl Stream for Interval {
     type Item = Instant;
 
     #[inline]
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut stream = Compat01As03::new(&mut self.tokio_interval);
-        // https://docs.rs/tokio/0.1.20/tokio/timer/struct.Error.html
-        futures::ready!(Pin::new(&mut stream).poll_next(cx))
-            .unwrap()
-            .unwrap();
-        Poll::Ready(Some(Instant::now()))
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        runtime_raw::Interval::poll_tick(self, cx).map(|tick| tick.ok().map(|tick| tick.when))
     }
 }
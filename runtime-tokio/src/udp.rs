@@ -1,18 +1,52 @@
-use futures01;
+use tokio::reactor::PollEvented2;
 
 use std::io;
+use std::mem::MaybeUninit;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use crate::evented::{poll_read_with, poll_write_with};
+
 #[derive(Debug)]
 pub(crate) struct UdpSocket {
-    pub tokio_socket: tokio::net::udp::UdpSocket,
+    io: PollEvented2<mio::net::UdpSocket>,
+}
+
+impl UdpSocket {
+    pub(crate) fn bind(addr: &SocketAddr) -> io::Result<Self> {
+        mio::net::UdpSocket::bind(addr).map(Self::new)
+    }
+
+    /// Register a standard library socket with the reactor.
+    pub(crate) fn from_std(socket: std::net::UdpSocket) -> io::Result<Self> {
+        mio::net::UdpSocket::from_socket(socket).map(Self::new)
+    }
+
+    fn new(socket: mio::net::UdpSocket) -> Self {
+        UdpSocket {
+            io: PollEvented2::new(socket),
+        }
+    }
+
+    fn socket(&self) -> &mio::net::UdpSocket {
+        self.io.get_ref()
+    }
 }
 
 impl runtime_raw::UdpSocket for UdpSocket {
     fn local_addr(&self) -> io::Result<SocketAddr> {
-        self.tokio_socket.local_addr()
+        self.socket().local_addr()
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        let addr = socket2::SockRef::from(self.socket()).peer_addr()?;
+        addr.as_socket().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "peer address is not an IP address",
+            )
+        })
     }
 
     fn poll_send_to(
@@ -21,11 +55,7 @@ impl runtime_raw::UdpSocket for UdpSocket {
         buf: &[u8],
         receiver: &SocketAddr,
     ) -> Poll<io::Result<usize>> {
-        let socket = &mut self.get_mut().tokio_socket;
-        match socket.poll_send_to(&buf, &receiver)? {
-            futures01::Async::Ready(size) => Poll::Ready(Ok(size)),
-            futures01::Async::NotReady => Poll::Pending,
-        }
+        poll_write_with(&self.io, |socket| socket.send_to(buf, receiver))
     }
 
     fn poll_recv_from(
@@ -33,87 +63,220 @@ impl runtime_raw::UdpSocket for UdpSocket {
         _cx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<io::Result<(usize, SocketAddr)>> {
-        let socket = &mut self.get_mut().tokio_socket;
-        match socket.poll_recv_from(buf)? {
-            futures01::Async::Ready((size, addr)) => Poll::Ready(Ok((size, addr))),
-            futures01::Async::NotReady => Poll::Pending,
+        poll_read_with(&self.io, |socket| socket.recv_from(buf))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn poll_send_batch(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        packets: &[(&[u8], SocketAddr)],
+    ) -> Poll<io::Result<usize>> {
+        use std::os::unix::io::AsRawFd;
+
+        if packets.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        poll_write_with(&self.io, |socket| {
+            runtime_raw::mmsg::send(socket.as_raw_fd(), packets)
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn poll_recv_batch(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        bufs: &mut [&mut [u8]],
+    ) -> Poll<io::Result<Vec<(usize, SocketAddr)>>> {
+        use std::os::unix::io::AsRawFd;
+
+        if bufs.is_empty() {
+            return Poll::Ready(Ok(Vec::new()));
         }
+        poll_read_with(&self.io, |socket| {
+            runtime_raw::mmsg::recv(socket.as_raw_fd(), bufs)
+        })
+    }
+
+    fn poll_peek_from(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        poll_read_with(&self.io, |socket| peek_from(socket, buf))
+    }
+
+    fn connect(&self, addr: &SocketAddr) -> io::Result<()> {
+        self.socket().connect(*addr)
+    }
+
+    fn poll_send(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.io, |socket| socket.send(buf))
+    }
+
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_read_with(&self.io, |socket| socket.recv(buf))
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option for this socket.
+    fn recv_buffer_size(&self) -> io::Result<usize> {
+        socket2::SockRef::from(self.socket()).recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option for this socket.
+    fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        socket2::SockRef::from(self.socket()).set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option for this socket.
+    fn send_buffer_size(&self) -> io::Result<usize> {
+        socket2::SockRef::from(self.socket()).send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option for this socket.
+    fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        socket2::SockRef::from(self.socket()).set_send_buffer_size(size)
     }
 
     /// Gets the value of the `SO_BROADCAST` option for this socket.
     fn broadcast(&self) -> io::Result<bool> {
-        self.tokio_socket.broadcast()
+        self.socket().broadcast()
     }
 
     /// Sets the value of the `SO_BROADCAST` option for this socket.
     fn set_broadcast(&self, on: bool) -> io::Result<()> {
-        self.tokio_socket.set_broadcast(on)
+        self.socket().set_broadcast(on)
     }
 
     /// Gets the value of the `IP_MULTICAST_LOOP` option for this socket.
     fn multicast_loop_v4(&self) -> io::Result<bool> {
-        self.tokio_socket.multicast_loop_v4()
+        self.socket().multicast_loop_v4()
     }
 
     /// Sets the value of the `IP_MULTICAST_LOOP` option for this socket.
     fn set_multicast_loop_v4(&self, on: bool) -> io::Result<()> {
-        self.tokio_socket.set_multicast_loop_v4(on)
+        self.socket().set_multicast_loop_v4(on)
     }
 
     /// Gets the value of the `IP_MULTICAST_TTL` option for this socket.
     fn multicast_ttl_v4(&self) -> io::Result<u32> {
-        self.tokio_socket.multicast_ttl_v4()
+        self.socket().multicast_ttl_v4()
     }
 
     /// Sets the value of the `IP_MULTICAST_TTL` option for this socket.
     fn set_multicast_ttl_v4(&self, ttl: u32) -> io::Result<()> {
-        self.tokio_socket.set_multicast_ttl_v4(ttl)
+        self.socket().set_multicast_ttl_v4(ttl)
     }
 
     /// Gets the value of the `IPV6_MULTICAST_LOOP` option for this socket.
     fn multicast_loop_v6(&self) -> io::Result<bool> {
-        self.tokio_socket.multicast_loop_v6()
+        self.socket().multicast_loop_v6()
     }
 
     /// Sets the value of the `IPV6_MULTICAST_LOOP` option for this socket.
     fn set_multicast_loop_v6(&self, on: bool) -> io::Result<()> {
-        self.tokio_socket.set_multicast_loop_v6(on)
+        self.socket().set_multicast_loop_v6(on)
+    }
+
+    /// Gets the value of the `IP_MULTICAST_IF` option for this socket.
+    fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        socket2::SockRef::from(self.socket()).multicast_if_v4()
+    }
+
+    /// Sets the value of the `IP_MULTICAST_IF` option for this socket.
+    fn set_multicast_if_v4(&self, interface: &Ipv4Addr) -> io::Result<()> {
+        socket2::SockRef::from(self.socket()).set_multicast_if_v4(interface)
+    }
+
+    /// Gets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    fn multicast_if_v6(&self) -> io::Result<u32> {
+        socket2::SockRef::from(self.socket()).multicast_if_v6()
+    }
+
+    /// Sets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    fn set_multicast_if_v6(&self, interface: u32) -> io::Result<()> {
+        socket2::SockRef::from(self.socket()).set_multicast_if_v6(interface)
     }
 
     /// Gets the value of the `IP_TTL` option for this socket.
     fn ttl(&self) -> io::Result<u32> {
-        self.tokio_socket.ttl()
+        self.socket().ttl()
     }
 
     /// Sets the value for the `IP_TTL` option on this socket.
     fn set_ttl(&self, ttl: u32) -> io::Result<()> {
-        self.tokio_socket.set_ttl(ttl)
+        self.socket().set_ttl(ttl)
+    }
+
+    /// Gets the value of the `IP_TOS` option for this socket.
+    fn tos(&self) -> io::Result<u32> {
+        socket2::SockRef::from(self.socket()).tos()
+    }
+
+    /// Sets the value for the `IP_TOS` option on this socket.
+    fn set_tos(&self, tos: u32) -> io::Result<()> {
+        socket2::SockRef::from(self.socket()).set_tos(tos)
     }
 
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     fn join_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
-        self.tokio_socket.join_multicast_v4(multiaddr, interface)
+        self.socket().join_multicast_v4(multiaddr, interface)
     }
 
     /// Executes an operation of the `IPV6_ADD_MEMBERSHIP` type.
     fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
-        self.tokio_socket.join_multicast_v6(multiaddr, interface)
+        self.socket().join_multicast_v6(multiaddr, interface)
     }
 
     /// Executes an operation of the `IP_DROP_MEMBERSHIP` type.
     fn leave_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
-        self.tokio_socket.leave_multicast_v4(multiaddr, interface)
+        self.socket().leave_multicast_v4(multiaddr, interface)
     }
 
     /// Executes an operation of the `IPV6_DROP_MEMBERSHIP` type.
     fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
-        self.tokio_socket.leave_multicast_v6(multiaddr, interface)
+        self.socket().leave_multicast_v6(multiaddr, interface)
     }
 
     /// Extracts the raw file descriptor.
     #[cfg(unix)]
     fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
         use std::os::unix::io::AsRawFd;
-        self.tokio_socket.as_raw_fd()
+        self.socket().as_raw_fd()
     }
 }
+
+/// Bind a standard library socket with `SO_REUSEADDR` and, where the platform supports it,
+/// `SO_REUSEPORT` set.
+pub(crate) fn bind_std_socket_reuseport(addr: &SocketAddr) -> io::Result<std::net::UdpSocket> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(*addr), Type::DGRAM, None)?;
+    super::tcp::set_reuseport(&socket)?;
+    socket.bind(&(*addr).into())?;
+    Ok(socket.into())
+}
+
+/// Receive the next datagram with `MSG_PEEK`, leaving it queued on the socket.
+#[allow(unsafe_code)]
+fn peek_from(socket: &mio::net::UdpSocket, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+    // The kernel only ever writes initialized bytes into the buffer, so it can be handed out as
+    // possibly uninitialized memory.
+    let uninit = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+    let (len, addr) = socket2::SockRef::from(socket).peek_from(uninit)?;
+    let addr = addr.as_socket().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "received a datagram from an address that isn't an IP address",
+        )
+    })?;
+    Ok((len, addr))
+}
@@ -0,0 +1,179 @@
+use futures::prelude::*;
+
+use futures::compat::Compat01As03;
+
+use std::io;
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[derive(Debug)]
+pub(crate) struct UnixStream {
+    pub tokio_stream: tokio::net::UnixStream,
+}
+
+#[derive(Debug)]
+pub(crate) struct UnixListener {
+    pub tokio_listener: tokio::net::UnixListener,
+}
+
+#[derive(Debug)]
+pub(crate) struct UnixDatagram {
+    pub tokio_datagram: tokio::net::UnixDatagram,
+}
+
+impl runtime_raw::UnixStream for UnixStream {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.tokio_stream.local_addr()
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.tokio_stream.peer_addr()
+    }
+
+    fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()> {
+        self.tokio_stream.shutdown(how)
+    }
+
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        use std::os::unix::io::AsRawFd;
+        self.tokio_stream.as_raw_fd()
+    }
+}
+
+impl AsyncRead for UnixStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut stream = Compat01As03::new(&self.tokio_stream);
+        Pin::new(&mut stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for UnixStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut stream = Compat01As03::new(&self.tokio_stream);
+        Pin::new(&mut stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut stream = Compat01As03::new(&self.tokio_stream);
+        Pin::new(&mut stream).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut stream = Compat01As03::new(&self.tokio_stream);
+        Pin::new(&mut stream).poll_close(cx)
+    }
+}
+
+impl runtime_raw::UnixListener for UnixListener {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.tokio_listener.local_addr()
+    }
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<io::Result<(Pin<Box<dyn runtime_raw::UnixStream>>, SocketAddr)>> {
+        match self.tokio_listener.poll_accept()? {
+            futures01::Async::Ready((tokio_stream, addr)) => {
+                let stream = Box::pin(UnixStream { tokio_stream });
+                Poll::Ready(Ok((stream, addr)))
+            }
+            futures01::Async::NotReady => Poll::Pending,
+        }
+    }
+
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        use std::os::unix::io::AsRawFd;
+        self.tokio_listener.as_raw_fd()
+    }
+}
+
+impl runtime_raw::UnixDatagram for UnixDatagram {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.tokio_datagram.local_addr()
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.tokio_datagram.peer_addr()
+    }
+
+    fn connect(&self, path: &Path) -> io::Result<()> {
+        self.tokio_datagram.connect(path)
+    }
+
+    fn poll_send_to(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+        path: &Path,
+    ) -> Poll<io::Result<usize>> {
+        match self.tokio_datagram.poll_send_to(buf, path)? {
+            futures01::Async::Ready(size) => Poll::Ready(Ok(size)),
+            futures01::Async::NotReady => Poll::Pending,
+        }
+    }
+
+    fn poll_recv_from(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        match self.tokio_datagram.poll_recv_from(buf)? {
+            futures01::Async::Ready((size, addr)) => Poll::Ready(Ok((size, addr))),
+            futures01::Async::NotReady => Poll::Pending,
+        }
+    }
+
+    fn poll_send(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.tokio_datagram.poll_send(buf)? {
+            futures01::Async::Ready(size) => Poll::Ready(Ok(size)),
+            futures01::Async::NotReady => Poll::Pending,
+        }
+    }
+
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.tokio_datagram.poll_recv(buf)? {
+            futures01::Async::Ready(size) => Poll::Ready(Ok(size)),
+            futures01::Async::NotReady => {
+                // Receiving after the read half was shut down would block rather than return
+                // end-of-file, even though the socket is reported as readable.
+                #[cfg(target_os = "linux")]
+                {
+                    use std::os::unix::io::AsRawFd;
+                    let fd = self.tokio_datagram.as_raw_fd();
+                    if runtime_raw::shutdown::is_read_shutdown(fd)? {
+                        return Poll::Ready(Ok(0));
+                    }
+                }
+                Poll::Pending
+            }
+        }
+    }
+
+    fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()> {
+        self.tokio_datagram.shutdown(how)
+    }
+
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        use std::os::unix::io::AsRawFd;
+        self.tokio_datagram.as_raw_fd()
+    }
+}
@@ -0,0 +1,45 @@
+//! Compatibility with futures 0.1.
+//!
+//! Libraries written for futures 0.1, such as hyper 0.12 and the tokio 0.1 ecosystem, produce
+//! futures that can't be spawned with [`spawn`] directly. [`spawn01`] adapts such a future and
+//! spawns it on the current runtime, whichever runtime that is.
+//!
+//! Legacy futures that rely on a tokio 0.1 executor being set for the current thread, for example
+//! by calling `tokio::spawn`, still need to run on `runtime-tokio`.
+//!
+//! [`spawn`]: ../task/fn.spawn.html
+//! [`spawn01`]: fn.spawn01.html
+
+use futures::compat::Future01CompatExt;
+
+use crate::task::JoinHandle;
+
+/// Spawn a futures 0.1 future on the current runtime.
+///
+/// The returned [`JoinHandle`] resolves to the legacy future's `Item` or `Error`. Dropping it
+/// detaches the task, which then keeps running in the background.
+///
+/// This function can only be called after a runtime has been initialized.
+///
+/// [`JoinHandle`]: ../task/struct.JoinHandle.html
+///
+/// # Examples
+///
+/// ```
+/// use futures01::Future;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let legacy = futures01::future::ok::<u32, ()>(20).map(|n| n * 2);
+///     let handle = runtime::compat::spawn01(legacy);
+///     assert_eq!(handle.await.unwrap(), Ok(40));
+/// }
+/// ```
+pub fn spawn01<F>(fut: F) -> JoinHandle<Result<F::Item, F::Error>>
+where
+    F: futures01::Future + Send + 'static,
+    F::Item: Send + 'static,
+    F::Error: Send + 'static,
+{
+    crate::task::spawn(fut.compat())
+}
@@ -0,0 +1,172 @@
+//! Asynchronous filesystem manipulation.
+//!
+//! This module mirrors `std::fs`. Most operating systems don't provide non-blocking file IO, so
+//! every operation runs the blocking `std::fs` call through [`spawn_blocking`], keeping the
+//! runtime's executor free to poll other futures in the meantime.
+//!
+//! [`spawn_blocking`]: ../task/fn.spawn_blocking.html
+//!
+//! # Organization
+//!
+//! * [`read`], [`read_to_string`], and [`write`] read or write a whole file at once.
+//! * [`File`] is an open file that can be read from and written to incrementally.
+//! * [`read_dir`] returns a stream over the entries in a directory.
+//!
+//! [`read`]: fn.read.html
+//! [`read_to_string`]: fn.read_to_string.html
+//! [`write`]: fn.write.html
+//! [`File`]: struct.File.html
+//! [`read_dir`]: fn.read_dir.html
+//!
+//! ## Examples
+//! __Write to a file and read it back__
+//! ```no_run
+//! use futures::prelude::*;
+//! use runtime::fs::File;
+//!
+//! #[runtime::main]
+//! async fn main() -> std::io::Result<()> {
+//!     let mut file = File::create("foo.txt").await?;
+//!     file.write_all(b"hello world").await?;
+//!     file.flush().await?;
+//!
+//!     let mut contents = String::new();
+//!     File::open("foo.txt").await?.read_to_string(&mut contents).await?;
+//!     assert_eq!(contents, "hello world");
+//!     Ok(())
+//! }
+//! ```
+
+use futures::prelude::*;
+use futures::task::{Context, Poll};
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+
+use crate::task::{spawn_blocking, SpawnBlockingFuture};
+
+mod file;
+mod read_dir;
+
+pub use file::{File, MetadataFuture, OpenFuture};
+pub use read_dir::{read_dir, DirEntry, ReadDir, ReadDirFuture};
+
+/// Read the entire contents of a file into a bytes vector.
+///
+/// This is the asynchronous counterpart of [`std::fs::read`].
+///
+/// [`std::fs::read`]: https://doc.rust-lang.org/std/fs/fn.read.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[runtime::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let contents = runtime::fs::read("foo.txt").await?;
+/// # Ok(()) }
+/// ```
+pub fn read<P: AsRef<Path>>(path: P) -> ReadFuture {
+    let path = path.as_ref().to_owned();
+    ReadFuture {
+        inner: spawn_blocking(move || fs::read(path)),
+    }
+}
+
+/// Read the entire contents of a file into a string.
+///
+/// This is the asynchronous counterpart of [`std::fs::read_to_string`]. The future resolves to an
+/// error of kind `ErrorKind::InvalidData` if the file isn't valid UTF-8.
+///
+/// [`std::fs::read_to_string`]: https://doc.rust-lang.org/std/fs/fn.read_to_string.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[runtime::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let contents = runtime::fs::read_to_string("foo.txt").await?;
+/// println!("{}", contents);
+/// # Ok(()) }
+/// ```
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> ReadToStringFuture {
+    let path = path.as_ref().to_owned();
+    ReadToStringFuture {
+        inner: spawn_blocking(move || fs::read_to_string(path)),
+    }
+}
+
+/// Write a slice as the entire contents of a file.
+///
+/// The file is created if it doesn't exist, and its contents are replaced if it does. This is the
+/// asynchronous counterpart of [`std::fs::write`].
+///
+/// [`std::fs::write`]: https://doc.rust-lang.org/std/fs/fn.write.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[runtime::main]
+/// # async fn main() -> std::io::Result<()> {
+/// runtime::fs::write("foo.txt", "hello world").await?;
+/// # Ok(()) }
+/// ```
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> WriteFuture {
+    let path = path.as_ref().to_owned();
+    let contents = contents.as_ref().to_owned();
+    WriteFuture {
+        inner: spawn_blocking(move || fs::write(path, contents)),
+    }
+}
+
+/// The future returned by [`read`].
+///
+/// [`read`]: fn.read.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadFuture {
+    inner: SpawnBlockingFuture<io::Result<Vec<u8>>>,
+}
+
+impl Future for ReadFuture {
+    type Output = io::Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_unpin(cx)
+    }
+}
+
+/// The future returned by [`read_to_string`].
+///
+/// [`read_to_string`]: fn.read_to_string.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadToStringFuture {
+    inner: SpawnBlockingFuture<io::Result<String>>,
+}
+
+impl Future for ReadToStringFuture {
+    type Output = io::Result<String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_unpin(cx)
+    }
+}
+
+/// The future returned by [`write`].
+///
+/// [`write`]: fn.write.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteFuture {
+    inner: SpawnBlockingFuture<io::Result<()>>,
+}
+
+impl Future for WriteFuture {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_unpin(cx)
+    }
+}
@@ -0,0 +1,367 @@
+use futures::prelude::*;
+use futures::ready;
+use futures::task::{Context, Poll};
+
+use std::fs::{self, Metadata};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::mem;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::task::{spawn_blocking, SpawnBlockingFuture};
+
+/// The largest number of bytes read or written by a single blocking call.
+const MAX_BUF: usize = 16 * 1024;
+
+/// A reference to an open file on the filesystem.
+///
+/// Files are read from and written to through the [`AsyncRead`] and [`AsyncWrite`] traits, and
+/// the cursor is moved with [`AsyncSeek`]. Writes are performed in the background: the number of
+/// bytes written is returned right away, and an error is reported by the next operation on the
+/// file. Call `flush` to wait for pending writes to complete.
+///
+/// [`AsyncRead`]: https://docs.rs/futures-preview/0.3.0-alpha.13/futures/io/trait.AsyncRead.html
+/// [`AsyncWrite`]: https://docs.rs/futures-preview/0.3.0-alpha.13/futures/io/trait.AsyncWrite.html
+/// [`AsyncSeek`]: https://docs.rs/futures-preview/0.3.0-alpha.19/futures/io/trait.AsyncSeek.html
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::prelude::*;
+/// use runtime::fs::File;
+///
+/// # #[runtime::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let mut file = File::open("foo.txt").await?;
+/// let mut contents = Vec::new();
+/// file.read_to_end(&mut contents).await?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct File {
+    std: Arc<fs::File>,
+    state: State,
+}
+
+impl File {
+    /// Open a file in read-only mode.
+    ///
+    /// See [`std::fs::File::open`] for more details.
+    ///
+    /// [`std::fs::File::open`]: https://doc.rust-lang.org/std/fs/struct.File.html#method.open
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::fs::File;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let file = File::open("foo.txt").await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> OpenFuture {
+        let path = path.as_ref().to_owned();
+        OpenFuture {
+            inner: spawn_blocking(move || fs::File::open(path)),
+        }
+    }
+
+    /// Open a file in write-only mode, creating it if it doesn't exist and truncating it if it
+    /// does.
+    ///
+    /// See [`std::fs::File::create`] for more details.
+    ///
+    /// [`std::fs::File::create`]: https://doc.rust-lang.org/std/fs/struct.File.html#method.create
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::fs::File;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut file = File::create("foo.txt").await?;
+    /// file.write_all(b"hello world").await?;
+    /// file.flush().await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn create<P: AsRef<Path>>(path: P) -> OpenFuture {
+        let path = path.as_ref().to_owned();
+        OpenFuture {
+            inner: spawn_blocking(move || fs::File::create(path)),
+        }
+    }
+
+    /// Create a `File` from a standard library file.
+    pub fn from_std(file: fs::File) -> File {
+        File {
+            std: Arc::new(file),
+            state: State::Idle(Buf::default()),
+        }
+    }
+
+    /// Query metadata about the file, such as its length.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::fs::File;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let file = File::open("foo.txt").await?;
+    /// let len = file.metadata().await?.len();
+    /// # Ok(()) }
+    /// ```
+    pub fn metadata(&self) -> MetadataFuture {
+        let std = self.std.clone();
+        MetadataFuture {
+            inner: spawn_blocking(move || std.metadata()),
+        }
+    }
+}
+
+impl AsyncRead for File {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        dst: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if dst.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle(buf) => {
+                    if !buf.is_empty() {
+                        return Poll::Ready(Ok(buf.copy_to(dst)));
+                    }
+
+                    let mut buf = mem::take(buf);
+                    let std = this.std.clone();
+                    let len = dst.len().min(MAX_BUF);
+                    this.state = State::Busy(spawn_blocking(move || {
+                        let res = buf.read_from(&mut &*std, len);
+                        (Operation::Read(res), buf)
+                    }));
+                }
+                State::Busy(fut) => {
+                    let (op, buf) = ready!(fut.poll_unpin(cx));
+                    this.state = State::Idle(buf);
+                    match op {
+                        Operation::Read(Ok(0)) => return Poll::Ready(Ok(0)),
+                        Operation::Read(Ok(_)) | Operation::Seek(_) => {}
+                        Operation::Read(Err(err)) => return Poll::Ready(Err(err)),
+                        Operation::Write(res) => res?,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl AsyncWrite for File {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        src: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if src.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle(buf) => {
+                    // Bytes that were read ahead but not returned yet haven't been consumed, so
+                    // the write has to start where they do.
+                    let unread = buf.len() as i64;
+                    let mut buf = mem::take(buf);
+                    let len = buf.copy_from(&src[..src.len().min(MAX_BUF)]);
+                    let std = this.std.clone();
+                    this.state = State::Busy(spawn_blocking(move || {
+                        let mut file = &*std;
+                        let res = match unread {
+                            0 => file.write_all(&buf.data),
+                            n => file
+                                .seek(SeekFrom::Current(-n))
+                                .and_then(|_| file.write_all(&buf.data)),
+                        };
+                        buf.clear();
+                        (Operation::Write(res), buf)
+                    }));
+                    return Poll::Ready(Ok(len));
+                }
+                State::Busy(fut) => {
+                    let (op, buf) = ready!(fut.poll_unpin(cx));
+                    this.state = State::Idle(buf);
+                    if let Operation::Write(res) = op {
+                        res?;
+                    }
+                }
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match &mut this.state {
+            State::Idle(_) => Poll::Ready(Ok(())),
+            State::Busy(fut) => {
+                let (op, buf) = ready!(fut.poll_unpin(cx));
+                this.state = State::Idle(buf);
+                match op {
+                    Operation::Write(res) => Poll::Ready(res),
+                    Operation::Read(_) | Operation::Seek(_) => Poll::Ready(Ok(())),
+                }
+            }
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl AsyncSeek for File {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle(buf) => {
+                    // Bytes that were read ahead but not returned yet are thrown away, so a seek
+                    // relative to the current position has to start where they do.
+                    let pos = match pos {
+                        SeekFrom::Current(n) => SeekFrom::Current(n - buf.len() as i64),
+                        pos => pos,
+                    };
+                    let mut buf = mem::take(buf);
+                    buf.clear();
+                    let std = this.std.clone();
+                    this.state = State::Busy(spawn_blocking(move || {
+                        let res = (&*std).seek(pos);
+                        (Operation::Seek(res), buf)
+                    }));
+                }
+                State::Busy(fut) => {
+                    let (op, buf) = ready!(fut.poll_unpin(cx));
+                    this.state = State::Idle(buf);
+                    match op {
+                        Operation::Seek(res) => return Poll::Ready(res),
+                        Operation::Read(_) => {}
+                        Operation::Write(res) => res?,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The future returned by [`File::open`] and [`File::create`].
+///
+/// Resolves to a [`File`] once the file has been opened.
+///
+/// [`File::open`]: struct.File.html#method.open
+/// [`File::create`]: struct.File.html#method.create
+/// [`File`]: struct.File.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct OpenFuture {
+    inner: SpawnBlockingFuture<io::Result<fs::File>>,
+}
+
+impl Future for OpenFuture {
+    type Output = io::Result<File>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let file = ready!(self.inner.poll_unpin(cx))?;
+        Poll::Ready(Ok(File::from_std(file)))
+    }
+}
+
+/// The future returned by [`File::metadata`] and [`DirEntry::metadata`].
+///
+/// [`File::metadata`]: struct.File.html#method.metadata
+/// [`DirEntry::metadata`]: struct.DirEntry.html#method.metadata
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct MetadataFuture {
+    pub(super) inner: SpawnBlockingFuture<io::Result<Metadata>>,
+}
+
+impl Future for MetadataFuture {
+    type Output = io::Result<Metadata>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_unpin(cx)
+    }
+}
+
+/// Whether the file is ready for a new operation, or waiting on one to complete.
+#[derive(Debug)]
+enum State {
+    Idle(Buf),
+    Busy(SpawnBlockingFuture<(Operation, Buf)>),
+}
+
+/// The result of a blocking operation.
+#[derive(Debug)]
+enum Operation {
+    Read(io::Result<usize>),
+    Write(io::Result<()>),
+    Seek(io::Result<u64>),
+}
+
+/// A buffer moved to the blocking thread and back, holding bytes read ahead or waiting to be
+/// written.
+#[derive(Debug, Default)]
+struct Buf {
+    data: Vec<u8>,
+    pos: usize,
+}
+
+impl Buf {
+    fn len(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.pos = 0;
+    }
+
+    fn copy_to(&mut self, dst: &mut [u8]) -> usize {
+        let len = dst.len().min(self.len());
+        dst[..len].copy_from_slice(&self.data[self.pos..self.pos + len]);
+        self.pos += len;
+        len
+    }
+
+    fn copy_from(&mut self, src: &[u8]) -> usize {
+        self.clear();
+        self.data.extend_from_slice(src);
+        src.len()
+    }
+
+    fn read_from(&mut self, reader: &mut impl Read, len: usize) -> io::Result<usize> {
+        self.clear();
+        self.data.resize(len, 0);
+        let res = reader.read(&mut self.data);
+        self.data.truncate(*res.as_ref().unwrap_or(&0));
+        res
+    }
+}
@@ -0,0 +1,142 @@
+use futures::prelude::*;
+use futures::ready;
+use futures::task::{Context, Poll};
+
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use super::MetadataFuture;
+use crate::task::{spawn_blocking, SpawnBlockingFuture};
+
+/// Read the entries in a directory.
+///
+/// The future resolves to a [`ReadDir`] stream, which yields the entries of the directory. This is
+/// the asynchronous counterpart of [`std::fs::read_dir`].
+///
+/// [`ReadDir`]: struct.ReadDir.html
+/// [`std::fs::read_dir`]: https://doc.rust-lang.org/std/fs/fn.read_dir.html
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::prelude::*;
+///
+/// # #[runtime::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let mut entries = runtime::fs::read_dir(".").await?;
+/// while let Some(entry) = entries.next().await {
+///     println!("{}", entry?.path().display());
+/// }
+/// # Ok(()) }
+/// ```
+pub fn read_dir<P: AsRef<Path>>(path: P) -> ReadDirFuture {
+    let path = path.as_ref().to_owned();
+    ReadDirFuture {
+        inner: spawn_blocking(move || fs::read_dir(path)),
+    }
+}
+
+/// The future returned by [`read_dir`].
+///
+/// [`read_dir`]: fn.read_dir.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadDirFuture {
+    inner: SpawnBlockingFuture<io::Result<fs::ReadDir>>,
+}
+
+impl Future for ReadDirFuture {
+    type Output = io::Result<ReadDir>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let read_dir = ready!(self.inner.poll_unpin(cx))?;
+        Poll::Ready(Ok(ReadDir {
+            state: State::Idle(Some(read_dir)),
+        }))
+    }
+}
+
+/// A stream over the entries in a directory, created by [`read_dir`].
+///
+/// Each entry is read on the blocking pool.
+///
+/// [`read_dir`]: fn.read_dir.html
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ReadDir {
+    state: State,
+}
+
+#[derive(Debug)]
+enum State {
+    /// Waiting to read the next entry, or done if the iterator is gone.
+    Idle(Option<fs::ReadDir>),
+    Busy(SpawnBlockingFuture<(Option<io::Result<fs::DirEntry>>, fs::ReadDir)>),
+}
+
+impl Stream for ReadDir {
+    type Item = io::Result<DirEntry>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match &mut self.state {
+                State::Idle(read_dir) => {
+                    let mut read_dir = match read_dir.take() {
+                        Some(read_dir) => read_dir,
+                        None => return Poll::Ready(None),
+                    };
+                    self.state = State::Busy(spawn_blocking(move || {
+                        let next = read_dir.next();
+                        (next, read_dir)
+                    }));
+                }
+                State::Busy(fut) => {
+                    let (next, read_dir) = ready!(fut.poll_unpin(cx));
+                    self.state = State::Idle(next.as_ref().map(|_| read_dir));
+                    let entry = next.map(|res| {
+                        res.map(|inner| DirEntry {
+                            inner: Arc::new(inner),
+                        })
+                    });
+                    return Poll::Ready(entry);
+                }
+            }
+        }
+    }
+}
+
+/// An entry in a directory, yielded by [`ReadDir`].
+///
+/// [`ReadDir`]: struct.ReadDir.html
+#[derive(Debug)]
+pub struct DirEntry {
+    inner: Arc<fs::DirEntry>,
+}
+
+impl DirEntry {
+    /// Returns the full path to the entry.
+    pub fn path(&self) -> PathBuf {
+        self.inner.path()
+    }
+
+    /// Returns the name of the entry, without its leading path.
+    pub fn file_name(&self) -> OsString {
+        self.inner.file_name()
+    }
+
+    /// Query metadata about the entry.
+    ///
+    /// Like [`std::fs::DirEntry::metadata`], this doesn't follow symbolic links.
+    ///
+    /// [`std::fs::DirEntry::metadata`]: https://doc.rust-lang.org/std/fs/struct.DirEntry.html#method.metadata
+    pub fn metadata(&self) -> MetadataFuture {
+        let inner = self.inner.clone();
+        MetadataFuture {
+            inner: spawn_blocking(move || inner.metadata()),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Traits, helpers, and type definitions for asynchronous I/O.
+//!
+//! This module mirrors `std::io`: it provides the traits for reading and writing asynchronously,
+//! along with buffered wrappers for protocols that are easier to handle a line at a time than a
+//! chunk at a time.
+//!
+//! # Organization
+//!
+//! * [`AsyncRead`] and [`AsyncWrite`] are implemented by the types that can be read from and
+//!   written to, such as [`TcpStream`].
+//! * [`AsyncReadExt`] and [`AsyncWriteExt`] provide methods like `read`, `read_exact`, and
+//!   `write_all` on top of them.
+//! * [`copy`] copies the entire contents of a reader into a writer, and [`copy_with_capacity`]
+//!   does the same with a buffer of a given size.
+//! * [`copy_bidirectional`] copies data in both directions between two streams, like a proxy.
+//! * [`BufReader`] adds buffering to an [`AsyncRead`] type, and implements [`AsyncBufRead`].
+//! * [`BufWriter`] adds buffering to an [`AsyncWrite`] type.
+//! * [`AsyncBufReadExt`] provides [`read_line`] and [`lines`] on top of [`AsyncBufRead`].
+//! * [`duplex`] creates a pair of connected in-memory streams, which is useful for testing.
+//!
+//! [`AsyncRead`]: trait.AsyncRead.html
+//! [`AsyncWrite`]: trait.AsyncWrite.html
+//! [`TcpStream`]: ../net/struct.TcpStream.html
+//! [`AsyncReadExt`]: trait.AsyncReadExt.html
+//! [`AsyncWriteExt`]: trait.AsyncWriteExt.html
+//! [`copy`]: fn.copy.html
+//! [`copy_with_capacity`]: fn.copy_with_capacity.html
+//! [`copy_bidirectional`]: fn.copy_bidirectional.html
+//! [`BufReader`]: struct.BufReader.html
+//! [`BufWriter`]: struct.BufWriter.html
+//! [`AsyncBufRead`]: trait.AsyncBufRead.html
+//! [`AsyncBufReadExt`]: trait.AsyncBufReadExt.html
+//! [`read_line`]: trait.AsyncBufReadExt.html#method.read_line
+//! [`lines`]: trait.AsyncBufReadExt.html#method.lines
+//! [`duplex`]: fn.duplex.html
+//!
+//! ## Examples
+//! __Echo everything received on a TCP stream__
+//! ```no_run
+//! use runtime::io::{self, AsyncReadExt};
+//! use runtime::net::TcpStream;
+//!
+//! #[runtime::main]
+//! async fn main() -> std::io::Result<()> {
+//!     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+//!     let (reader, writer) = &mut stream.split();
+//!
+//!     let bytes = io::copy(reader, writer).await?;
+//!     println!("echoed {} bytes", bytes);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! __Read a TCP stream line by line__
+//! ```no_run
+//! use futures::prelude::*;
+//! use runtime::io::{AsyncBufReadExt, BufReader};
+//! use runtime::net::TcpStream;
+//!
+//! #[runtime::main]
+//! async fn main() -> std::io::Result<()> {
+//!     let stream = TcpStream::connect("127.0.0.1:8080").await?;
+//!     let mut lines = BufReader::new(stream).lines();
+//!
+//!     while let Some(line) = lines.next().await {
+//!         println!("{}", line?);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+mod copy_bidirectional;
+mod duplex;
+
+pub use copy_bidirectional::{copy_bidirectional, CopyBidirectionalFuture};
+pub use duplex::{duplex, DuplexStream};
+
+#[doc(inline)]
+pub use futures::io::{AsyncBufRead, AsyncBufReadExt, BufReader, BufWriter, Lines, ReadLine};
+
+#[doc(inline)]
+pub use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, CopyBufInto, CopyInto};
+
+/// Copy the entire contents of a reader into a writer.
+///
+/// The returned future resolves to the number of bytes copied once the reader has reached
+/// end-of-file and all data has been written. This is the asynchronous counterpart of
+/// `std::io::copy`.
+///
+/// ## Examples
+/// ```
+/// # use futures::executor::block_on;
+/// # block_on(async {
+/// let reader: &[u8] = b"hello world";
+/// let mut writer = Vec::new();
+///
+/// let bytes = runtime::io::copy(reader, &mut writer).await?;
+/// assert_eq!(bytes, 11);
+/// assert_eq!(writer, b"hello world");
+/// # Ok::<(), std::io::Error>(()) }).unwrap();
+/// ```
+pub fn copy<R, W>(reader: R, writer: &mut W) -> CopyInto<'_, R, W>
+where
+    R: AsyncRead,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    reader.copy_into(writer)
+}
+
+/// Copy the entire contents of a reader into a writer, reading through a buffer of `capacity`
+/// bytes.
+///
+/// This works like [`copy`], which uses a fixed-size buffer. A larger buffer means fewer reads and
+/// writes when copying a lot of data, for example in a proxy with high throughput.
+///
+/// [`copy`]: fn.copy.html
+///
+/// # Panics
+///
+/// This function panics if `capacity` is zero.
+///
+/// ## Examples
+/// ```
+/// # use futures::executor::block_on;
+/// # block_on(async {
+/// let reader: &[u8] = b"hello world";
+/// let mut writer = Vec::new();
+///
+/// let bytes = runtime::io::copy_with_capacity(reader, &mut writer, 64 * 1024).await?;
+/// assert_eq!(bytes, 11);
+/// assert_eq!(writer, b"hello world");
+/// # Ok::<(), std::io::Error>(()) }).unwrap();
+/// ```
+pub fn copy_with_capacity<R, W>(
+    reader: R,
+    writer: &mut W,
+    capacity: usize,
+) -> CopyBufInto<'_, BufReader<R>, W>
+where
+    R: AsyncRead,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    assert!(
+        capacity > 0,
+        "the buffer capacity must be greater than zero"
+    );
+    BufReader::with_capacity(capacity, reader).copy_buf_into(writer)
+}
@@ -0,0 +1,158 @@
+use futures::prelude::*;
+use futures::task::{Context, Poll};
+
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+
+/// The size of the buffer used for each direction, the same as `BufReader`'s default.
+const BUF_SIZE: usize = 8 * 1024;
+
+/// Copy data in both directions between two streams, until both of them have closed.
+///
+/// Everything read from `a` is written to `b`, and everything read from `b` is written to `a`.
+/// When one stream reaches end-of-file, the other one is closed for writing, so closing one side
+/// of a connection is forwarded to the other side. The returned future resolves to the number of
+/// bytes copied from `a` to `b` and from `b` to `a`, once both directions are done.
+///
+/// This is what a proxy does with its two connections. If either stream fails, the future
+/// resolves to the error right away.
+///
+/// ## Examples
+/// ```no_run
+/// use runtime::net::TcpStream;
+///
+/// #[runtime::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut client = TcpStream::connect("127.0.0.1:8080").await?;
+///     let mut server = TcpStream::connect("127.0.0.1:8081").await?;
+///
+///     let (sent, received) = runtime::io::copy_bidirectional(&mut client, &mut server).await?;
+///     println!("sent {} bytes and received {} bytes", sent, received);
+///     Ok(())
+/// }
+/// ```
+pub fn copy_bidirectional<'a, A, B>(a: &'a mut A, b: &'a mut B) -> CopyBidirectionalFuture<'a, A, B>
+where
+    A: AsyncRead + AsyncWrite + Unpin + ?Sized,
+    B: AsyncRead + AsyncWrite + Unpin + ?Sized,
+{
+    CopyBidirectionalFuture {
+        a,
+        b,
+        a_to_b: Transfer::new(),
+        b_to_a: Transfer::new(),
+    }
+}
+
+/// The future returned by [`copy_bidirectional`].
+///
+/// [`copy_bidirectional`]: fn.copy_bidirectional.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CopyBidirectionalFuture<'a, A: ?Sized, B: ?Sized> {
+    a: &'a mut A,
+    b: &'a mut B,
+    a_to_b: Transfer,
+    b_to_a: Transfer,
+}
+
+impl<A, B> Future for CopyBidirectionalFuture<'_, A, B>
+where
+    A: AsyncRead + AsyncWrite + Unpin + ?Sized,
+    B: AsyncRead + AsyncWrite + Unpin + ?Sized,
+{
+    type Output = io::Result<(u64, u64)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let a_to_b = this
+            .a_to_b
+            .poll_copy(cx, Pin::new(&mut *this.a), Pin::new(&mut *this.b))?;
+        let b_to_a = this
+            .b_to_a
+            .poll_copy(cx, Pin::new(&mut *this.b), Pin::new(&mut *this.a))?;
+        match (a_to_b, b_to_a) {
+            (Poll::Ready(a_to_b), Poll::Ready(b_to_a)) => Poll::Ready(Ok((a_to_b, b_to_a))),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+impl<A: ?Sized, B: ?Sized> fmt::Debug for CopyBidirectionalFuture<'_, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("CopyBidirectionalFuture { .. }")
+    }
+}
+
+/// The state of copying in one direction.
+struct Transfer {
+    buf: Box<[u8]>,
+    /// The range of `buf` that has been read but not written yet.
+    pos: usize,
+    cap: usize,
+    /// Whether bytes have been written since the writer was last flushed.
+    needs_flush: bool,
+    /// Set once the reader has reached end-of-file.
+    closing: bool,
+    done: bool,
+    amt: u64,
+}
+
+impl Transfer {
+    fn new() -> Self {
+        Self {
+            buf: vec![0; BUF_SIZE].into_boxed_slice(),
+            pos: 0,
+            cap: 0,
+            needs_flush: false,
+            closing: false,
+            done: false,
+            amt: 0,
+        }
+    }
+
+    fn poll_copy<R, W>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut reader: Pin<&mut R>,
+        mut writer: Pin<&mut W>,
+    ) -> Poll<io::Result<u64>>
+    where
+        R: AsyncRead + ?Sized,
+        W: AsyncWrite + ?Sized,
+    {
+        while !self.done {
+            if self.closing {
+                futures::ready!(writer.as_mut().poll_close(cx))?;
+                self.done = true;
+            } else if self.pos < self.cap {
+                let n = futures::ready!(writer
+                    .as_mut()
+                    .poll_write(cx, &self.buf[self.pos..self.cap]))?;
+                if n == 0 {
+                    return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+                }
+                self.pos += n;
+                self.amt += n as u64;
+                self.needs_flush = true;
+            } else {
+                match reader.as_mut().poll_read(cx, &mut self.buf)? {
+                    Poll::Ready(0) => self.closing = true,
+                    Poll::Ready(n) => {
+                        self.pos = 0;
+                        self.cap = n;
+                    }
+                    Poll::Pending => {
+                        // Don't hold on to written bytes while waiting for more to read.
+                        if self.needs_flush {
+                            futures::ready!(writer.as_mut().poll_flush(cx))?;
+                            self.needs_flush = false;
+                        }
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+        Poll::Ready(Ok(self.amt))
+    }
+}
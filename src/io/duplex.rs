@@ -0,0 +1,169 @@
+use futures::io::{AsyncRead, AsyncWrite};
+use futures::task::{Context, Poll, Waker};
+
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Create a pair of connected in-memory streams.
+///
+/// Bytes written to one end can be read from the other, which makes the pair a stand-in for a
+/// socket when testing protocol code. Each direction buffers up to `buf_size` bytes: writes to a
+/// full buffer wait until the other end has read from it.
+///
+/// Dropping or closing one end makes reads on the other end return end-of-file once the buffered
+/// bytes have been read. Writing to an end whose peer has been dropped fails with
+/// `ErrorKind::BrokenPipe`.
+///
+/// # Panics
+///
+/// This function panics if `buf_size` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::prelude::*;
+///
+/// # #[runtime::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let (mut client, mut server) = runtime::io::duplex(64);
+///
+/// client.write_all(b"ping").await?;
+///
+/// let mut buf = [0u8; 4];
+/// server.read_exact(&mut buf).await?;
+/// assert_eq!(&buf, b"ping");
+/// # Ok(()) }
+/// ```
+pub fn duplex(buf_size: usize) -> (DuplexStream, DuplexStream) {
+    assert!(buf_size > 0, "the buffer size must be greater than zero");
+    let one = Arc::new(Mutex::new(Pipe::new(buf_size)));
+    let two = Arc::new(Mutex::new(Pipe::new(buf_size)));
+    let a = DuplexStream {
+        read: one.clone(),
+        write: two.clone(),
+    };
+    let b = DuplexStream {
+        read: two,
+        write: one,
+    };
+    (a, b)
+}
+
+/// One end of an in-memory stream, created by [`duplex`].
+///
+/// [`duplex`]: fn.duplex.html
+#[derive(Debug)]
+pub struct DuplexStream {
+    read: Arc<Mutex<Pipe>>,
+    write: Arc<Mutex<Pipe>>,
+}
+
+impl AsyncRead for DuplexStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.read.lock().unwrap().poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for DuplexStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.write.lock().unwrap().poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.write.lock().unwrap().close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for DuplexStream {
+    fn drop(&mut self) {
+        if let Ok(mut read) = self.read.lock() {
+            read.close();
+        }
+        if let Ok(mut write) = self.write.lock() {
+            write.close();
+        }
+    }
+}
+
+/// A bounded buffer carrying bytes in one direction.
+#[derive(Debug)]
+struct Pipe {
+    buf: VecDeque<u8>,
+    max_len: usize,
+    closed: bool,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+}
+
+impl Pipe {
+    fn new(max_len: usize) -> Self {
+        Self {
+            buf: VecDeque::with_capacity(max_len),
+            max_len,
+            closed: false,
+            read_waker: None,
+            write_waker: None,
+        }
+    }
+
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        if self.buf.is_empty() && !buf.is_empty() {
+            if self.closed {
+                return Poll::Ready(Ok(0));
+            }
+            self.read_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let len = buf.len().min(self.buf.len());
+        for (dst, src) in buf.iter_mut().zip(self.buf.drain(..len)) {
+            *dst = src;
+        }
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        if self.closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+        if self.buf.len() == self.max_len && !buf.is_empty() {
+            self.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let len = buf.len().min(self.max_len - self.buf.len());
+        self.buf.extend(&buf[..len]);
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(len))
+    }
+
+    fn close(&mut self) {
+        self.closed = true;
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+    }
+}
@@ -7,11 +7,11 @@
 //! asynchronous software.
 //!
 //! - __runtime agnostic:__ Runtime comes with minimal OS bindings out of the box, but switching to a
-//!     different runtime is a matter of changing a single line.
+//!   different runtime is a matter of changing a single line.
 //! - __await anywhere:__ Runtime allows you to write async main functions, async tests, and async
-//!     benchmarks. Experience what first-class async support in Rust feels like.
+//!   benchmarks. Experience what first-class async support in Rust feels like.
 //! - __built for performance:__ Runtime is the thinnest layer possible on top of the backing
-//!     implementations. All of the speed, none of the boilerplate.
+//!   implementations. All of the speed, none of the boilerplate.
 //!
 //! ## Examples
 //! __UDP Echo Server__
@@ -91,7 +91,12 @@
     rust_2018_idioms
 )]
 
+pub mod compat;
+pub mod fs;
+pub mod io;
 pub mod net;
+pub mod os;
+pub mod sync;
 pub mod task;
 pub mod time;
 
@@ -108,7 +113,27 @@ pub mod time;
 /// ```
 /// use runtime::prelude::*;
 /// ```
+///
+/// ## Examples
+/// ```
+/// use runtime::prelude::*;
+/// use std::time::Duration;
+///
+/// # #[runtime::main]
+/// # async fn main () -> Result<(), runtime::time::Elapsed> {
+/// let handle = spawn(async { 42 });
+/// let res = handle.timeout(Duration::from_secs(1)).await?;
+/// assert_eq!(res.unwrap(), 42);
+/// # Ok(())
+/// # }
+/// ```
 pub mod prelude {
+    #[doc(no_inline)]
+    pub use super::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+    #[doc(no_inline)]
+    pub use super::io::{AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _};
+    #[doc(no_inline)]
+    pub use super::task::spawn;
     #[doc(no_inline)]
     pub use super::time::AsyncReadExt as _;
     #[doc(no_inline)]
@@ -120,6 +145,12 @@ pub mod prelude {
 #[doc(inline)]
 pub use task::spawn;
 
+#[doc(inline)]
+pub use time::sleep;
+
+#[doc(inline)]
+pub use runtime_raw::{Capabilities, RuntimeMetrics};
+
 #[doc(inline)]
 pub use runtime_attributes::{bench, test};
 
@@ -130,6 +161,44 @@ pub use runtime_attributes::main;
 #[doc(hidden)]
 pub use runtime_raw as raw;
 
+/// Get the features the current runtime supports.
+///
+/// Not every runtime supports every kind of I/O, so libraries that work with any runtime can use
+/// this to check for a feature instead of hitting a panic.
+///
+/// # Examples
+///
+/// ```
+/// #[runtime::main]
+/// async fn main() {
+///     if runtime::capabilities().timers {
+///         runtime::time::sleep(std::time::Duration::from_millis(10)).await;
+///     }
+/// }
+/// ```
+pub fn capabilities() -> Capabilities {
+    runtime_raw::current_runtime().capabilities()
+}
+
+/// Get a snapshot of the current runtime's metrics.
+///
+/// Metrics are best-effort: fields the runtime doesn't keep track of are `None`.
+///
+/// # Examples
+///
+/// ```
+/// #[runtime::main]
+/// async fn main() {
+///     let metrics = runtime::metrics();
+///     if let Some(pending) = metrics.num_pending_tasks {
+///         println!("{} tasks are pending", pending);
+///     }
+/// }
+/// ```
+pub fn metrics() -> RuntimeMetrics {
+    runtime_raw::current_runtime().metrics()
+}
+
 #[doc(hidden)]
 #[cfg(feature = "native")]
 pub use runtime_native as native;
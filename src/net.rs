@@ -7,17 +7,99 @@
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
 //! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`lookup_host`] resolves host names to socket addresses without blocking
 //! * Other types are return or parameter types for various methods in this module
 //!
 //! [`TcpListener`]: struct.TcpListener.html
 //! [`TcpStream`]: struct.TcpStream.html
 //! [`UdpSocket`]: struct.UdpSocket.html
+//! [`lookup_host`]: fn.lookup_host.html
 
+use futures::future::BoxFuture;
+use futures::prelude::*;
+use futures::task::{Context, Poll};
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::vec;
+
+mod addr;
 pub mod tcp;
 pub mod udp;
 
+pub use addr::ToSocketAddrs;
+
+use addr::sealed::Resolve;
+
 #[doc(inline)]
 pub use tcp::{TcpListener, TcpStream};
 
 #[doc(inline)]
 pub use udp::UdpSocket;
+
+/// Get the current runtime, or an error if no runtime has been set on the current thread.
+pub(crate) fn current_runtime() -> io::Result<&'static dyn runtime_raw::Runtime> {
+    runtime_raw::try_current_runtime().ok_or_else(no_runtime)
+}
+
+/// The error returned when an operation needs a runtime, but none has been set.
+pub(crate) fn no_runtime() -> io::Error {
+    io::Error::other("the runtime has not been set")
+}
+
+/// Resolves a host name to the socket addresses it maps to.
+///
+/// Socket addresses and IP address literals are converted without a lookup. Host names are
+/// resolved by the current runtime so that the calling task isn't blocked while the (potentially
+/// slow) DNS lookup takes place. If there is no runtime, looking up a host name fails with an
+/// error.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[runtime::main]
+/// # async fn main() -> std::io::Result<()> {
+/// for addr in runtime::net::lookup_host("localhost:8080").await? {
+///     println!("localhost resolves to {}", addr);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn lookup_host<A: ToSocketAddrs>(addr: A) -> LookupHostFuture {
+    let future = match addr.to_resolve() {
+        Resolve::Done(res) => future::ready(res).boxed(),
+        Resolve::Lookup(resolve) => match current_runtime() {
+            Ok(runtime) => runtime.resolve(resolve),
+            Err(err) => future::ready(Err(err)).boxed(),
+        },
+    };
+    LookupHostFuture { future }
+}
+
+/// The future returned by [`lookup_host`].
+///
+/// Resolves to an iterator over the resolved socket addresses.
+///
+/// [`lookup_host`]: fn.lookup_host.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct LookupHostFuture {
+    future: BoxFuture<'static, io::Result<Vec<SocketAddr>>>,
+}
+
+impl Future for LookupHostFuture {
+    type Output = io::Result<vec::IntoIter<SocketAddr>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.future
+            .poll_unpin(cx)
+            .map(|res| res.map(IntoIterator::into_iter))
+    }
+}
+
+impl fmt::Debug for LookupHostFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LookupHostFuture").finish()
+    }
+}
@@ -0,0 +1,131 @@
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use sealed::{Resolve, ToSocketAddrsPriv};
+
+/// A value that can be converted into one or more socket addresses, without blocking.
+///
+/// This is implemented for the same types as [`std::net::ToSocketAddrs`]. Socket addresses, and
+/// strings holding an IP address literal, are converted right away. Only host names are resolved
+/// on the runtime's blocking pool, as described for [`lookup_host`].
+///
+/// Unlike the standard library trait, the value is borrowed only while the returned future is
+/// created, so `&str` and other borrowed inputs can be used.
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+///
+/// [`std::net::ToSocketAddrs`]: https://doc.rust-lang.org/std/net/trait.ToSocketAddrs.html
+/// [`lookup_host`]: fn.lookup_host.html
+pub trait ToSocketAddrs: ToSocketAddrsPriv {}
+
+impl<T: ToSocketAddrsPriv + ?Sized> ToSocketAddrs for T {}
+
+pub(crate) mod sealed {
+    use std::fmt;
+    use std::io;
+    use std::net::SocketAddr;
+
+    /// How a value is turned into socket addresses.
+    pub enum Resolve {
+        /// The addresses are known without doing a lookup.
+        Done(io::Result<Vec<SocketAddr>>),
+        /// A host name has to be looked up, which may block.
+        Lookup(Box<dyn FnOnce() -> io::Result<Vec<SocketAddr>> + Send>),
+    }
+
+    impl fmt::Debug for Resolve {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.pad("Resolve { .. }")
+        }
+    }
+
+    pub trait ToSocketAddrsPriv {
+        fn to_resolve(&self) -> Resolve;
+    }
+}
+
+impl ToSocketAddrsPriv for SocketAddr {
+    fn to_resolve(&self) -> Resolve {
+        Resolve::Done(to_addrs(self))
+    }
+}
+
+impl ToSocketAddrsPriv for SocketAddrV4 {
+    fn to_resolve(&self) -> Resolve {
+        Resolve::Done(to_addrs(self))
+    }
+}
+
+impl ToSocketAddrsPriv for SocketAddrV6 {
+    fn to_resolve(&self) -> Resolve {
+        Resolve::Done(to_addrs(self))
+    }
+}
+
+impl ToSocketAddrsPriv for (IpAddr, u16) {
+    fn to_resolve(&self) -> Resolve {
+        Resolve::Done(to_addrs(self))
+    }
+}
+
+impl ToSocketAddrsPriv for (Ipv4Addr, u16) {
+    fn to_resolve(&self) -> Resolve {
+        Resolve::Done(to_addrs(self))
+    }
+}
+
+impl ToSocketAddrsPriv for (Ipv6Addr, u16) {
+    fn to_resolve(&self) -> Resolve {
+        Resolve::Done(to_addrs(self))
+    }
+}
+
+impl ToSocketAddrsPriv for [SocketAddr] {
+    fn to_resolve(&self) -> Resolve {
+        Resolve::Done(Ok(self.to_vec()))
+    }
+}
+
+impl ToSocketAddrsPriv for str {
+    fn to_resolve(&self) -> Resolve {
+        if let Ok(addr) = self.parse::<SocketAddr>() {
+            return Resolve::Done(Ok(vec![addr]));
+        }
+        let host = self.to_owned();
+        Resolve::Lookup(Box::new(move || to_addrs(host.as_str())))
+    }
+}
+
+impl ToSocketAddrsPriv for String {
+    fn to_resolve(&self) -> Resolve {
+        self.as_str().to_resolve()
+    }
+}
+
+impl ToSocketAddrsPriv for (&str, u16) {
+    fn to_resolve(&self) -> Resolve {
+        let (host, port) = *self;
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Resolve::Done(Ok(vec![SocketAddr::new(ip, port)]));
+        }
+        let host = host.to_owned();
+        Resolve::Lookup(Box::new(move || to_addrs(&(host.as_str(), port))))
+    }
+}
+
+impl ToSocketAddrsPriv for (String, u16) {
+    fn to_resolve(&self) -> Resolve {
+        (self.0.as_str(), self.1).to_resolve()
+    }
+}
+
+impl<T: ToSocketAddrsPriv + ?Sized> ToSocketAddrsPriv for &T {
+    fn to_resolve(&self) -> Resolve {
+        (**self).to_resolve()
+    }
+}
+
+/// Convert with the standard library trait, which blocks if a host name has to be looked up.
+fn to_addrs<A: std::net::ToSocketAddrs + ?Sized>(addr: &A) -> io::Result<Vec<SocketAddr>> {
+    addr.to_socket_addrs().map(Iterator::collect)
+}
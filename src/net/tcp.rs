@@ -16,10 +16,13 @@
 //! [`Incoming`]: struct.Incoming.html
 
 use std::collections::VecDeque;
+use std::error::Error;
 use std::fmt;
 use std::io;
 use std::net::{SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use futures::future::BoxFuture;
 use futures::io::*;
@@ -27,6 +30,14 @@ use futures::prelude::*;
 use futures::ready;
 use futures::task::{Context, Poll};
 
+use crate::net::{lookup_host, LookupHostFuture};
+
+/// A connection attempt to a single address, made by the runtime.
+type ConnectAttempt = BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::TcpStream>>>>;
+
+/// A timer made by the runtime the connection attempts are made on.
+type RuntimeDelay = Pin<Box<dyn runtime_raw::Delay>>;
+
 /// A TCP stream between a local and a remote socket.
 ///
 /// A `TcpStream` can either be created by connecting to an endpoint, via the [`connect`] method,
@@ -68,9 +79,29 @@ use futures::task::{Context, Poll};
 #[derive(Debug)]
 pub struct TcpStream {
     inner: Pin<Box<dyn runtime_raw::TcpStream>>,
+    read_eof: bool,
+    /// The addresses of a connected stream can't change, so they're looked up once and cached.
+    local_addr: Option<SocketAddr>,
+    peer_addr: Option<SocketAddr>,
+    shutdown_on_drop: Option<std::net::Shutdown>,
 }
 
 impl TcpStream {
+    /// Wrap a connected stream, caching its addresses.
+    ///
+    /// The peer address is passed in when it's already known from connecting or accepting.
+    fn new(inner: Pin<Box<dyn runtime_raw::TcpStream>>, peer_addr: Option<SocketAddr>) -> Self {
+        let local_addr = inner.local_addr().ok();
+        let peer_addr = peer_addr.or_else(|| inner.peer_addr().ok());
+        TcpStream {
+            inner,
+            read_eof: false,
+            local_addr,
+            peer_addr,
+            shutdown_on_drop: None,
+        }
+    }
+
     /// Create a new TCP stream connected to the specified address.
     ///
     /// This function will create a new TCP socket and attempt to connect it to
@@ -78,7 +109,19 @@ impl TcpStream {
     /// stream has successfully connected, or it will return an error if one
     /// occurs.
     ///
+    /// The address is resolved using [`lookup_host`], so resolving a host name doesn't block the
+    /// executor, while socket addresses and IP address literals are used right away. The resolved
+    /// addresses are tried following the Happy Eyeballs algorithm of [RFC 8305]: IPv6 and IPv4
+    /// addresses are tried alternately, and if an attempt hasn't completed after 250 milliseconds,
+    /// the next address is tried alongside it. The first connection to succeed is returned, so an
+    /// unreachable address doesn't hold up the others for the whole duration of the OS connection
+    /// timeout.
+    ///
+    /// If there is no runtime on the calling thread, the returned future resolves to an error.
+    ///
     /// [returned future]: struct.Connect.html
+    /// [`lookup_host`]: ../fn.lookup_host.html
+    /// [RFC 8305]: https://tools.ietf.org/html/rfc8305
     ///
     /// # Examples
     ///
@@ -89,17 +132,91 @@ impl TcpStream {
     /// let stream = TcpStream::connect("127.0.0.1:0").await?;
     /// # Ok(())}
     /// ```
-    pub fn connect<A: ToSocketAddrs>(addr: A) -> ConnectFuture {
+    pub fn connect<A: super::ToSocketAddrs>(addr: A) -> ConnectFuture {
         ConnectFuture {
-            addrs: Some(addr.to_socket_addrs().map(|iter| iter.collect())),
+            lookup: Some(lookup_host(addr)),
+            addrs: VecDeque::new(),
+            attempts: Vec::new(),
+            futures: Vec::new(),
+            next_attempt: None,
+            runtime: super::current_runtime().ok(),
+        }
+    }
+
+    /// Create a new TCP stream connected to the specified address, reporting every failed
+    /// connection attempt if no address could be connected to.
+    ///
+    /// This behaves like [`connect`], except that the [returned future] resolves to a
+    /// [`ConnectError`] on failure, which records the error of each resolved address that was
+    /// tried. A `ConnectError` converts into the `io::Error` that [`connect`] would have returned.
+    ///
+    /// [`connect`]: #method.connect
+    /// [returned future]: struct.ConnectDetailedFuture.html
+    /// [`ConnectError`]: struct.ConnectError.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # async fn connect_localhost() {
+    /// match TcpStream::connect_detailed("localhost:8080").await {
+    ///     Ok(stream) => println!("connected to {}", stream.peer_addr().unwrap()),
+    ///     Err(err) => {
+    ///         for (addr, err) in err.attempts() {
+    ///             println!("could not connect to {}: {}", addr, err);
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn connect_detailed<A: super::ToSocketAddrs>(addr: A) -> ConnectDetailedFuture {
+        ConnectDetailedFuture {
+            inner: Self::connect(addr),
+        }
+    }
+
+    /// Create a new TCP stream connected to the specified address, giving up on each address
+    /// after `timeout` has elapsed.
+    ///
+    /// This behaves like [`connect`], except that every connection attempt is raced against a
+    /// timer. If an attempt doesn't complete within `timeout`, the next resolved address is tried.
+    /// If no address could be connected to, the [returned future] resolves to the last error that
+    /// occurred, which is an error of kind [`TimedOut`] if the last attempt timed out.
+    ///
+    /// [`connect`]: #method.connect
+    /// [returned future]: struct.ConnectTimeoutFuture.html
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// # async fn connect_localhost() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect_timeout("127.0.0.1:8080", Duration::from_secs(1)).await?;
+    /// # Ok(())}
+    /// ```
+    pub fn connect_timeout<A: super::ToSocketAddrs>(
+        addr: A,
+        timeout: Duration,
+    ) -> ConnectTimeoutFuture {
+        ConnectTimeoutFuture {
+            lookup: Some(lookup_host(addr)),
+            addrs: VecDeque::new(),
             last_err: None,
             future: None,
-            runtime: runtime_raw::current_runtime(),
+            timeout,
+            runtime: super::current_runtime().ok(),
         }
     }
 
     /// Returns the local address that this stream is connected to.
     ///
+    /// The address is looked up once when the stream is connected or accepted, so this doesn't
+    /// make a system call.
+    ///
     /// ## Examples
     /// ```no_run
     /// use runtime::net::TcpStream;
@@ -114,11 +231,17 @@ impl TcpStream {
     /// # Ok(())}
     /// ```
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
-        self.inner.local_addr()
+        match self.local_addr {
+            Some(addr) => Ok(addr),
+            None => self.inner.local_addr(),
+        }
     }
 
     /// Returns the remote address that this stream is connected to.
     ///
+    /// The address is known once the stream is connected or accepted, so this doesn't make a
+    /// system call.
+    ///
     /// ## Examples
     /// ```no_run
     /// use runtime::net::TcpStream;
@@ -132,7 +255,10 @@ impl TcpStream {
     /// # Ok(())}
     /// ```
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
-        self.inner.peer_addr()
+        match self.peer_addr {
+            Some(addr) => Ok(addr),
+            None => self.inner.peer_addr(),
+        }
     }
 
     /// Shuts down the read, write, or both halves of this connection.
@@ -158,15 +284,623 @@ impl TcpStream {
     pub fn shutdown(&self, how: std::net::Shutdown) -> std::io::Result<()> {
         self.inner.shutdown(how)
     }
+
+    /// Flush the data written to this stream.
+    ///
+    /// A `TcpStream` doesn't buffer writes itself: data is handed to the operating system as soon
+    /// as a write completes. Flushing therefore completes immediately, and doesn't wait for the
+    /// data to be sent, or for the peer to receive it. It's still useful when the stream is used
+    /// through a wrapper like [`BufWriter`], or by code that's generic over [`AsyncWrite`].
+    ///
+    /// To make sure the peer has received all data, shut down the write half of the stream and
+    /// wait for the peer to close the connection.
+    ///
+    /// This is the same as [`AsyncWriteExt::flush`], without having to import the trait.
+    ///
+    /// [`BufWriter`]: ../io/struct.BufWriter.html
+    /// [`AsyncWrite`]: ../io/trait.AsyncWrite.html
+    /// [`AsyncWriteExt::flush`]: ../io/trait.AsyncWriteExt.html#method.flush
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.write_all(b"hello world").await?;
+    /// stream.flush().await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn flush(&mut self) -> FlushFuture<'_> {
+        FlushFuture { inner: self }
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// For more information about this option, see [`set_nodelay`].
+    ///
+    /// [`set_nodelay`]: #method.set_nodelay
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_nodelay(true)?;
+    /// assert_eq!(stream.nodelay()?, true);
+    /// # Ok(()) }
+    /// ```
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.nodelay()
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// If set, this option disables the Nagle algorithm. This means that
+    /// segments are always sent as soon as possible, even if there is only a
+    /// small amount of data. When not set, data is buffered until there is a
+    /// sufficient amount to send out, thereby avoiding the frequent sending of
+    /// small packets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_nodelay(true)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    ///
+    /// For more information about this option, see [`set_ttl`].
+    ///
+    /// [`set_ttl`]: #method.set_ttl
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_ttl(100)?;
+    /// assert_eq!(stream.ttl()?, 100);
+    /// # Ok(()) }
+    /// ```
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.inner.ttl()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    ///
+    /// This value sets the time-to-live field that is used in every packet sent
+    /// from this socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_ttl(100)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.inner.set_ttl(ttl)
+    }
+
+    /// Gets the value of the `IP_TOS` option for this socket.
+    ///
+    /// For more information about this option, see [`set_tos`].
+    ///
+    /// [`set_tos`]: #method.set_tos
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_tos(0x10)?;
+    /// assert_eq!(stream.tos()?, 0x10);
+    /// # Ok(()) }
+    /// ```
+    pub fn tos(&self) -> io::Result<u32> {
+        self.inner.tos()
+    }
+
+    /// Sets the value for the `IP_TOS` option on this socket.
+    ///
+    /// This value sets the type-of-service field that is used in every packet sent from this
+    /// socket, such as a DSCP class for traffic prioritization. Only IPv4 sockets support this
+    /// option.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_tos(0x10)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        self.inner.set_tos(tos)
+    }
+
+    /// Reads the linger duration for this socket by getting the `SO_LINGER` option.
+    ///
+    /// For more information about this option, see [`set_linger`].
+    ///
+    /// [`set_linger`]: #method.set_linger
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_linger(Some(Duration::from_secs(1)))?;
+    /// assert_eq!(stream.linger()?, Some(Duration::from_secs(1)));
+    /// # Ok(()) }
+    /// ```
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.inner.linger()
+    }
+
+    /// Sets the linger duration of this socket by setting the `SO_LINGER` option.
+    ///
+    /// This option controls the action taken when a stream has unsent messages and the stream is
+    /// closed. If `SO_LINGER` is set, the system shall block the process until it can transmit the
+    /// data or until the time expires.
+    ///
+    /// If `SO_LINGER` is not specified, and the stream is closed, the system handles the call in a
+    /// way that allows the process to continue as quickly as possible.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_linger(Some(Duration::from_secs(1)))?;
+    /// # Ok(()) }
+    /// ```
+    pub fn set_linger(&self, dur: Option<Duration>) -> io::Result<()> {
+        self.inner.set_linger(dur)
+    }
+
+    /// Gets the way this stream is shut down when it's dropped.
+    ///
+    /// For more information, see [`set_shutdown_on_drop`].
+    ///
+    /// [`set_shutdown_on_drop`]: #method.set_shutdown_on_drop
+    pub fn shutdown_on_drop(&self) -> Option<std::net::Shutdown> {
+        self.shutdown_on_drop
+    }
+
+    /// Shut down the stream in the given way when it's dropped, before its socket is closed.
+    ///
+    /// By default dropping the stream just closes the socket, which resets the connection if data
+    /// that was received hasn't been read. Shutting down the write direction first sends the peer
+    /// an end-of-file instead, which some protocols require. Passing `None` disables this again.
+    ///
+    /// The setting only applies to this handle: streams created with [`try_clone`] start out with
+    /// it disabled. Since the shutdown applies to the connection, dropping this handle affects the
+    /// clones too.
+    ///
+    /// [`try_clone`]: #method.try_clone
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::net::TcpStream;
+    /// use std::net::Shutdown;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_shutdown_on_drop(Some(Shutdown::Write));
+    /// stream.write_all(b"bye").await?;
+    /// drop(stream);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_shutdown_on_drop(&mut self, how: Option<std::net::Shutdown>) {
+        self.shutdown_on_drop = how;
+    }
+
+    /// Creates a new `TcpStream` for the same socket.
+    ///
+    /// The returned stream duplicates the underlying file descriptor and registers the copy with
+    /// the runtime separately, so each handle can be used from its own task. Both handles refer to
+    /// the same connection: bytes written through either are sent to the peer, and bytes received
+    /// are read by whichever handle reads first.
+    ///
+    /// Returns an error if the runtime can't clone streams.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut reader = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let mut writer = reader.try_clone()?;
+    ///
+    /// runtime::spawn(async move {
+    ///     writer.write_all(b"hello world").await
+    /// });
+    ///
+    /// let mut buf = vec![0u8; 1024];
+    /// reader.read(&mut buf).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn try_clone(&self) -> io::Result<TcpStream> {
+        let inner = self.inner.try_clone()?;
+        Ok(TcpStream {
+            inner,
+            read_eof: false,
+            local_addr: self.local_addr,
+            peer_addr: self.peer_addr,
+            shutdown_on_drop: None,
+        })
+    }
+
+    /// Gets the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing the field in the
+    /// process. This can be useful for checking errors between calls, such as a connection that
+    /// was reset by the peer while no read or write was pending.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// if let Some(err) = stream.take_error()? {
+    ///     println!("socket error: {}", err);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+
+    /// Returns `true` once a read on this stream has returned 0 bytes.
+    ///
+    /// A read returns 0 bytes into a non-empty buffer when the peer has shut down its write half,
+    /// or closed the connection, and all data it sent has been read. Nothing more can be read
+    /// after that, but the stream can still be written to if the peer has only shut down writing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let mut buf = Vec::new();
+    /// stream.read_to_end(&mut buf).await?;
+    /// assert!(stream.read_eof());
+    /// # Ok(()) }
+    /// ```
+    pub fn read_eof(&self) -> bool {
+        self.read_eof
+    }
+
+    /// Splits the stream into a read half and a write half that borrow it.
+    ///
+    /// The halves can be used concurrently from within the same task, for example to read and write
+    /// at the same time with `join!`. Use [`into_split`] to get halves that can be moved into
+    /// separate tasks instead.
+    ///
+    /// Note that with [`AsyncReadExt`] in scope, calling `split` on an owned `TcpStream` resolves to
+    /// [`AsyncReadExt::split`], which consumes the stream. Call this method on a `&mut TcpStream`,
+    /// or as `TcpStream::split(&mut stream)`.
+    ///
+    /// [`into_split`]: struct.TcpStream.html#method.into_split
+    /// [`AsyncReadExt`]: https://docs.rs/futures-preview/0.3.0-alpha.13/futures/io/trait.AsyncReadExt.html
+    /// [`AsyncReadExt::split`]: https://docs.rs/futures-preview/0.3.0-alpha.13/futures/io/trait.AsyncReadExt.html#method.split
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let (mut reader, mut writer) = TcpStream::split(&mut stream);
+    ///
+    /// let mut buf = vec![0u8; 11];
+    /// let (written, read) = futures::join!(
+    ///     writer.write_all(b"hello world"),
+    ///     reader.read_exact(&mut buf),
+    /// );
+    /// written?;
+    /// read?;
+    /// # Ok(()) }
+    /// ```
+    pub fn split(&mut self) -> (ReadHalf<'_>, WriteHalf<'_>) {
+        let (read, write) = AsyncReadExt::split(self);
+        (ReadHalf { inner: read }, WriteHalf { inner: write })
+    }
+
+    /// Splits the stream into an owned read half and an owned write half.
+    ///
+    /// Unlike `split` from [`futures::io`], the returned halves don't borrow the stream, which
+    /// means they can be moved into separate tasks. Dropping the [`OwnedWriteHalf`] shuts down the
+    /// write direction of the connection.
+    ///
+    /// [`futures::io`]: https://docs.rs/futures-preview/0.3.0-alpha.13/futures/io
+    /// [`OwnedWriteHalf`]: struct.OwnedWriteHalf.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let (mut reader, mut writer) = stream.into_split();
+    ///
+    /// runtime::spawn(async move {
+    ///     writer.write_all(b"hello world").await
+    /// });
+    ///
+    /// let mut buf = vec![0u8; 1024];
+    /// reader.read(&mut buf).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf) {
+        let inner = Arc::new(Mutex::new(self));
+        let read = OwnedReadHalf {
+            inner: inner.clone(),
+        };
+        let write = OwnedWriteHalf { inner };
+        (read, write)
+    }
+
+    /// Check if the stream can be read from.
+    ///
+    /// Returns `Poll::Ready(Ok(()))` once data can be read without blocking, and otherwise
+    /// schedules the current task to be woken up when it can. This is useful for doing custom
+    /// buffering on top of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::future;
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// future::poll_fn(|cx| stream.poll_read_ready(cx)).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.as_mut().poll_read_ready(cx)
+    }
+
+    /// Check if the stream can be written to.
+    ///
+    /// Returns `Poll::Ready(Ok(()))` once data can be written without blocking, and otherwise
+    /// schedules the current task to be woken up when it can.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::future;
+    /// use runtime::net::TcpStream;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// future::poll_fn(|cx| stream.poll_write_ready(cx)).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.as_mut().poll_write_ready(cx)
+    }
+}
+
+/// Reads resolve to `Ok(0)` when the peer has shut down its write half, or closed the connection,
+/// and all data it sent has been read. The stream remembers this, which can be checked with
+/// [`read_eof`].
+///
+/// [`read_eof`]: struct.TcpStream.html#method.read_eof
+impl AsyncRead for TcpStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let res = ready!(self.inner.as_mut().poll_read(cx, buf));
+        if let Ok(0) = res {
+            self.read_eof |= !buf.is_empty();
+        }
+        Poll::Ready(res)
+    }
+
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let res = ready!(self.inner.as_mut().poll_read_vectored(cx, bufs));
+        if let Ok(0) = res {
+            self.read_eof |= bufs.iter().any(|buf| !buf.is_empty());
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl AsyncWrite for TcpStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.inner.as_mut().poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.as_mut().poll_close(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.inner.as_mut().poll_write_vectored(cx, bufs)
+    }
+}
+
+impl Drop for TcpStream {
+    fn drop(&mut self) {
+        if let Some(how) = self.shutdown_on_drop {
+            let _ = self.inner.shutdown(how);
+        }
+    }
+}
+
+/// The read half of a [`TcpStream`], created by [`TcpStream::into_split`].
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`TcpStream::into_split`]: struct.TcpStream.html#method.into_split
+#[derive(Debug)]
+pub struct OwnedReadHalf {
+    inner: Arc<Mutex<TcpStream>>,
+}
+
+impl AsyncRead for OwnedReadHalf {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.inner.lock().unwrap()).poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.inner.lock().unwrap()).poll_read_vectored(cx, bufs)
+    }
+}
+
+/// The write half of a [`TcpStream`], created by [`TcpStream::into_split`].
+///
+/// Dropping this half shuts down the write direction of the connection.
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`TcpStream::into_split`]: struct.TcpStream.html#method.into_split
+#[derive(Debug)]
+pub struct OwnedWriteHalf {
+    inner: Arc<Mutex<TcpStream>>,
+}
+
+impl AsyncWrite for OwnedWriteHalf {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.inner.lock().unwrap()).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.inner.lock().unwrap()).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Closing the whole stream would also close the read half.
+        let res = self
+            .inner
+            .lock()
+            .unwrap()
+            .shutdown(std::net::Shutdown::Write);
+        Poll::Ready(res)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.inner.lock().unwrap()).poll_write_vectored(cx, bufs)
+    }
+}
+
+impl Drop for OwnedWriteHalf {
+    fn drop(&mut self) {
+        if let Ok(inner) = self.inner.lock() {
+            let _ = inner.shutdown(std::net::Shutdown::Write);
+        }
+    }
+}
+
+/// The read half of a [`TcpStream`], created by [`TcpStream::split`].
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`TcpStream::split`]: struct.TcpStream.html#method.split
+#[derive(Debug)]
+pub struct ReadHalf<'a> {
+    inner: futures::io::ReadHalf<&'a mut TcpStream>,
 }
 
-impl AsyncRead for TcpStream {
+impl AsyncRead for ReadHalf<'_> {
     fn poll_read(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
-        self.inner.as_mut().poll_read(cx, buf)
+        Pin::new(&mut self.inner).poll_read(cx, buf)
     }
 
     fn poll_read_vectored(
@@ -174,25 +908,34 @@ impl AsyncRead for TcpStream {
         cx: &mut Context<'_>,
         bufs: &mut [IoSliceMut<'_>],
     ) -> Poll<io::Result<usize>> {
-        self.inner.as_mut().poll_read_vectored(cx, bufs)
+        Pin::new(&mut self.inner).poll_read_vectored(cx, bufs)
     }
 }
 
-impl AsyncWrite for TcpStream {
+/// The write half of a [`TcpStream`], created by [`TcpStream::split`].
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`TcpStream::split`]: struct.TcpStream.html#method.split
+#[derive(Debug)]
+pub struct WriteHalf<'a> {
+    inner: futures::io::WriteHalf<&'a mut TcpStream>,
+}
+
+impl AsyncWrite for WriteHalf<'_> {
     fn poll_write(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        self.inner.as_mut().poll_write(cx, buf)
+        Pin::new(&mut self.inner).poll_write(cx, buf)
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.inner.as_mut().poll_flush(cx)
+        Pin::new(&mut self.inner).poll_flush(cx)
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.inner.as_mut().poll_close(cx)
+        Pin::new(&mut self.inner).poll_close(cx)
     }
 
     fn poll_write_vectored(
@@ -200,10 +943,34 @@ impl AsyncWrite for TcpStream {
         cx: &mut Context<'_>,
         bufs: &[IoSlice<'_>],
     ) -> Poll<io::Result<usize>> {
-        self.inner.as_mut().poll_write_vectored(cx, bufs)
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+}
+
+/// The future returned by [`TcpStream::flush`].
+///
+/// [`TcpStream::flush`]: struct.TcpStream.html#method.flush
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct FlushFuture<'stream> {
+    inner: &'stream mut TcpStream,
+}
+
+impl Future for FlushFuture<'_> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.inner).poll_flush(cx)
     }
 }
 
+/// How long a connection attempt is given before the next address is tried alongside it.
+///
+/// This is the "Connection Attempt Delay" recommended by [RFC 8305].
+///
+/// [RFC 8305]: https://tools.ietf.org/html/rfc8305#section-5
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
 /// The future returned by [`TcpStream::connect`].
 ///
 /// Resolves to a [`TcpStream`] when the stream is connected.
@@ -212,36 +979,284 @@ impl AsyncWrite for TcpStream {
 /// [`TcpStream`]: struct.TcpStream.html
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct ConnectFuture {
-    addrs: Option<io::Result<VecDeque<SocketAddr>>>,
-    last_err: Option<io::Error>,
-    future: Option<BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::TcpStream>>>>>,
-    runtime: &'static dyn runtime_raw::Runtime,
+    lookup: Option<LookupHostFuture>,
+    addrs: VecDeque<SocketAddr>,
+    attempts: Vec<(SocketAddr, io::Error)>,
+    /// The connection attempts in progress.
+    futures: Vec<(SocketAddr, ConnectAttempt)>,
+    /// Fires when the next address should be tried, even if the previous attempts are still in
+    /// progress.
+    next_attempt: Option<RuntimeDelay>,
+    /// The runtime to connect on, or `None` if there was no runtime when the future was created.
+    runtime: Option<&'static dyn runtime_raw::Runtime>,
+}
+
+impl ConnectFuture {
+    fn poll_connect(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<std::result::Result<TcpStream, ConnectError>> {
+        let runtime = match self.runtime {
+            Some(runtime) => runtime,
+            None => {
+                return Poll::Ready(Err(ConnectError {
+                    lookup: Some(super::no_runtime()),
+                    attempts: Vec::new(),
+                }))
+            }
+        };
+
+        // Wait for the addresses to resolve, or return an error if they couldn't be resolved.
+        if let Some(lookup) = self.lookup.as_mut() {
+            match ready!(lookup.poll_unpin(cx)) {
+                Ok(addrs) => self.addrs = interleave_families(addrs),
+                Err(err) => {
+                    self.lookup = None;
+                    return Poll::Ready(Err(ConnectError {
+                        lookup: Some(err),
+                        attempts: Vec::new(),
+                    }));
+                }
+            }
+            self.lookup = None;
+        }
+
+        loop {
+            // Poll the connection attempts, and return the first one that succeeds.
+            let mut i = 0;
+            while i < self.futures.len() {
+                match self.futures[i].1.as_mut().poll(cx) {
+                    Poll::Pending => i += 1,
+                    Poll::Ready(Ok(inner)) => {
+                        let (addr, _) = self.futures.swap_remove(i);
+                        self.futures.clear();
+                        self.next_attempt = None;
+                        return Poll::Ready(Ok(TcpStream::new(inner, Some(addr))));
+                    }
+                    Poll::Ready(Err(err)) => {
+                        let (addr, _) = self.futures.remove(i);
+                        self.attempts.push((addr, err));
+                    }
+                }
+            }
+
+            // Try the next address once every attempt has failed, or once the last one has been
+            // given `CONNECTION_ATTEMPT_DELAY` to complete.
+            if !self.futures.is_empty() {
+                match self.next_attempt.as_mut() {
+                    Some(delay) => ready!(delay.poll_unpin(cx)),
+                    None => return Poll::Pending,
+                };
+            }
+
+            // Get the next address from the list, or return an error if every attempt failed.
+            let addr = match self.addrs.pop_front() {
+                Some(addr) => addr,
+                None if self.futures.is_empty() => {
+                    return Poll::Ready(Err(ConnectError {
+                        lookup: None,
+                        attempts: std::mem::take(&mut self.attempts),
+                    }));
+                }
+                None => {
+                    self.next_attempt = None;
+                    return Poll::Pending;
+                }
+            };
+
+            // Start the next connection attempt.
+            self.futures.push((addr, runtime.connect_tcp_stream(&addr)));
+            self.next_attempt = if self.addrs.is_empty() {
+                None
+            } else {
+                Some(runtime.new_delay_at(runtime.now() + CONNECTION_ATTEMPT_DELAY))
+            };
+        }
+    }
+}
+
+/// Order addresses so that IPv6 and IPv4 addresses alternate, starting with the family of the
+/// first address, as recommended by [RFC 8305].
+///
+/// [RFC 8305]: https://tools.ietf.org/html/rfc8305#section-4
+fn interleave_families(addrs: impl Iterator<Item = SocketAddr>) -> VecDeque<SocketAddr> {
+    let mut addrs = addrs.peekable();
+    let first_is_ipv6 = match addrs.peek() {
+        Some(addr) => addr.is_ipv6(),
+        None => return VecDeque::new(),
+    };
+    let (mut first, mut second): (VecDeque<_>, VecDeque<_>) =
+        addrs.partition(|addr| addr.is_ipv6() == first_is_ipv6);
+
+    let mut interleaved = VecDeque::with_capacity(first.len() + second.len());
+    loop {
+        match (first.pop_front(), second.pop_front()) {
+            (None, None) => return interleaved,
+            (a, b) => interleaved.extend(a.into_iter().chain(b)),
+        }
+    }
 }
 
 impl Future for ConnectFuture {
     type Output = io::Result<TcpStream>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_connect(cx).map_err(io::Error::from)
+    }
+}
+
+impl fmt::Debug for ConnectFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connect")
+            .field("addrs", &self.addrs)
+            .finish()
+    }
+}
+
+/// The future returned by [`TcpStream::connect_detailed`].
+///
+/// Resolves to a [`TcpStream`] when the stream is connected, or to a [`ConnectError`] recording
+/// every failed attempt.
+///
+/// [`TcpStream::connect_detailed`]: struct.TcpStream.html#method.connect_detailed
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`ConnectError`]: struct.ConnectError.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ConnectDetailedFuture {
+    inner: ConnectFuture,
+}
+
+impl Future for ConnectDetailedFuture {
+    type Output = std::result::Result<TcpStream, ConnectError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_connect(cx)
+    }
+}
+
+/// The error returned by [`TcpStream::connect_detailed`].
+///
+/// Records the error of every address that a connection was attempted to, in the order the
+/// attempts failed. If the address couldn't be resolved, no connection was attempted.
+///
+/// [`TcpStream::connect_detailed`]: struct.TcpStream.html#method.connect_detailed
+#[derive(Debug)]
+pub struct ConnectError {
+    lookup: Option<io::Error>,
+    attempts: Vec<(SocketAddr, io::Error)>,
+}
+
+impl ConnectError {
+    /// Returns each address a connection was attempted to, along with the error it failed with.
+    pub fn attempts(&self) -> &[(SocketAddr, io::Error)] {
+        &self.attempts
+    }
+}
+
+impl fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(err) = &self.lookup {
+            return write!(f, "could not resolve the address: {}", err);
+        }
+        match self.attempts.last() {
+            None => f.write_str("could not resolve to any addresses"),
+            Some((addr, err)) if self.attempts.len() == 1 => {
+                write!(f, "could not connect to {}: {}", addr, err)
+            }
+            Some((addr, err)) => write!(
+                f,
+                "could not connect to any of {} addresses, the last one ({}) failed with: {}",
+                self.attempts.len(),
+                addr,
+                err
+            ),
+        }
+    }
+}
+
+impl Error for ConnectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        let err = self
+            .lookup
+            .as_ref()
+            .or_else(|| self.attempts.last().map(|(_, err)| err))?;
+        Some(err)
+    }
+}
+
+impl From<ConnectError> for io::Error {
+    /// Returns the error that made the last attempt fail, or the error that prevented the address
+    /// from being resolved.
+    fn from(mut err: ConnectError) -> io::Error {
+        match (err.lookup, err.attempts.pop()) {
+            (Some(err), _) => err,
+            (None, Some((_, err))) => err,
+            (None, None) => io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "could not resolve to any addresses",
+            ),
+        }
+    }
+}
+
+/// The future returned by [`TcpStream::connect_timeout`].
+///
+/// Resolves to a [`TcpStream`] when the stream is connected.
+///
+/// [`TcpStream::connect_timeout`]: struct.TcpStream.html#method.connect_timeout
+/// [`TcpStream`]: struct.TcpStream.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ConnectTimeoutFuture {
+    lookup: Option<LookupHostFuture>,
+    addrs: VecDeque<SocketAddr>,
+    last_err: Option<io::Error>,
+    future: Option<(SocketAddr, ConnectAttempt, RuntimeDelay)>,
+    timeout: Duration,
+    /// The runtime to connect on, or `None` if there was no runtime when the future was created.
+    runtime: Option<&'static dyn runtime_raw::Runtime>,
+}
+
+impl Future for ConnectTimeoutFuture {
+    type Output = io::Result<TcpStream>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let runtime = self.runtime.ok_or_else(super::no_runtime)?;
         loop {
-            // Poll the connect future, if there is one.
-            if let Some(future) = self.future.as_mut() {
+            // Poll the connect future and its timer, if there are any.
+            if let Some((addr, future, delay)) = self.future.as_mut() {
                 match future.as_mut().poll(cx) {
-                    Poll::Pending => return Poll::Pending,
-                    Poll::Ready(Ok(inner)) => return Poll::Ready(Ok(TcpStream { inner })),
+                    Poll::Ready(Ok(inner)) => {
+                        return Poll::Ready(Ok(TcpStream::new(inner, Some(*addr))));
+                    }
                     Poll::Ready(Err(err)) => self.last_err = Some(err),
+                    Poll::Pending => match delay.poll_unpin(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(_) => {
+                            self.last_err = Some(io::Error::new(
+                                io::ErrorKind::TimedOut,
+                                "connection attempt timed out",
+                            ))
+                        }
+                    },
                 }
             }
 
-            // Get the list of addresses, or return an error if the list couldn't be parsed.
-            let addrs = match self.addrs.as_mut().expect("polled a completed future") {
-                Ok(addrs) => addrs,
-                Err(_) => {
-                    return Poll::Ready(Err(self.addrs.take().unwrap().err().unwrap()));
+            // Wait for the addresses to resolve, or return an error if they couldn't be resolved.
+            if let Some(lookup) = self.lookup.as_mut() {
+                match lookup.poll_unpin(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(addrs)) => self.addrs = addrs.collect(),
+                    Poll::Ready(Err(err)) => {
+                        self.lookup = None;
+                        return Poll::Ready(Err(err));
+                    }
                 }
-            };
+                self.lookup = None;
+            }
 
             // Get the next address from the list, or return an error if the list is empty.
-            let addr = match addrs.pop_front() {
+            let addr = match self.addrs.pop_front() {
                 Some(addr) => addr,
                 None => {
                     let err = self.last_err.take().unwrap_or_else(|| {
@@ -254,16 +1269,19 @@ impl Future for ConnectFuture {
                 }
             };
 
-            // Initialize the next connect future.
-            self.future = Some(self.runtime.connect_tcp_stream(&addr));
+            // Initialize the next connect future, racing it against a fresh timer.
+            let future = runtime.connect_tcp_stream(&addr);
+            let delay = runtime.new_delay_at(runtime.now() + self.timeout);
+            self.future = Some((addr, future, delay));
         }
     }
 }
 
-impl fmt::Debug for ConnectFuture {
+impl fmt::Debug for ConnectTimeoutFuture {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Connect")
+        f.debug_struct("ConnectTimeout")
             .field("addrs", &self.addrs)
+            .field("timeout", &self.timeout)
             .finish()
     }
 }
@@ -271,8 +1289,9 @@ impl fmt::Debug for ConnectFuture {
 /// A TCP socket server, listening for connections.
 ///
 /// After creating a `TcpListener` by [`bind`]ing it to a socket address, it listens for incoming
-/// TCP connections. These can be accepted by awaiting elements from the async stream of incoming
-/// connections, [`incoming`][`TcpListener::incoming`].
+/// TCP connections. The listener is itself an async stream of incoming connections, so these can
+/// be accepted by awaiting its elements. The stream can also be borrowed from the listener with
+/// [`incoming`][`TcpListener::incoming`].
 ///
 /// The socket will be closed when the value is dropped.
 ///
@@ -293,8 +1312,7 @@ impl fmt::Debug for ConnectFuture {
 ///     println!("Listening on {}", listener.local_addr()?);
 ///
 ///     // accept connections and process them in parallel
-///     let mut incoming = listener.incoming();
-///     while let Some(stream) = incoming.next().await {
+///     while let Some(stream) = listener.next().await {
 ///         runtime::spawn(async move {
 ///             let stream = stream?;
 ///             println!("Accepting from: {}", stream.peer_addr()?);
@@ -335,9 +1353,140 @@ impl TcpListener {
     ///
     /// [`local_addr`]: #method.local_addr
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let runtime = super::current_runtime()?;
+        let mut last_err = None;
+        for addr in addr.to_socket_addrs()? {
+            match runtime.bind_tcp_listener(&addr) {
+                Ok(inner) => return Ok(TcpListener { inner }),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "could not resolve to any addresses",
+            )
+        }))
+    }
+
+    /// Creates a new `TcpListener` which will be bound to the specified socket address.
+    ///
+    /// This is equivalent to [`bind`], but skips address resolution, which is useful when the
+    /// address is already known, for example from a configuration file.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpListener;
+    /// use std::net::SocketAddr;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = TcpListener::bind_addr(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+    /// # Ok(())}
+    /// ```
+    pub fn bind_addr(addr: SocketAddr) -> io::Result<Self> {
+        let inner = super::current_runtime()?.bind_tcp_listener(&addr)?;
+        Ok(TcpListener { inner })
+    }
+
+    /// Creates a new `TcpListener` which will be bound to the specified address, with `backlog`
+    /// as the maximum length of the queue of pending connections.
+    ///
+    /// This is otherwise equivalent to [`bind`]. Servers that receive connections at a high rate
+    /// may want a larger queue than the platform default, so that connections aren't refused
+    /// while the application is busy. The operating system may silently cap the value.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpListener;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = TcpListener::bind_with_backlog("127.0.0.1:0", 1024)?;
+    /// # Ok(())}
+    /// ```
+    pub fn bind_with_backlog<A: ToSocketAddrs>(addr: A, backlog: i32) -> io::Result<Self> {
+        let runtime = super::current_runtime()?;
+        let mut last_err = None;
+        for addr in addr.to_socket_addrs()? {
+            match runtime.bind_tcp_listener_with_backlog(&addr, backlog) {
+                Ok(inner) => return Ok(TcpListener { inner }),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "could not resolve to any addresses",
+            )
+        }))
+    }
+
+    /// Creates a new `TcpListener` which will be bound to the specified address, with
+    /// `SO_REUSEADDR` and `SO_REUSEPORT` set.
+    ///
+    /// This is otherwise equivalent to [`bind`]. `SO_REUSEPORT` lets several listeners, usually
+    /// in separate worker processes, bind to the same address, and the operating system spreads
+    /// incoming connections across them. Windows doesn't support `SO_REUSEPORT`, so only
+    /// `SO_REUSEADDR` is set there.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpListener;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = TcpListener::bind_reuseport("127.0.0.1:8080")?;
+    /// # Ok(())}
+    /// ```
+    pub fn bind_reuseport<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let runtime = super::current_runtime()?;
+        let mut last_err = None;
+        for addr in addr.to_socket_addrs()? {
+            match runtime.bind_tcp_listener_reuseport(&addr) {
+                Ok(inner) => return Ok(TcpListener { inner }),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "could not resolve to any addresses",
+            )
+        }))
+    }
+
+    /// Creates a new `TcpListener` which will be bound to the specified address and only accept
+    /// connections arriving on the network interface named `interface`, such as `"eth0"`.
+    ///
+    /// This is otherwise equivalent to [`bind`]. It sets `SO_BINDTODEVICE` on the socket before
+    /// binding it, which is only available on Linux. Kernels before 5.7 only allow it for processes
+    /// with the `CAP_NET_RAW` capability, and fail with a permission error otherwise. Like
+    /// [`bind`], it also sets `SO_REUSEADDR`.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpListener;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = TcpListener::bind_device("0.0.0.0:8080", "eth0")?;
+    /// # Ok(())}
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn bind_device<A: ToSocketAddrs>(addr: A, interface: &str) -> io::Result<Self> {
+        let runtime = super::current_runtime()?;
         let mut last_err = None;
         for addr in addr.to_socket_addrs()? {
-            match runtime_raw::current_runtime().bind_tcp_listener(&addr) {
+            match runtime.bind_tcp_listener_device(&addr, interface) {
                 Ok(inner) => return Ok(TcpListener { inner }),
                 Err(e) => last_err = Some(e),
             }
@@ -350,6 +1499,28 @@ impl TcpListener {
         }))
     }
 
+    /// Creates a new `TcpListener` from a bound standard library listener.
+    ///
+    /// This is useful when the listening socket was created elsewhere, for example when it was
+    /// inherited from a parent process through socket activation. The listener is put into
+    /// non-blocking mode and registered with the current runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpListener;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main () -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    /// let std_listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// let listener = TcpListener::from_std(std_listener)?;
+    /// # Ok(())}
+    /// ```
+    pub fn from_std(listener: std::net::TcpListener) -> io::Result<Self> {
+        let inner = super::current_runtime()?.tcp_listener_from_std(listener)?;
+        Ok(TcpListener { inner })
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, to identify when binding to port 0
@@ -373,12 +1544,53 @@ impl TcpListener {
         self.inner.local_addr()
     }
 
+    /// Gets the value of the `IP_TTL` option for this socket.
+    ///
+    /// For more information about this option, see [`set_ttl`].
+    ///
+    /// [`set_ttl`]: #method.set_ttl
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpListener;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// listener.set_ttl(100)?;
+    /// assert_eq!(listener.ttl()?, 100);
+    /// # Ok(())}
+    /// ```
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.inner.ttl()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    ///
+    /// This value sets the time-to-live field that is used in every packet sent
+    /// from this socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::TcpListener;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// listener.set_ttl(100)?;
+    /// # Ok(())}
+    /// ```
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.inner.set_ttl(ttl)
+    }
+
     /// Handle all incoming connections.
     ///
     /// This method returns a stream of [`TcpStream`]s. This is useful when you
     /// want to open up a port that can handle multiple incoming requests.
     ///
-    /// If you intend to only handle single connections use [`.accept()`].
+    /// If you intend to only handle single connections use [`.accept()`]. The listener itself is
+    /// also a stream of incoming connections, which can be used when it's not needed afterwards.
     ///
     /// [`TcpStream`]: struct.TcpStream.html
     /// [`.accept()`]: struct.TcpListener.html#method.accept
@@ -427,8 +1639,7 @@ impl TcpListener {
     /// # Ok(())}
     /// ```
     pub fn accept(&mut self) -> AcceptFuture<'_> {
-        let incoming = self.incoming();
-        AcceptFuture { inner: incoming }
+        AcceptFuture { inner: self }
     }
 }
 
@@ -441,16 +1652,15 @@ impl TcpListener {
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 #[derive(Debug)]
 pub struct AcceptFuture<'stream> {
-    inner: IncomingStream<'stream>,
+    inner: &'stream mut TcpListener,
 }
 
 impl<'stream> Future for AcceptFuture<'stream> {
     type Output = io::Result<(TcpStream, SocketAddr)>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let stream = ready!(self.inner.poll_next_unpin(cx)).unwrap()?;
-        let addr = stream.peer_addr().unwrap();
-        Poll::Ready(Ok((stream, addr)))
+        let (inner, addr) = ready!(self.inner.inner.as_mut().poll_accept(cx)?);
+        Poll::Ready(Ok((TcpStream::new(inner, Some(addr)), addr)))
     }
 }
 
@@ -468,12 +1678,21 @@ pub struct IncomingStream<'listener> {
     inner: &'listener mut TcpListener,
 }
 
+impl Stream for TcpListener {
+    type Item = io::Result<TcpStream>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (inner, addr) = ready!(self.inner.as_mut().poll_accept(cx)?);
+        Poll::Ready(Some(Ok(TcpStream::new(inner, Some(addr)))))
+    }
+}
+
 impl<'listener> Stream for IncomingStream<'listener> {
     type Item = io::Result<TcpStream>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let inner = ready!(self.inner.inner.as_mut().poll_accept(cx)?);
-        Poll::Ready(Some(Ok(TcpStream { inner })))
+        let (inner, addr) = ready!(self.inner.inner.as_mut().poll_accept(cx)?);
+        Poll::Ready(Some(Ok(TcpStream::new(inner, Some(addr)))))
     }
 }
 
@@ -4,6 +4,16 @@
 //! done by using [`send_to`] which returns the [`SendTo`] future. Reading data from the socket is
 //! done by using [`recv_from`] which returns the [`RecvFrom`] future.
 //!
+//! A socket that only talks to a single peer can be [`connect`]ed to it, after which [`send`] and
+//! [`recv`] can be used without passing the peer's address around.
+//!
+//! To send and receive from separate tasks, [`split`] the socket into a send half and a receive
+//! half.
+//!
+//! [`connect`]: struct.UdpSocket.html#method.connect
+//! [`send`]: struct.UdpSocket.html#method.send
+//! [`recv`]: struct.UdpSocket.html#method.recv
+//! [`split`]: struct.UdpSocket.html#method.split
 //! [`UdpSocket::bind`]: struct.UdpSocket.html#method.bind
 //! [`send_to`]: struct.UdpSocket.html#method.send_to
 //! [`recv_from`]: struct.UdpSocket.html#method.recv_from
@@ -12,9 +22,10 @@
 
 use futures::prelude::*;
 
-use std::io;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 
 /// A UDP socket.
@@ -76,9 +87,10 @@ impl UdpSocket {
     /// # }
     /// ```
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let runtime = super::current_runtime()?;
         let mut last_err = None;
         for addr in addr.to_socket_addrs()? {
-            match runtime_raw::current_runtime().bind_udp_socket(&addr) {
+            match runtime.bind_udp_socket(&addr) {
                 Ok(inner) => return Ok(UdpSocket { inner }),
                 Err(e) => last_err = Some(e),
             }
@@ -91,6 +103,86 @@ impl UdpSocket {
         }))
     }
 
+    /// Creates a UDP socket bound to the given socket address.
+    ///
+    /// This is equivalent to [`bind`], but skips address resolution, which is useful when the
+    /// address is already known, for example from a configuration file.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    /// use std::net::SocketAddr;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let socket = UdpSocket::bind_addr(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_addr(addr: SocketAddr) -> io::Result<Self> {
+        let inner = super::current_runtime()?.bind_udp_socket(&addr)?;
+        Ok(UdpSocket { inner })
+    }
+
+    /// Creates a UDP socket from the given address, with `SO_REUSEADDR` and `SO_REUSEPORT` set.
+    ///
+    /// This is otherwise equivalent to [`bind`]. `SO_REUSEPORT` lets several sockets, usually in
+    /// separate worker processes, bind to the same address, and the operating system spreads
+    /// incoming datagrams across them. Windows doesn't support `SO_REUSEPORT`, so only
+    /// `SO_REUSEADDR` is set there.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let socket = UdpSocket::bind_reuseport("127.0.0.1:8080")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_reuseport<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let runtime = super::current_runtime()?;
+        let mut last_err = None;
+        for addr in addr.to_socket_addrs()? {
+            match runtime.bind_udp_socket_reuseport(&addr) {
+                Ok(inner) => return Ok(UdpSocket { inner }),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "could not resolve to any addresses",
+            )
+        }))
+    }
+
+    /// Creates a new `UdpSocket` from a bound standard library socket.
+    ///
+    /// This is useful when the socket was created or configured elsewhere. The socket is put into
+    /// non-blocking mode and registered with the current runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let std_socket = std::net::UdpSocket::bind("127.0.0.1:0")?;
+    /// let socket = UdpSocket::from_std(std_socket)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_std(socket: std::net::UdpSocket) -> io::Result<Self> {
+        let inner = super::current_runtime()?.udp_socket_from_std(socket)?;
+        Ok(UdpSocket { inner })
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port 0 to figure out which port was
@@ -99,9 +191,9 @@ impl UdpSocket {
     /// # Examples
     ///
     /// ```no_run
-    ///	use runtime::net::UdpSocket;
+    /// use runtime::net::UdpSocket;
     ///
-    ///	# fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let socket = UdpSocket::bind("127.0.0.1:0")?;
     /// println!("Address: {:?}", socket.local_addr());
     /// # Ok(())
@@ -111,6 +203,29 @@ impl UdpSocket {
         self.inner.local_addr()
     }
 
+    /// Returns the remote address that this socket is [connected] to.
+    ///
+    /// Returns an error of kind [`NotConnected`] if the socket isn't connected.
+    ///
+    /// [connected]: #method.connect
+    /// [`NotConnected`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.NotConnected
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let socket = UdpSocket::bind("127.0.0.1:0")?;
+    /// socket.connect("127.0.0.1:8080")?;
+    /// assert_eq!(socket.peer_addr()?, "127.0.0.1:8080".parse().unwrap());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
     /// Sends data on the socket to the given address.
     ///
     /// On success, returns the number of bytes written.
@@ -179,6 +294,305 @@ impl UdpSocket {
         RecvFromFuture { buf, socket: self }
     }
 
+    /// Sends data from multiple buffers on the socket to the given address, as a single datagram.
+    ///
+    /// This is useful to assemble a packet from several parts, such as a header and a payload,
+    /// without copying them into one buffer first. On success, returns the number of bytes
+    /// written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    /// use std::io::IoSlice;
+    ///
+    /// # async fn send_data() -> std::io::Result<()> {
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0")?;
+    ///
+    /// let header = [1, 0, 0, 5];
+    /// let bufs = [IoSlice::new(&header), IoSlice::new(b"hello")];
+    /// let sent = socket.send_to_vectored(&bufs, "127.0.0.1:7878").await?;
+    /// println!("Sent {} bytes", sent);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_to_vectored<'socket, 'bufs, A: ToSocketAddrs>(
+        &'socket mut self,
+        bufs: &'bufs [IoSlice<'bufs>],
+        addr: A,
+    ) -> SendToVectoredFuture<'socket, 'bufs> {
+        let addr = addr
+            .to_socket_addrs()
+            .map(|mut iter| iter.next())
+            .transpose();
+        SendToVectoredFuture {
+            bufs,
+            addr,
+            socket: self,
+        }
+    }
+
+    /// Receives a datagram from the socket into multiple buffers, filling them in order.
+    ///
+    /// On success, returns the number of bytes read and the origin.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    /// use std::io::IoSliceMut;
+    ///
+    /// # async fn recv_data() -> std::io::Result<()> {
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0")?;
+    ///
+    /// let mut header = [0; 4];
+    /// let mut payload = [0; 1024];
+    /// let mut bufs = [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)];
+    /// let (recv, peer) = socket.recv_from_vectored(&mut bufs).await?;
+    /// println!("Received {} bytes from {}", recv, peer);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn recv_from_vectored<'socket, 'bufs, 'buf>(
+        &'socket mut self,
+        bufs: &'bufs mut [IoSliceMut<'buf>],
+    ) -> RecvFromVectoredFuture<'socket, 'bufs, 'buf> {
+        RecvFromVectoredFuture { bufs, socket: self }
+    }
+
+    /// Sends several datagrams, each to its own address.
+    ///
+    /// On Linux, the datagrams are sent with a single `sendmmsg` system call instead of one
+    /// system call each. Other platforms send them one at a time.
+    ///
+    /// On success, returns the number of datagrams sent. This is smaller than the number of
+    /// packets if the socket's send buffer filled up part of the way through, in which case the
+    /// remaining packets can be sent with another call.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # async fn send_data() -> std::io::Result<()> {
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0")?;
+    ///
+    /// let peer = "127.0.0.1:7878".parse().unwrap();
+    /// let packets = [(&b"hello"[..], peer), (&b"world"[..], peer)];
+    /// let sent = socket.send_batch(&packets).await?;
+    /// println!("Sent {} datagrams", sent);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_batch<'socket, 'packets>(
+        &'socket mut self,
+        packets: &'packets [(&'packets [u8], SocketAddr)],
+    ) -> SendBatchFuture<'socket, 'packets> {
+        SendBatchFuture {
+            packets,
+            socket: self,
+        }
+    }
+
+    /// Receives several datagrams, one into each buffer.
+    ///
+    /// On Linux, the datagrams are received with a single `recvmmsg` system call instead of one
+    /// system call each. Other platforms receive them one at a time.
+    ///
+    /// Completes as soon as at least one datagram has been received, without waiting for every
+    /// buffer to be filled. On success, returns the number of bytes read and the origin of each
+    /// datagram, in the order of the buffers they were received into.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # async fn recv_data() -> std::io::Result<()> {
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0")?;
+    ///
+    /// let mut storage = vec![[0; 1500]; 8];
+    /// let mut bufs: Vec<&mut [u8]> = storage.iter_mut().map(|buf| &mut buf[..]).collect();
+    /// for (len, peer) in socket.recv_batch(&mut bufs).await? {
+    ///     println!("Received {} bytes from {}", len, peer);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn recv_batch<'socket, 'bufs, 'buf>(
+        &'socket mut self,
+        bufs: &'bufs mut [&'buf mut [u8]],
+    ) -> RecvBatchFuture<'socket, 'bufs, 'buf> {
+        RecvBatchFuture { bufs, socket: self }
+    }
+
+    /// Receives data from the socket without removing it from the queue.
+    ///
+    /// On success, returns the number of bytes read and the origin. Successive calls return the
+    /// same datagram until it's received with [`recv_from`] or [`recv`].
+    ///
+    /// [`recv_from`]: #method.recv_from
+    /// [`recv`]: #method.recv
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # async fn peek_data() -> std::io::Result<()> {
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0")?;
+    ///
+    /// let mut header = [0; 4];
+    /// let (peeked, peer) = socket.peek_from(&mut header).await?;
+    /// println!("Peeked {} bytes from {}", peeked, peer);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn peek_from<'socket, 'buf>(
+        &'socket mut self,
+        buf: &'buf mut [u8],
+    ) -> PeekFromFuture<'socket, 'buf> {
+        PeekFromFuture { buf, socket: self }
+    }
+
+    /// Connects the socket to a remote address.
+    ///
+    /// Once connected, data can be sent to the remote address using [`send`] and received from it
+    /// using [`recv`]. Datagrams from any other address are discarded by the OS.
+    ///
+    /// If `addr` yields multiple addresses, connecting is attempted with each of them in order
+    /// until one succeeds.
+    ///
+    /// [`send`]: #method.send
+    /// [`recv`]: #method.recv
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let socket = UdpSocket::bind("127.0.0.1:0")?;
+    /// socket.connect("127.0.0.1:8080")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<()> {
+        let mut last_err = None;
+        for addr in addr.to_socket_addrs()? {
+            match self.inner.connect(&addr) {
+                Ok(()) => return Ok(()),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "could not resolve to any addresses",
+            )
+        }))
+    }
+
+    /// Sends data on the socket to the remote address it's [connected] to.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// [connected]: #method.connect
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # async fn send_data() -> std::io::Result<()> {
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0")?;
+    /// socket.connect("127.0.0.1:7878")?;
+    ///
+    /// let sent = socket.send(b"hello").await?;
+    /// println!("Sent {} bytes", sent);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send<'socket, 'buf>(&'socket mut self, buf: &'buf [u8]) -> SendFuture<'socket, 'buf> {
+        SendFuture { buf, socket: self }
+    }
+
+    /// Receives data on the socket from the remote address it's [connected] to.
+    ///
+    /// On success, returns the number of bytes read.
+    ///
+    /// [connected]: #method.connect
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # async fn recv_data() -> std::io::Result<Vec<u8>> {
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0")?;
+    /// socket.connect("127.0.0.1:7878")?;
+    ///
+    /// let mut buf = vec![0; 1024];
+    /// let recv = socket.recv(&mut buf).await?;
+    /// println!("Received {} bytes", recv);
+    /// # Ok(buf)
+    /// # }
+    /// ```
+    pub fn recv<'socket, 'buf>(
+        &'socket mut self,
+        buf: &'buf mut [u8],
+    ) -> RecvFuture<'socket, 'buf> {
+        RecvFuture { buf, socket: self }
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option for this socket.
+    ///
+    /// For more information about this option, see [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: #method.set_recv_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option for this socket.
+    ///
+    /// Changes the size of the kernel buffer that received datagrams are queued in until they're
+    /// read. Datagrams that arrive while the buffer is full are dropped.
+    ///
+    /// # Note
+    ///
+    /// The OS may round or double the requested size, so the value returned by
+    /// [`recv_buffer_size`] can differ from the one that was set.
+    ///
+    /// [`recv_buffer_size`]: #method.recv_buffer_size
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option for this socket.
+    ///
+    /// For more information about this option, see [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: #method.set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option for this socket.
+    ///
+    /// Changes the size of the kernel buffer that outgoing datagrams are queued in until they're
+    /// sent.
+    ///
+    /// # Note
+    ///
+    /// The OS may round or double the requested size, so the value returned by
+    /// [`send_buffer_size`] can differ from the one that was set.
+    ///
+    /// [`send_buffer_size`]: #method.send_buffer_size
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_send_buffer_size(size)
+    }
+
     /// Gets the value of the `SO_BROADCAST` option for this socket.
     ///
     /// For more information about this option, see [`set_broadcast`].
@@ -258,6 +672,48 @@ impl UdpSocket {
         self.inner.set_multicast_loop_v6(on)
     }
 
+    /// Gets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// For more information about this option, see [`set_multicast_if_v4`].
+    ///
+    /// [`set_multicast_if_v4`]: #method.set_multicast_if_v4
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        self.inner.multicast_if_v4()
+    }
+
+    /// Sets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// Specifies the address of the local interface outgoing multicast packets are sent from. The
+    /// default value of `0.0.0.0` lets the OS choose the interface.
+    ///
+    /// # Note
+    ///
+    /// This may not have any affect on IPv6 sockets.
+    pub fn set_multicast_if_v4(&self, interface: &Ipv4Addr) -> io::Result<()> {
+        self.inner.set_multicast_if_v4(interface)
+    }
+
+    /// Gets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    ///
+    /// For more information about this option, see [`set_multicast_if_v6`].
+    ///
+    /// [`set_multicast_if_v6`]: #method.set_multicast_if_v6
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        self.inner.multicast_if_v6()
+    }
+
+    /// Sets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    ///
+    /// Specifies the index of the local interface outgoing multicast packets are sent from. The
+    /// default value of 0 lets the OS choose the interface.
+    ///
+    /// # Note
+    ///
+    /// This may not have any affect on IPv4 sockets.
+    pub fn set_multicast_if_v6(&self, interface: u32) -> io::Result<()> {
+        self.inner.set_multicast_if_v6(interface)
+    }
+
     /// Gets the value of the `IP_TTL` option for this socket.
     ///
     /// For more information about this option, see [`set_ttl`].
@@ -275,6 +731,24 @@ impl UdpSocket {
         self.inner.set_ttl(ttl)
     }
 
+    /// Gets the value of the `IP_TOS` option for this socket.
+    ///
+    /// For more information about this option, see [`set_tos`].
+    ///
+    /// [`set_tos`]: #method.set_tos
+    pub fn tos(&self) -> io::Result<u32> {
+        self.inner.tos()
+    }
+
+    /// Sets the value for the `IP_TOS` option on this socket.
+    ///
+    /// This value sets the type-of-service field that is used in every packet sent from this
+    /// socket, such as a DSCP class for traffic prioritization. Only IPv4 sockets support this
+    /// option.
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        self.inner.set_tos(tos)
+    }
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     ///
     /// This function specifies a new multicast group for this socket to join.  The address must be
@@ -343,6 +817,101 @@ impl UdpSocket {
     pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
         self.inner.leave_multicast_v6(multiaddr, interface)
     }
+
+    /// Splits the socket into a send half and a receive half.
+    ///
+    /// The halves share the socket, and can be moved into separate tasks so that datagrams are
+    /// sent and received concurrently, without wrapping the socket in a `Mutex`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::net::UdpSocket;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let socket = UdpSocket::bind("127.0.0.1:0")?;
+    /// let (mut send, mut recv) = socket.split();
+    ///
+    /// runtime::spawn(async move {
+    ///     let mut buf = vec![0u8; 1024];
+    ///     while let Ok((len, peer)) = recv.recv_from(&mut buf).await {
+    ///         println!("received {} bytes from {}", len, peer);
+    ///     }
+    /// });
+    ///
+    /// send.send_to(b"hello world", "127.0.0.1:8080").await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn split(self) -> (UdpSendHalf, UdpRecvHalf) {
+        let inner = Arc::new(Mutex::new(self.inner));
+        let send = UdpSendHalf {
+            inner: inner.clone(),
+        };
+        let recv = UdpRecvHalf { inner };
+        (send, recv)
+    }
+}
+
+/// The send half of a [`UdpSocket`], created by [`UdpSocket::split`].
+///
+/// [`UdpSocket`]: struct.UdpSocket.html
+/// [`UdpSocket::split`]: struct.UdpSocket.html#method.split
+#[derive(Debug)]
+pub struct UdpSendHalf {
+    inner: Arc<Mutex<Pin<Box<dyn runtime_raw::UdpSocket>>>>,
+}
+
+impl UdpSendHalf {
+    /// Returns the local address that this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.lock().unwrap().local_addr()
+    }
+
+    /// Sends data on the socket to the given address.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to<'half, 'buf, A: ToSocketAddrs>(
+        &'half mut self,
+        buf: &'buf [u8],
+        addr: A,
+    ) -> SplitSendToFuture<'half, 'buf> {
+        let addr = addr
+            .to_socket_addrs()
+            .map(|mut iter| iter.next())
+            .transpose();
+        SplitSendToFuture {
+            buf,
+            addr,
+            half: self,
+        }
+    }
+}
+
+/// The receive half of a [`UdpSocket`], created by [`UdpSocket::split`].
+///
+/// [`UdpSocket`]: struct.UdpSocket.html
+/// [`UdpSocket::split`]: struct.UdpSocket.html#method.split
+#[derive(Debug)]
+pub struct UdpRecvHalf {
+    inner: Arc<Mutex<Pin<Box<dyn runtime_raw::UdpSocket>>>>,
+}
+
+impl UdpRecvHalf {
+    /// Returns the local address that this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.lock().unwrap().local_addr()
+    }
+
+    /// Receives data from the socket.
+    ///
+    /// On success, returns the number of bytes read and the origin.
+    pub fn recv_from<'half, 'buf>(
+        &'half mut self,
+        buf: &'buf mut [u8],
+    ) -> SplitRecvFromFuture<'half, 'buf> {
+        SplitRecvFromFuture { buf, half: self }
+    }
 }
 
 /// The future returned by [`UdpSocket::send_to`].
@@ -401,6 +970,222 @@ impl<'socket, 'buf> Future for RecvFromFuture<'socket, 'buf> {
     }
 }
 
+/// The future returned by [`UdpSendHalf::send_to`].
+///
+/// On success, returns the number of bytes written.
+///
+/// [`UdpSendHalf::send_to`]: struct.UdpSendHalf.html#method.send_to
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct SplitSendToFuture<'half, 'buf> {
+    half: &'half mut UdpSendHalf,
+    buf: &'buf [u8],
+    addr: Option<io::Result<SocketAddr>>,
+}
+
+impl<'half, 'buf> Future for SplitSendToFuture<'half, 'buf> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let SplitSendToFuture { half, buf, addr } = &mut *self;
+        let addr = match addr.take() {
+            Some(addr) => addr?,
+            None => {
+                let err_msg = "no addresses to send data to";
+                let err = io::Error::new(io::ErrorKind::InvalidInput, err_msg);
+                return Poll::Ready(Err(err));
+            }
+        };
+        let poll = half
+            .inner
+            .lock()
+            .unwrap()
+            .as_mut()
+            .poll_send_to(cx, buf, &addr);
+        self.addr = Some(Ok(addr));
+        poll
+    }
+}
+
+/// The future returned by [`UdpRecvHalf::recv_from`].
+///
+/// On success, returns the number of bytes read and the origin.
+///
+/// [`UdpRecvHalf::recv_from`]: struct.UdpRecvHalf.html#method.recv_from
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct SplitRecvFromFuture<'half, 'buf> {
+    half: &'half mut UdpRecvHalf,
+    buf: &'buf mut [u8],
+}
+
+impl<'half, 'buf> Future for SplitRecvFromFuture<'half, 'buf> {
+    type Output = io::Result<(usize, SocketAddr)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let SplitRecvFromFuture { half, buf } = &mut *self;
+        half.inner.lock().unwrap().as_mut().poll_recv_from(cx, buf)
+    }
+}
+
+/// The future returned by [`UdpSocket::send_to_vectored`].
+///
+/// On success, returns the number of bytes written.
+///
+/// [`UdpSocket::send_to_vectored`]: struct.UdpSocket.html#method.send_to_vectored
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct SendToVectoredFuture<'socket, 'bufs> {
+    socket: &'socket mut UdpSocket,
+    bufs: &'bufs [IoSlice<'bufs>],
+    addr: Option<io::Result<SocketAddr>>,
+}
+
+impl<'socket, 'bufs> Future for SendToVectoredFuture<'socket, 'bufs> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let SendToVectoredFuture { socket, bufs, addr } = &mut *self;
+        let addr = match addr.take() {
+            Some(addr) => addr?,
+            None => {
+                let err_msg = "no addresses to send data to";
+                let err = io::Error::new(io::ErrorKind::InvalidInput, err_msg);
+                return Poll::Ready(Err(err));
+            }
+        };
+        let poll = socket.inner.as_mut().poll_send_to_vectored(cx, bufs, &addr);
+        self.addr = Some(Ok(addr));
+        poll
+    }
+}
+
+/// The future returned by [`UdpSocket::recv_from_vectored`].
+///
+/// On success, returns the number of bytes read and the origin.
+///
+/// [`UdpSocket::recv_from_vectored`]: struct.UdpSocket.html#method.recv_from_vectored
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct RecvFromVectoredFuture<'socket, 'bufs, 'buf> {
+    socket: &'socket mut UdpSocket,
+    bufs: &'bufs mut [IoSliceMut<'buf>],
+}
+
+impl<'socket, 'bufs, 'buf> Future for RecvFromVectoredFuture<'socket, 'bufs, 'buf> {
+    type Output = io::Result<(usize, SocketAddr)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvFromVectoredFuture { socket, bufs } = &mut *self;
+        socket.inner.as_mut().poll_recv_from_vectored(cx, bufs)
+    }
+}
+
+/// The future returned by [`UdpSocket::send_batch`].
+///
+/// On success, returns the number of datagrams sent.
+///
+/// [`UdpSocket::send_batch`]: struct.UdpSocket.html#method.send_batch
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct SendBatchFuture<'socket, 'packets> {
+    socket: &'socket mut UdpSocket,
+    packets: &'packets [(&'packets [u8], SocketAddr)],
+}
+
+impl<'socket, 'packets> Future for SendBatchFuture<'socket, 'packets> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let SendBatchFuture { socket, packets } = &mut *self;
+        socket.inner.as_mut().poll_send_batch(cx, packets)
+    }
+}
+
+/// The future returned by [`UdpSocket::recv_batch`].
+///
+/// On success, returns the number of bytes read and the origin of each datagram received.
+///
+/// [`UdpSocket::recv_batch`]: struct.UdpSocket.html#method.recv_batch
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct RecvBatchFuture<'socket, 'bufs, 'buf> {
+    socket: &'socket mut UdpSocket,
+    bufs: &'bufs mut [&'buf mut [u8]],
+}
+
+impl<'socket, 'bufs, 'buf> Future for RecvBatchFuture<'socket, 'bufs, 'buf> {
+    type Output = io::Result<Vec<(usize, SocketAddr)>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvBatchFuture { socket, bufs } = &mut *self;
+        socket.inner.as_mut().poll_recv_batch(cx, bufs)
+    }
+}
+
+/// The future returned by [`UdpSocket::peek_from`].
+///
+/// On success, returns the number of bytes read and the origin.
+///
+/// [`UdpSocket::peek_from`]: struct.UdpSocket.html#method.peek_from
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct PeekFromFuture<'socket, 'buf> {
+    socket: &'socket mut UdpSocket,
+    buf: &'buf mut [u8],
+}
+
+impl<'socket, 'buf> Future for PeekFromFuture<'socket, 'buf> {
+    type Output = io::Result<(usize, SocketAddr)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let PeekFromFuture { socket, buf } = &mut *self;
+        socket.inner.as_mut().poll_peek_from(cx, buf)
+    }
+}
+
+/// The future returned by [`UdpSocket::send`].
+///
+/// On success, returns the number of bytes written.
+///
+/// [`UdpSocket::send`]: struct.UdpSocket.html#method.send
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct SendFuture<'socket, 'buf> {
+    socket: &'socket mut UdpSocket,
+    buf: &'buf [u8],
+}
+
+impl<'socket, 'buf> Future for SendFuture<'socket, 'buf> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let SendFuture { socket, buf } = &mut *self;
+        socket.inner.as_mut().poll_send(cx, buf)
+    }
+}
+
+/// The future returned by [`UdpSocket::recv`].
+///
+/// On success, returns the number of bytes read.
+///
+/// [`UdpSocket::recv`]: struct.UdpSocket.html#method.recv
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct RecvFuture<'socket, 'buf> {
+    socket: &'socket mut UdpSocket,
+    buf: &'buf mut [u8],
+}
+
+impl<'socket, 'buf> Future for RecvFuture<'socket, 'buf> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvFuture { socket, buf } = &mut *self;
+        socket.inner.as_mut().poll_recv(cx, buf)
+    }
+}
+
 #[cfg(unix)]
 mod sys {
     use super::UdpSocket;
@@ -0,0 +1,4 @@
+//! OS-specific functionality.
+
+#[cfg(unix)]
+pub mod unix;
@@ -0,0 +1,3 @@
+//! Unix-specific extensions.
+
+pub mod net;
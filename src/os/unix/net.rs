@@ -0,0 +1,19 @@
+//! Networking primitives for asynchronous communication over Unix domain sockets.
+//!
+//! # Organization
+//!
+//! * [`UnixListener`] and [`UnixStream`] provide functionality for stream-oriented communication
+//! * [`UnixDatagram`] provides functionality for datagram-oriented communication
+//!
+//! [`UnixListener`]: struct.UnixListener.html
+//! [`UnixStream`]: struct.UnixStream.html
+//! [`UnixDatagram`]: struct.UnixDatagram.html
+
+pub mod datagram;
+pub mod stream;
+
+#[doc(inline)]
+pub use datagram::UnixDatagram;
+
+#[doc(inline)]
+pub use stream::{UnixListener, UnixStream};
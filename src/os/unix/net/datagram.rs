@@ -0,0 +1,408 @@
+//! Asynchronous Unix domain datagram sockets.
+//!
+//! To create a Unix datagram socket use [`UnixDatagram::bind`], or [`UnixDatagram::pair`] to
+//! create an unnamed pair of sockets connected to each other. Sending data from a socket is done by
+//! using [`send_to`], or [`send`] once the socket is [`connect`]ed. Reading data from a socket is done by using [`recv_from`] or
+//! [`recv`].
+//!
+//! [`UnixDatagram::bind`]: struct.UnixDatagram.html#method.bind
+//! [`UnixDatagram::pair`]: struct.UnixDatagram.html#method.pair
+//! [`send_to`]: struct.UnixDatagram.html#method.send_to
+//! [`connect`]: struct.UnixDatagram.html#method.connect
+//! [`send`]: struct.UnixDatagram.html#method.send
+//! [`recv_from`]: struct.UnixDatagram.html#method.recv_from
+//! [`recv`]: struct.UnixDatagram.html#method.recv
+
+use futures::prelude::*;
+
+use std::io;
+use std::os::unix::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A Unix domain datagram socket.
+///
+/// After creating a `UnixDatagram` by [`bind`]ing it to a path, data can be [sent to] and
+/// [received from] any other socket path.
+///
+/// [`bind`]: #method.bind
+/// [received from]: #method.recv_from
+/// [sent to]: #method.send_to
+///
+/// ## Examples
+/// ```no_run
+/// use runtime::os::unix::net::UnixDatagram;
+///
+/// #[runtime::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut socket = UnixDatagram::bind("/tmp/sock")?;
+///     let mut buf = vec![0u8; 1024];
+///
+///     loop {
+///         let (recv, peer) = socket.recv_from(&mut buf).await?;
+///         if let Some(path) = peer.as_pathname() {
+///             let sent = socket.send_to(&buf[..recv], path).await?;
+///             println!("Sent {} out of {} bytes to {:?}", sent, recv, path);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct UnixDatagram {
+    inner: Pin<Box<dyn runtime_raw::UnixDatagram>>,
+}
+
+impl UnixDatagram {
+    /// Creates a Unix datagram socket bound to the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let socket = UnixDatagram::bind("/tmp/sock")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let inner = crate::net::current_runtime()?.bind_unix_datagram(path.as_ref())?;
+        Ok(UnixDatagram { inner })
+    }
+
+    /// Creates an unnamed pair of connected sockets.
+    ///
+    /// Data sent on one socket with [`send`] can be received on the other with [`recv`], without
+    /// needing a path on the file system.
+    ///
+    /// [`send`]: #method.send
+    /// [`recv`]: #method.recv
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let (a, b) = UnixDatagram::pair()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (a, b) = crate::net::current_runtime()?.unix_datagram_pair()?;
+        Ok((UnixDatagram { inner: a }, UnixDatagram { inner: b }))
+    }
+
+    /// Creates a new `UnixDatagram` from a standard library socket.
+    ///
+    /// This is useful when the socket was created elsewhere, for example when it was inherited
+    /// from a parent process through socket activation. The socket is put into non-blocking mode
+    /// and registered with the current runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let std_socket = std::os::unix::net::UnixDatagram::unbound()?;
+    /// let socket = UnixDatagram::from_std(std_socket)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_std(socket: std::os::unix::net::UnixDatagram) -> io::Result<Self> {
+        let inner = crate::net::current_runtime()?.unix_datagram_from_std(socket)?;
+        Ok(UnixDatagram { inner })
+    }
+
+    /// Returns the address of this socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let socket = UnixDatagram::bind("/tmp/sock")?;
+    /// println!("Address: {:?}", socket.local_addr()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Returns the address of this socket's peer.
+    ///
+    /// The peer is set by [`connect`], or is the other socket of a [`pair`].
+    ///
+    /// [`connect`]: #method.connect
+    /// [`pair`]: #method.pair
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let socket = UnixDatagram::bind("/tmp/sock")?;
+    /// socket.connect("/tmp/other")?;
+    /// println!("Peer: {:?}", socket.peer_addr()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Connects the socket to the socket at the given path.
+    ///
+    /// Once connected, data can be sent to the peer using [`send`] and received from it using
+    /// [`recv`].
+    ///
+    /// [`send`]: #method.send
+    /// [`recv`]: #method.recv
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let socket = UnixDatagram::bind("/tmp/sock")?;
+    /// socket.connect("/tmp/other")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.connect(path.as_ref())
+    }
+
+    /// Shuts down the read, write, or both halves of this socket.
+    ///
+    /// This function will cause all pending and future I/O on the specified portions to return
+    /// immediately with an appropriate value (see the documentation of [`Shutdown`]).
+    ///
+    /// [`Shutdown`]: https://doc.rust-lang.org/std/net/enum.Shutdown.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::net::Shutdown;
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let socket = UnixDatagram::bind("/tmp/sock")?;
+    /// socket.shutdown(Shutdown::Both)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+
+    /// Sends data on the socket to the socket at the given path.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # async fn send_data() -> std::io::Result<()> {
+    /// let mut socket = UnixDatagram::bind("/tmp/sock")?;
+    ///
+    /// let sent = socket.send_to(b"hello", "/tmp/other").await?;
+    /// println!("Sent {} bytes", sent);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_to<'socket, 'buf, P: AsRef<Path>>(
+        &'socket mut self,
+        buf: &'buf [u8],
+        path: P,
+    ) -> SendToFuture<'socket, 'buf> {
+        SendToFuture {
+            buf,
+            path: path.as_ref().to_path_buf(),
+            socket: self,
+        }
+    }
+
+    /// Receives data from the socket.
+    ///
+    /// On success, returns the number of bytes read and the origin.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # async fn recv_data() -> std::io::Result<Vec<u8>> {
+    /// let mut socket = UnixDatagram::bind("/tmp/sock")?;
+    ///
+    /// let mut buf = vec![0; 1024];
+    /// let (recv, peer) = socket.recv_from(&mut buf).await?;
+    /// println!("Received {} bytes from {:?}", recv, peer);
+    /// # Ok(buf)
+    /// # }
+    /// ```
+    pub fn recv_from<'socket, 'buf>(
+        &'socket mut self,
+        buf: &'buf mut [u8],
+    ) -> RecvFromFuture<'socket, 'buf> {
+        RecvFromFuture { buf, socket: self }
+    }
+
+    /// Sends data on the socket to the socket's peer.
+    ///
+    /// The socket must be [connected] or be part of a [`pair`]. On success, returns the number of
+    /// bytes written.
+    ///
+    /// [connected]: #method.connect
+    /// [`pair`]: #method.pair
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # async fn send_data() -> std::io::Result<()> {
+    /// let (mut a, _b) = UnixDatagram::pair()?;
+    ///
+    /// let sent = a.send(b"hello").await?;
+    /// println!("Sent {} bytes", sent);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send<'socket, 'buf>(&'socket mut self, buf: &'buf [u8]) -> SendFuture<'socket, 'buf> {
+        SendFuture { buf, socket: self }
+    }
+
+    /// Receives data from the socket's peer.
+    ///
+    /// The socket must be [connected] or be part of a [`pair`]. On success, returns the number of
+    /// bytes read.
+    ///
+    /// [connected]: #method.connect
+    /// [`pair`]: #method.pair
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixDatagram;
+    ///
+    /// # async fn recv_data() -> std::io::Result<Vec<u8>> {
+    /// let (_a, mut b) = UnixDatagram::pair()?;
+    ///
+    /// let mut buf = vec![0; 1024];
+    /// let recv = b.recv(&mut buf).await?;
+    /// println!("Received {} bytes", recv);
+    /// # Ok(buf)
+    /// # }
+    /// ```
+    pub fn recv<'socket, 'buf>(
+        &'socket mut self,
+        buf: &'buf mut [u8],
+    ) -> RecvFuture<'socket, 'buf> {
+        RecvFuture { buf, socket: self }
+    }
+}
+
+/// The future returned by [`UnixDatagram::send_to`].
+///
+/// On success, returns the number of bytes written.
+///
+/// [`UnixDatagram::send_to`]: struct.UnixDatagram.html#method.send_to
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct SendToFuture<'socket, 'buf> {
+    socket: &'socket mut UnixDatagram,
+    buf: &'buf [u8],
+    path: PathBuf,
+}
+
+impl<'socket, 'buf> Future for SendToFuture<'socket, 'buf> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let SendToFuture { socket, buf, path } = &mut *self;
+        socket.inner.as_mut().poll_send_to(cx, buf, path)
+    }
+}
+
+/// The future returned by [`UnixDatagram::recv_from`].
+///
+/// On success, returns the number of bytes read and the origin.
+///
+/// [`UnixDatagram::recv_from`]: struct.UnixDatagram.html#method.recv_from
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct RecvFromFuture<'socket, 'buf> {
+    socket: &'socket mut UnixDatagram,
+    buf: &'buf mut [u8],
+}
+
+impl<'socket, 'buf> Future for RecvFromFuture<'socket, 'buf> {
+    type Output = io::Result<(usize, SocketAddr)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvFromFuture { socket, buf } = &mut *self;
+        socket.inner.as_mut().poll_recv_from(cx, buf)
+    }
+}
+
+/// The future returned by [`UnixDatagram::send`].
+///
+/// On success, returns the number of bytes written.
+///
+/// [`UnixDatagram::send`]: struct.UnixDatagram.html#method.send
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct SendFuture<'socket, 'buf> {
+    socket: &'socket mut UnixDatagram,
+    buf: &'buf [u8],
+}
+
+impl<'socket, 'buf> Future for SendFuture<'socket, 'buf> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let SendFuture { socket, buf } = &mut *self;
+        socket.inner.as_mut().poll_send(cx, buf)
+    }
+}
+
+/// The future returned by [`UnixDatagram::recv`].
+///
+/// On success, returns the number of bytes read.
+///
+/// [`UnixDatagram::recv`]: struct.UnixDatagram.html#method.recv
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct RecvFuture<'socket, 'buf> {
+    socket: &'socket mut UnixDatagram,
+    buf: &'buf mut [u8],
+}
+
+impl<'socket, 'buf> Future for RecvFuture<'socket, 'buf> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvFuture { socket, buf } = &mut *self;
+        socket.inner.as_mut().poll_recv(cx, buf)
+    }
+}
+
+mod sys {
+    use super::UnixDatagram;
+    use std::os::unix::prelude::*;
+
+    impl AsRawFd for UnixDatagram {
+        fn as_raw_fd(&self) -> RawFd {
+            self.inner.as_raw_fd()
+        }
+    }
+}
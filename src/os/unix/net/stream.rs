@@ -0,0 +1,403 @@
+//! Asynchronous Unix domain stream sockets.
+//!
+//! Connecting to a socket is done by using [`UnixStream::connect`]. This returns a [`ConnectFuture`]
+//! which resolves to a [`UnixStream`].
+//!
+//! To listen for incoming connections use [`UnixListener::bind`], which creates a new
+//! [`UnixListener`]. Then use the [`incoming`] method to accept new connections, yielding a stream
+//! of [`UnixStream`]s.
+//!
+//! [`UnixStream`]: struct.UnixStream.html
+//! [`UnixStream::connect`]: struct.UnixStream.html#method.connect
+//! [`ConnectFuture`]: struct.ConnectFuture.html
+//! [`UnixListener`]: struct.UnixListener.html
+//! [`UnixListener::bind`]: struct.UnixListener.html#method.bind
+//! [`incoming`]: struct.UnixListener.html#method.incoming
+
+use std::fmt;
+use std::io;
+use std::os::unix::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+
+use futures::future::BoxFuture;
+use futures::io::*;
+use futures::prelude::*;
+use futures::ready;
+use futures::task::{Context, Poll};
+
+/// A Unix domain stream socket connected to a peer.
+///
+/// A `UnixStream` can either be created by connecting to a socket path, via the [`connect`]
+/// method, or by [accepting] a connection from a [listener]. It can be read or written to using
+/// the `AsyncRead`, `AsyncWrite`, and related extension traits in `futures::io`.
+///
+/// The connection will be closed when the value is dropped. The reading and writing portions of
+/// the connection can also be shut down individually with the [`shutdown`] method.
+///
+/// [`connect`]: struct.UnixStream.html#method.connect
+/// [accepting]: struct.UnixListener.html#method.accept
+/// [listener]: struct.UnixListener.html
+/// [`shutdown`]: struct.UnixStream.html#method.shutdown
+///
+/// ## Examples
+/// ```no_run
+/// use futures::prelude::*;
+/// use runtime::os::unix::net::UnixStream;
+///
+/// #[runtime::main]
+/// async fn main() -> Result<(), failure::Error> {
+///     let mut stream = UnixStream::connect("/tmp/sock").await?;
+///
+///     let msg = "hello world";
+///     println!("<- {}", msg);
+///     stream.write_all(msg.as_bytes()).await?;
+///
+///     let mut buf = vec![0u8; 1024];
+///     stream.read(&mut buf).await?;
+///     println!("-> {}\n", std::str::from_utf8(&mut buf)?);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct UnixStream {
+    inner: Pin<Box<dyn runtime_raw::UnixStream>>,
+}
+
+impl UnixStream {
+    /// Create a new Unix domain stream connected to the socket at `path`.
+    ///
+    /// The [returned future] will be resolved once the stream has successfully connected, or it
+    /// will return an error if one occurs.
+    ///
+    /// [returned future]: struct.ConnectFuture.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixStream;
+    ///
+    /// # async fn connect() -> std::io::Result<()> {
+    /// let stream = UnixStream::connect("/tmp/sock").await?;
+    /// # Ok(())}
+    /// ```
+    pub fn connect<P: AsRef<Path>>(path: P) -> ConnectFuture {
+        let future = match crate::net::current_runtime() {
+            Ok(runtime) => runtime.connect_unix_stream(path.as_ref()),
+            Err(err) => future::ready(Err(err)).boxed(),
+        };
+        ConnectFuture { future }
+    }
+
+    /// Returns the socket address of the local half of this connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixStream;
+    ///
+    /// # async fn connect() -> std::io::Result<()> {
+    /// let stream = UnixStream::connect("/tmp/sock").await?;
+    /// println!("{:?}", stream.local_addr()?);
+    /// # Ok(())}
+    /// ```
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Returns the socket address of the remote half of this connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixStream;
+    ///
+    /// # async fn connect() -> std::io::Result<()> {
+    /// let stream = UnixStream::connect("/tmp/sock").await?;
+    /// println!("{:?}", stream.peer_addr()?);
+    /// # Ok(())}
+    /// ```
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Shuts down the read, write, or both halves of this connection.
+    ///
+    /// This function will cause all pending and future I/O on the specified portions to return
+    /// immediately with an appropriate value (see the documentation of [`Shutdown`]).
+    ///
+    /// [`Shutdown`]: https://doc.rust-lang.org/std/net/enum.Shutdown.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::net::Shutdown;
+    /// use runtime::os::unix::net::UnixStream;
+    ///
+    /// # async fn connect() -> std::io::Result<()> {
+    /// let stream = UnixStream::connect("/tmp/sock").await?;
+    /// stream.shutdown(Shutdown::Both)?;
+    /// # Ok(())}
+    /// ```
+    pub fn shutdown(&self, how: std::net::Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+}
+
+impl AsyncRead for UnixStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.inner.as_mut().poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.inner.as_mut().poll_read_vectored(cx, bufs)
+    }
+}
+
+impl AsyncWrite for UnixStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.inner.as_mut().poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.as_mut().poll_close(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.inner.as_mut().poll_write_vectored(cx, bufs)
+    }
+}
+
+/// The future returned by [`UnixStream::connect`].
+///
+/// Resolves to a [`UnixStream`] when the stream is connected.
+///
+/// [`UnixStream::connect`]: struct.UnixStream.html#method.connect
+/// [`UnixStream`]: struct.UnixStream.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ConnectFuture {
+    future: BoxFuture<'static, io::Result<Pin<Box<dyn runtime_raw::UnixStream>>>>,
+}
+
+impl Future for ConnectFuture {
+    type Output = io::Result<UnixStream>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = ready!(self.future.as_mut().poll(cx)?);
+        Poll::Ready(Ok(UnixStream { inner }))
+    }
+}
+
+impl fmt::Debug for ConnectFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectFuture").finish()
+    }
+}
+
+/// A Unix domain socket server, listening for connections.
+///
+/// After creating a `UnixListener` by [`bind`]ing it to a path, it listens for incoming
+/// connections. These can be accepted by awaiting elements from the async stream of incoming
+/// connections, [`incoming`][`UnixListener::incoming`].
+///
+/// The socket will be closed when the value is dropped. The socket file isn't removed.
+///
+/// [`bind`]: #method.bind
+/// [`UnixListener::incoming`]: #method.incoming
+///
+/// # Examples
+/// ```ignore
+/// use futures::prelude::*;
+/// use runtime::os::unix::net::UnixListener;
+///
+/// #[runtime::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut listener = UnixListener::bind("/tmp/sock")?;
+///
+///     // accept connections and process them in parallel
+///     let mut incoming = listener.incoming();
+///     while let Some(stream) = incoming.next().await {
+///         runtime::spawn(async move {
+///             let stream = stream?;
+///             let (reader, writer) = &mut stream.split();
+///             reader.copy_into(writer).await?;
+///             Ok::<(), std::io::Error>(())
+///         });
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct UnixListener {
+    inner: Pin<Box<dyn runtime_raw::UnixListener>>,
+}
+
+impl UnixListener {
+    /// Creates a new `UnixListener` bound to the specified path.
+    ///
+    /// The returned listener is ready for accepting connections.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixListener;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = UnixListener::bind("/tmp/sock")?;
+    /// # Ok(())}
+    /// ```
+    pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let inner = crate::net::current_runtime()?.bind_unix_listener(path.as_ref())?;
+        Ok(UnixListener { inner })
+    }
+
+    /// Returns the local socket address of this listener.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixListener;
+    ///
+    /// # fn main () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let listener = UnixListener::bind("/tmp/sock")?;
+    /// println!("{:?}", listener.local_addr()?);
+    /// # Ok(())}
+    /// ```
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Handle all incoming connections.
+    ///
+    /// This method returns a stream of [`UnixStream`]s. This is useful when you want to handle
+    /// multiple incoming connections.
+    ///
+    /// If you intend to only handle single connections use [`.accept()`].
+    ///
+    /// [`UnixStream`]: struct.UnixStream.html
+    /// [`.accept()`]: struct.UnixListener.html#method.accept
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use futures::prelude::*;
+    /// use runtime::os::unix::net::UnixListener;
+    ///
+    /// # async fn work () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let mut listener = UnixListener::bind("/tmp/sock")?;
+    /// let mut incoming = listener.incoming();
+    /// while let Some(stream) = incoming.next().await {
+    ///     match stream {
+    ///         Ok(stream) => println!("new client!"),
+    ///         Err(e) => { /* connection failed */ }
+    ///     }
+    /// }
+    /// # Ok(())}
+    /// ```
+    pub fn incoming(&mut self) -> IncomingStream<'_> {
+        IncomingStream { inner: self }
+    }
+
+    /// Handle an incoming connection.
+    ///
+    /// If you intend to handle all incoming connections use [`.incoming()`].
+    ///
+    /// [`.incoming()`]: struct.UnixListener.html#method.incoming
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use runtime::os::unix::net::UnixListener;
+    ///
+    /// # async fn work () -> Result<(), Box<dyn std::error::Error + 'static>> {
+    /// let mut listener = UnixListener::bind("/tmp/sock")?;
+    /// let (stream, addr) = listener.accept().await?;
+    /// println!("Connected to {:?}", addr);
+    /// # Ok(())}
+    /// ```
+    pub fn accept(&mut self) -> AcceptFuture<'_> {
+        AcceptFuture { inner: self }
+    }
+}
+
+/// The future returned by [`UnixListener::accept`].
+///
+/// Resolves to a [`UnixStream`] and the address of its peer.
+///
+/// [`UnixListener::accept`]: struct.UnixListener.html#method.accept
+/// [`UnixStream`]: struct.UnixStream.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct AcceptFuture<'listener> {
+    inner: &'listener mut UnixListener,
+}
+
+impl<'listener> Future for AcceptFuture<'listener> {
+    type Output = io::Result<(UnixStream, SocketAddr)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let (inner, addr) = ready!(self.inner.inner.as_mut().poll_accept(cx)?);
+        Poll::Ready(Ok((UnixStream { inner }, addr)))
+    }
+}
+
+/// A stream that infinitely [`accept`]s connections on a [`UnixListener`].
+///
+/// This `struct` is created by the [`incoming`] method on [`UnixListener`].
+/// See its documentation for more.
+///
+/// [`incoming`]: struct.UnixListener.html#method.incoming
+/// [`accept`]: struct.UnixListener.html#method.accept
+/// [`UnixListener`]: struct.UnixListener.html
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct IncomingStream<'listener> {
+    inner: &'listener mut UnixListener,
+}
+
+impl<'listener> Stream for IncomingStream<'listener> {
+    type Item = io::Result<UnixStream>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (inner, _) = ready!(self.inner.inner.as_mut().poll_accept(cx)?);
+        Poll::Ready(Some(Ok(UnixStream { inner })))
+    }
+}
+
+mod sys {
+    use super::{UnixListener, UnixStream};
+    use std::os::unix::prelude::*;
+
+    impl AsRawFd for UnixListener {
+        fn as_raw_fd(&self) -> RawFd {
+            self.inner.as_raw_fd()
+        }
+    }
+
+    impl AsRawFd for UnixStream {
+        fn as_raw_fd(&self) -> RawFd {
+            self.inner.as_raw_fd()
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Synchronization primitives for communicating between tasks.
+//!
+//! # Organization
+//!
+//! * [`mpsc`] provides a bounded multi-producer, single-consumer channel, whose senders wait for
+//!   capacity when the channel is full.
+//! * [`oneshot`] provides a channel for sending a single value, like the response to a request.
+//! * [`Barrier`] makes a group of tasks wait until all of them are ready to continue.
+//! * [`Mutex`] and [`RwLock`] share mutable data between tasks, which wait for the lock without
+//!   blocking their thread.
+//!
+//! [`mpsc`]: mpsc/index.html
+//! [`oneshot`]: oneshot/index.html
+//! [`Barrier`]: struct.Barrier.html
+//! [`Mutex`]: struct.Mutex.html
+//! [`RwLock`]: struct.RwLock.html
+
+mod barrier;
+mod lock;
+mod mutex;
+#[allow(unsafe_code)]
+mod rwlock;
+
+pub mod mpsc;
+pub mod oneshot;
+
+pub use barrier::{Barrier, BarrierWaitFuture, BarrierWaitResult};
+pub use mutex::{Mutex, MutexGuard, MutexLockFuture};
+pub use rwlock::{RwLock, RwLockReadFuture, RwLockReadGuard, RwLockWriteFuture, RwLockWriteGuard};
@@ -0,0 +1,172 @@
+use futures::prelude::*;
+
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Makes a group of tasks wait until all of them have reached the same point.
+///
+/// A barrier is created for a number of tasks. Each task calls [`wait`], and the returned futures
+/// complete together once that many tasks are waiting. Exactly one of them is told that it's the
+/// leader, which is useful for doing something once per group, like logging. The barrier can then
+/// be used again by the next group of tasks.
+///
+/// Cloning a `Barrier` returns a new handle to the same barrier.
+///
+/// [`wait`]: #method.wait
+///
+/// # Examples
+///
+/// ```
+/// use runtime::sync::Barrier;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let barrier = Barrier::new(3);
+///
+///     let handles = (0..3)
+///         .map(|i| {
+///             let barrier = barrier.clone();
+///             runtime::spawn(async move {
+///                 println!("worker {} is ready", i);
+///                 barrier.wait().await;
+///                 println!("worker {} is running", i);
+///             })
+///         })
+///         .collect::<Vec<_>>();
+///
+///     for handle in handles {
+///         handle.await.unwrap();
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Barrier {
+    inner: Arc<Mutex<State>>,
+}
+
+struct State {
+    /// The number of tasks that have to wait before they're all released.
+    n: usize,
+    /// The number of tasks waiting in the current generation.
+    arrived: usize,
+    /// Incremented every time a group of tasks is released.
+    generation: u64,
+    wakers: Vec<Waker>,
+}
+
+impl Barrier {
+    /// Create a barrier that releases tasks in groups of `n`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `n` is 0.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "a barrier must be created for at least one task");
+        let state = State {
+            n,
+            arrived: 0,
+            generation: 0,
+            wakers: Vec::new(),
+        };
+        Self {
+            inner: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Wait until `n` tasks are waiting on the barrier, including this one.
+    ///
+    /// The task is counted as waiting from the first time the returned future is polled. If the
+    /// future is dropped before the group is complete, the task stops being counted.
+    pub fn wait(&self) -> BarrierWaitFuture {
+        BarrierWaitFuture {
+            barrier: self.clone(),
+            generation: None,
+        }
+    }
+}
+
+impl fmt::Debug for Barrier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.lock().unwrap();
+        f.debug_struct("Barrier")
+            .field("n", &state.n)
+            .field("arrived", &state.arrived)
+            .finish()
+    }
+}
+
+/// The result of waiting on a [`Barrier`].
+///
+/// [`Barrier`]: struct.Barrier.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+    leader: bool,
+}
+
+impl BarrierWaitResult {
+    /// Returns `true` for exactly one of the tasks released together: the last one to arrive.
+    pub fn is_leader(&self) -> bool {
+        self.leader
+    }
+}
+
+/// The future returned by [`Barrier::wait`].
+///
+/// [`Barrier::wait`]: struct.Barrier.html#method.wait
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct BarrierWaitFuture {
+    barrier: Barrier,
+    /// The generation this task is waiting in, once it has arrived.
+    generation: Option<u64>,
+}
+
+impl Future for BarrierWaitFuture {
+    type Output = BarrierWaitResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.barrier.inner.lock().unwrap();
+        match this.generation {
+            Some(generation) if generation != state.generation => {
+                this.generation = None;
+                return Poll::Ready(BarrierWaitResult { leader: false });
+            }
+            Some(_) => {}
+            None => {
+                state.arrived += 1;
+                if state.arrived == state.n {
+                    state.arrived = 0;
+                    state.generation += 1;
+                    let wakers = std::mem::take(&mut state.wakers);
+                    drop(state);
+                    for waker in wakers {
+                        waker.wake();
+                    }
+                    return Poll::Ready(BarrierWaitResult { leader: true });
+                }
+                this.generation = Some(state.generation);
+            }
+        }
+
+        if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for BarrierWaitFuture {
+    fn drop(&mut self) {
+        // A task that gives up waiting before its group is complete no longer counts towards it.
+        if let Some(generation) = self.generation {
+            if let Ok(mut state) = self.barrier.inner.lock() {
+                if state.generation == generation {
+                    state.arrived -= 1;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use futures::prelude::*;
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// A lock without data, which hands out shared and exclusive access in the order it's requested.
+///
+/// Access is only granted right away if nobody is queued, so a task waiting for exclusive access
+/// isn't starved by a stream of tasks asking for shared access. When the lock is released, the
+/// waiters at the front of the queue are granted access: the first one if it wants exclusive
+/// access, or else every waiter up to the next one that wants exclusive access.
+#[derive(Debug, Default)]
+pub(super) struct RawLock {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    readers: usize,
+    writer: bool,
+    queue: VecDeque<Arc<Waiter>>,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    exclusive: bool,
+    granted: AtomicBool,
+    waker: Mutex<Waker>,
+}
+
+impl State {
+    fn can_grant(&self, exclusive: bool) -> bool {
+        !self.writer && (!exclusive || self.readers == 0)
+    }
+
+    fn grant(&mut self, exclusive: bool) {
+        if exclusive {
+            self.writer = true;
+        } else {
+            self.readers += 1;
+        }
+    }
+
+    fn release(&mut self, exclusive: bool) {
+        if exclusive {
+            self.writer = false;
+        } else {
+            self.readers -= 1;
+        }
+    }
+
+    /// Grant access to the waiters at the front of the queue that can have it, returning them so
+    /// they can be woken up once the lock is released.
+    fn grant_waiters(&mut self) -> Vec<Arc<Waiter>> {
+        let mut granted = Vec::new();
+        while let Some(waiter) = self.queue.front() {
+            if !self.can_grant(waiter.exclusive) {
+                break;
+            }
+            let waiter = self.queue.pop_front().unwrap();
+            self.grant(waiter.exclusive);
+            waiter.granted.store(true, Ordering::SeqCst);
+            granted.push(waiter);
+        }
+        granted
+    }
+}
+
+fn wake_all(waiters: Vec<Arc<Waiter>>) {
+    for waiter in waiters {
+        waiter.waker.lock().unwrap().wake_by_ref();
+    }
+}
+
+impl RawLock {
+    /// Acquire access if it can be granted right away, without waiting.
+    pub(super) fn try_acquire(&self, exclusive: bool) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.queue.is_empty() && state.can_grant(exclusive) {
+            state.grant(exclusive);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Wait until access is granted.
+    pub(super) fn acquire(&self, exclusive: bool) -> Acquire<'_> {
+        Acquire {
+            lock: self,
+            exclusive,
+            waiter: None,
+        }
+    }
+
+    /// Give back access that was granted, and grant it to the next waiters.
+    pub(super) fn release(&self, exclusive: bool) {
+        let granted = {
+            let mut state = self.state.lock().unwrap();
+            state.release(exclusive);
+            state.grant_waiters()
+        };
+        wake_all(granted);
+    }
+}
+
+/// The future returned by `RawLock::acquire`.
+#[derive(Debug)]
+pub(super) struct Acquire<'a> {
+    lock: &'a RawLock,
+    exclusive: bool,
+    waiter: Option<Arc<Waiter>>,
+}
+
+impl Future for Acquire<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let waiter = match &self.waiter {
+            Some(waiter) => waiter.clone(),
+            None => {
+                let mut state = self.lock.state.lock().unwrap();
+                if state.queue.is_empty() && state.can_grant(self.exclusive) {
+                    state.grant(self.exclusive);
+                    return Poll::Ready(());
+                }
+                let waiter = Arc::new(Waiter {
+                    exclusive: self.exclusive,
+                    granted: AtomicBool::new(false),
+                    waker: Mutex::new(cx.waker().clone()),
+                });
+                state.queue.push_back(waiter.clone());
+                drop(state);
+                self.waiter = Some(waiter);
+                return Poll::Pending;
+            }
+        };
+
+        {
+            let mut waker = waiter.waker.lock().unwrap();
+            if !waker.will_wake(cx.waker()) {
+                *waker = cx.waker().clone();
+            }
+        }
+        // The waker is updated before checking whether access was granted, so a grant that comes
+        // in between wakes up the new waker.
+        if !waiter.granted.load(Ordering::SeqCst) {
+            return Poll::Pending;
+        }
+        self.waiter = None;
+        Poll::Ready(())
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        let waiter = match self.waiter.take() {
+            Some(waiter) => waiter,
+            None => return,
+        };
+
+        // Access is granted while holding the lock, so this can't miss a grant.
+        let granted = {
+            let mut state = self.lock.state.lock().unwrap();
+            if waiter.granted.load(Ordering::SeqCst) {
+                // Access was granted to this future, but it will never be used.
+                state.release(waiter.exclusive);
+            } else {
+                state.queue.retain(|w| !Arc::ptr_eq(w, &waiter));
+            }
+            // Leaving the queue may unblock the waiters that were behind this one.
+            state.grant_waiters()
+        };
+        wake_all(granted);
+    }
+}
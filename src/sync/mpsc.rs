@@ -0,0 +1,368 @@
+//! A bounded multi-producer, single-consumer channel.
+//!
+//! [`channel`] creates a [`Sender`] and a [`Receiver`]. The channel holds at most as many values
+//! as its capacity: once it's full, [`Sender::send`] waits until the receiver has taken a value
+//! out, which applies backpressure to producers that are faster than the consumer. The
+//! [`Receiver`] is a stream of the values sent, which ends once every `Sender` has been dropped
+//! and the values left in the channel have been received.
+//!
+//! [`channel`]: fn.channel.html
+//! [`Sender`]: struct.Sender.html
+//! [`Sender::send`]: struct.Sender.html#method.send
+//! [`Receiver`]: struct.Receiver.html
+//!
+//! ## Examples
+//! ```
+//! use futures::prelude::*;
+//! use runtime::sync::mpsc;
+//!
+//! #[runtime::main]
+//! async fn main() {
+//!     let (tx, mut rx) = mpsc::channel(16);
+//!
+//!     runtime::spawn(async move {
+//!         for i in 0..10 {
+//!             tx.send(i).await.unwrap();
+//!         }
+//!     });
+//!
+//!     let mut sum = 0;
+//!     while let Some(i) = rx.next().await {
+//!         sum += i;
+//!     }
+//!     assert_eq!(sum, 45);
+//! }
+//! ```
+
+use futures::prelude::*;
+use futures::task::{Context, Poll, Waker};
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Create a bounded channel that holds up to `cap` values.
+///
+/// # Panics
+///
+/// This function panics if `cap` is 0.
+pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(cap > 0, "the capacity of a channel must be larger than 0");
+    let chan = Arc::new(Mutex::new(Chan {
+        queue: VecDeque::with_capacity(cap),
+        cap,
+        senders: 1,
+        receiver_alive: true,
+        receiver_waker: None,
+        sender_wakers: VecDeque::new(),
+        next_sender_key: 0,
+    }));
+    let sender = Sender { chan: chan.clone() };
+    let receiver = Receiver { chan };
+    (sender, receiver)
+}
+
+struct Chan<T> {
+    queue: VecDeque<T>,
+    cap: usize,
+    senders: usize,
+    receiver_alive: bool,
+    receiver_waker: Option<Waker>,
+    /// Senders waiting for capacity, woken up one at a time as values are received. Each one is
+    /// identified by the key of its `SendFuture`.
+    sender_wakers: VecDeque<(usize, Waker)>,
+    next_sender_key: usize,
+}
+
+impl<T> Chan<T> {
+    fn wake_receiver(&mut self) {
+        if let Some(waker) = self.receiver_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn wake_sender(&mut self) {
+        if let Some((_, waker)) = self.sender_wakers.pop_front() {
+            waker.wake();
+        }
+    }
+
+    /// Queue a waiting sender, or update its waker if it's already queued.
+    fn register_sender(&mut self, key: &mut Option<usize>, waker: &Waker) {
+        let queued = key.and_then(|key| self.sender_wakers.iter_mut().find(|(k, _)| *k == key));
+        match queued {
+            Some((_, queued)) => {
+                if !queued.will_wake(waker) {
+                    *queued = waker.clone();
+                }
+            }
+            None => {
+                let new_key = self.next_sender_key;
+                self.next_sender_key = new_key.wrapping_add(1);
+                self.sender_wakers.push_back((new_key, waker.clone()));
+                *key = Some(new_key);
+            }
+        }
+    }
+
+    /// Take a waiting sender off the queue, returning whether it was still queued.
+    fn deregister_sender(&mut self, key: usize) -> bool {
+        match self.sender_wakers.iter().position(|(k, _)| *k == key) {
+            Some(index) => {
+                self.sender_wakers.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn try_send(&mut self, value: T) -> Result<(), TrySendError<T>> {
+        if !self.receiver_alive {
+            return Err(TrySendError::Closed(value));
+        }
+        if self.queue.len() == self.cap {
+            return Err(TrySendError::Full(value));
+        }
+        self.queue.push_back(value);
+        self.wake_receiver();
+        Ok(())
+    }
+}
+
+/// The sending half of a channel, created by [`channel`].
+///
+/// Senders can be cloned to send from several tasks. The channel is closed once every sender has
+/// been dropped.
+///
+/// [`channel`]: fn.channel.html
+pub struct Sender<T> {
+    chan: Arc<Mutex<Chan<T>>>,
+}
+
+impl<T> Sender<T> {
+    /// Send a value, waiting until the channel has capacity for it.
+    ///
+    /// The returned future resolves to an error holding the value if the [`Receiver`] has been
+    /// dropped.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    pub fn send(&self, value: T) -> SendFuture<'_, T> {
+        SendFuture {
+            sender: self,
+            value: Some(value),
+            key: None,
+        }
+    }
+
+    /// Send a value if the channel has capacity for it, without waiting.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.chan.lock().unwrap().try_send(value)
+    }
+
+    /// Returns the number of values that can currently be sent without waiting.
+    pub fn capacity(&self) -> usize {
+        let chan = self.chan.lock().unwrap();
+        chan.cap - chan.queue.len()
+    }
+
+    /// Returns `true` if the [`Receiver`] has been dropped.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    pub fn is_closed(&self) -> bool {
+        !self.chan.lock().unwrap().receiver_alive
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.chan.lock().unwrap().senders += 1;
+        Sender {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut chan = self.chan.lock().unwrap();
+        chan.senders -= 1;
+        if chan.senders == 0 {
+            chan.wake_receiver();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The future returned by [`Sender::send`].
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendFuture<'a, T> {
+    sender: &'a Sender<T>,
+    value: Option<T>,
+    /// The key this future is queued under, once it has had to wait for capacity.
+    key: Option<usize>,
+}
+
+// The value is moved, never pinned.
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let value = self
+            .value
+            .take()
+            .expect("SendFuture polled after completion");
+        let this = &mut *self;
+        let mut chan = this.sender.chan.lock().unwrap();
+        let res = match chan.try_send(value) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Closed(value)) => Err(SendError(value)),
+            Err(TrySendError::Full(value)) => {
+                chan.register_sender(&mut this.key, cx.waker());
+                this.value = Some(value);
+                return Poll::Pending;
+            }
+        };
+        if let Some(key) = this.key.take() {
+            chan.deregister_sender(key);
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<T> Drop for SendFuture<'_, T> {
+    fn drop(&mut self) {
+        // A future that has been taken off the queue was woken up for capacity it will never use,
+        // so the wake-up is passed on to the next waiting sender.
+        if let Some(key) = self.key {
+            let mut chan = self.sender.chan.lock().unwrap();
+            if !chan.deregister_sender(key) && chan.queue.len() < chan.cap {
+                chan.wake_sender();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for SendFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendFuture { .. }")
+    }
+}
+
+/// The receiving half of a channel, created by [`channel`].
+///
+/// The receiver is a stream of the values sent on the channel. Dropping it closes the channel,
+/// after which sending fails.
+///
+/// [`channel`]: fn.channel.html
+#[must_use = "streams do nothing unless polled"]
+pub struct Receiver<T> {
+    chan: Arc<Mutex<Chan<T>>>,
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut chan = self.chan.lock().unwrap();
+        if let Some(value) = chan.queue.pop_front() {
+            chan.wake_sender();
+            return Poll::Ready(Some(value));
+        }
+        if chan.senders == 0 {
+            return Poll::Ready(None);
+        }
+        chan.receiver_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut chan = self.chan.lock().unwrap();
+        chan.receiver_alive = false;
+        for (_, waker) in chan.sender_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// The error returned by [`Sender::send`] when the receiver has been dropped.
+///
+/// It holds the value that couldn't be sent.
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendError { .. }")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending on a closed channel")
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+/// The error returned by [`Sender::try_send`].
+///
+/// Both variants hold the value that couldn't be sent.
+///
+/// [`Sender::try_send`]: struct.Sender.html#method.try_send
+#[derive(Clone, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is full.
+    Full(T),
+    /// The receiver has been dropped.
+    Closed(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the value that couldn't be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(value) | TrySendError::Closed(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.pad("Full(..)"),
+            TrySendError::Closed(_) => f.pad("Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("sending on a full channel"),
+            TrySendError::Closed(_) => f.write_str("sending on a closed channel"),
+        }
+    }
+}
+
+impl<T> Error for TrySendError<T> {}
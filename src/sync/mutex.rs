@@ -0,0 +1,183 @@
+use futures::lock::{Mutex as FuturesMutex, MutexGuard as FuturesMutexGuard};
+use futures::prelude::*;
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::lock::{Acquire, RawLock};
+
+/// An async mutual exclusion lock, for sharing mutable data between tasks.
+///
+/// Unlike `std::sync::Mutex`, waiting for the lock suspends the task instead of blocking the
+/// thread, and the guard can be held across `.await` points. Tasks acquire the lock in the order
+/// they started waiting for it.
+///
+/// # Examples
+///
+/// ```
+/// use runtime::sync::Mutex;
+/// use std::sync::Arc;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let counter = Arc::new(Mutex::new(0));
+///
+///     let handles = (0..10)
+///         .map(|_| {
+///             let counter = counter.clone();
+///             runtime::spawn(async move {
+///                 *counter.lock().await += 1;
+///             })
+///         })
+///         .collect::<Vec<_>>();
+///
+///     for handle in handles {
+///         handle.await.unwrap();
+///     }
+///     assert_eq!(*counter.lock().await, 10);
+/// }
+/// ```
+pub struct Mutex<T: ?Sized> {
+    raw: RawLock,
+    // Only ever locked after the raw lock has been acquired, so locking it never fails.
+    data: FuturesMutex<T>,
+}
+
+impl<T> Mutex<T> {
+    /// Create a new unlocked mutex holding `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            raw: RawLock::default(),
+            data: FuturesMutex::new(value),
+        }
+    }
+
+    /// Consume the mutex, returning the data it holds.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Wait until the lock can be acquired, and acquire it.
+    pub fn lock(&self) -> MutexLockFuture<'_, T> {
+        MutexLockFuture {
+            mutex: self,
+            acquire: self.raw.acquire(true),
+        }
+    }
+
+    /// Acquire the lock if it's available, without waiting.
+    ///
+    /// This fails if other tasks are already waiting for the lock, even if it's not held.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        if self.raw.try_acquire(true) {
+            Some(MutexGuard::new(self))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the data.
+    ///
+    /// This doesn't need to lock the mutex, since the mutable borrow guarantees that no guards
+    /// exist.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+impl<T: Default> Default for Mutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for Mutex<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Mutex { .. }")
+    }
+}
+
+/// The future returned by [`Mutex::lock`].
+///
+/// [`Mutex::lock`]: struct.Mutex.html#method.lock
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct MutexLockFuture<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+    acquire: Acquire<'a>,
+}
+
+impl<'a, T: ?Sized> Future for MutexLockFuture<'a, T> {
+    type Output = MutexGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        futures::ready!(self.acquire.poll_unpin(cx));
+        Poll::Ready(MutexGuard::new(self.mutex))
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for MutexLockFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("MutexLockFuture { .. }")
+    }
+}
+
+/// A guard giving access to the data of a [`Mutex`], which unlocks it when dropped.
+///
+/// [`Mutex`]: struct.Mutex.html
+pub struct MutexGuard<'a, T: ?Sized> {
+    raw: &'a RawLock,
+    /// Only `None` while the guard is being dropped.
+    guard: Option<FuturesMutexGuard<'a, T>>,
+}
+
+impl<'a, T: ?Sized> MutexGuard<'a, T> {
+    /// Create a guard once the raw lock has been acquired.
+    fn new(mutex: &'a Mutex<T>) -> Self {
+        let guard = mutex
+            .data
+            .try_lock()
+            .expect("the data of a Mutex was locked without holding the Mutex");
+        MutexGuard {
+            raw: &mutex.raw,
+            guard: Some(guard),
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        // Unlock the data before handing the lock to the next task.
+        self.guard = None;
+        self.raw.release(true);
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
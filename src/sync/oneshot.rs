@@ -0,0 +1,167 @@
+//! A channel for sending a single value between tasks.
+//!
+//! [`channel`] creates a [`Sender`] and a [`Receiver`]. The sender sends at most one value, and
+//! the receiver is a future that resolves to it, which makes the channel a natural fit for
+//! returning a response to a task that made a request. If the sender is dropped without sending a
+//! value, the receiver resolves to a [`RecvError`] instead.
+//!
+//! [`channel`]: fn.channel.html
+//! [`Sender`]: struct.Sender.html
+//! [`Receiver`]: struct.Receiver.html
+//! [`RecvError`]: struct.RecvError.html
+//!
+//! ## Examples
+//! ```
+//! use runtime::sync::oneshot;
+//!
+//! #[runtime::main]
+//! async fn main() {
+//!     let (tx, rx) = oneshot::channel();
+//!
+//!     runtime::spawn(async move {
+//!         tx.send(42).unwrap();
+//!     });
+//!
+//!     assert_eq!(rx.await.unwrap(), 42);
+//! }
+//! ```
+
+use futures::channel::oneshot as inner;
+use futures::prelude::*;
+use futures::task::{Context, Poll};
+
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+
+/// Create a channel for sending a single value.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = inner::channel();
+    (Sender { inner: tx }, Receiver { inner: rx })
+}
+
+/// The sending half of a channel, created by [`channel`].
+///
+/// [`channel`]: fn.channel.html
+pub struct Sender<T> {
+    inner: inner::Sender<T>,
+}
+
+impl<T> Sender<T> {
+    /// Send a value to the receiver.
+    ///
+    /// This fails, returning the value, if the [`Receiver`] has been dropped or closed.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    pub fn send(self, value: T) -> Result<(), T> {
+        self.inner.send(value)
+    }
+
+    /// Returns `true` if the [`Receiver`] has been dropped or closed, in which case sending
+    /// would fail.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_canceled()
+    }
+
+    /// Check if the [`Receiver`] has been dropped or closed.
+    ///
+    /// Returns `Poll::Ready(())` once it has, and otherwise schedules the current task to be woken
+    /// up when it is. This lets a task stop computing a value nobody is waiting for anymore.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    pub fn poll_closed(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner.poll_cancel(cx)
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving half of a channel, created by [`channel`].
+///
+/// The receiver is a future that resolves to the value sent, or to a [`RecvError`] if the
+/// [`Sender`] is dropped without sending one.
+///
+/// [`channel`]: fn.channel.html
+/// [`Sender`]: struct.Sender.html
+/// [`RecvError`]: struct.RecvError.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Receiver<T> {
+    inner: inner::Receiver<T>,
+}
+
+impl<T> Receiver<T> {
+    /// Receive the value if it has already been sent, without waiting.
+    ///
+    /// Once the value has been received, this returns `TryRecvError::Closed`.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        match self.inner.try_recv() {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(TryRecvError::Empty),
+            Err(inner::Canceled) => Err(TryRecvError::Closed),
+        }
+    }
+
+    /// Close the channel, so that sending fails, without dropping the receiver.
+    ///
+    /// A value that was sent before the channel was closed can still be received.
+    pub fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_unpin(cx).map_err(|_| RecvError)
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// The error returned by a [`Receiver`] when the [`Sender`] was dropped without sending a value.
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`Sender`]: struct.Sender.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the sender was dropped without sending a value")
+    }
+}
+
+impl Error for RecvError {}
+
+/// The error returned by [`Receiver::try_recv`].
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No value has been sent yet.
+    Empty,
+    /// The sender was dropped without sending a value, or the value was already received.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("receiving on an empty channel"),
+            TryRecvError::Closed => f.write_str("receiving on a closed channel"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
@@ -0,0 +1,236 @@
+use futures::prelude::*;
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::lock::{Acquire, RawLock};
+
+/// An async reader-writer lock, allowing many readers or a single writer at a time.
+///
+/// Any number of tasks can hold a read guard at the same time, while a write guard gives a single
+/// task exclusive access. Waiting suspends the task instead of blocking the thread, and the guards
+/// can be held across `.await` points. Access is granted in the order it was requested, so a
+/// waiting writer isn't starved by readers that come after it.
+///
+/// # Examples
+///
+/// ```
+/// use runtime::sync::RwLock;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let lock = RwLock::new(5);
+///
+///     {
+///         let r1 = lock.read().await;
+///         let r2 = lock.read().await;
+///         assert_eq!(*r1 + *r2, 10);
+///     }
+///
+///     *lock.write().await += 1;
+///     assert_eq!(*lock.read().await, 6);
+/// }
+/// ```
+pub struct RwLock<T: ?Sized> {
+    raw: RawLock,
+    data: UnsafeCell<T>,
+}
+
+// The raw lock guarantees that the data is either shared by readers or borrowed by a single
+// writer, like `std::sync::RwLock`.
+unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    /// Create a new unlocked lock holding `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            raw: RawLock::default(),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consume the lock, returning the data it holds.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> RwLock<T> {
+    /// Wait until the lock can be shared with other readers, and acquire a read guard.
+    pub fn read(&self) -> RwLockReadFuture<'_, T> {
+        RwLockReadFuture {
+            lock: self,
+            acquire: self.raw.acquire(false),
+        }
+    }
+
+    /// Wait until the lock can be held exclusively, and acquire a write guard.
+    pub fn write(&self) -> RwLockWriteFuture<'_, T> {
+        RwLockWriteFuture {
+            lock: self,
+            acquire: self.raw.acquire(true),
+        }
+    }
+
+    /// Acquire a read guard if it's available, without waiting.
+    ///
+    /// This fails if tasks are already waiting for the lock.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        if self.raw.try_acquire(false) {
+            Some(RwLockReadGuard { lock: self })
+        } else {
+            None
+        }
+    }
+
+    /// Acquire a write guard if it's available, without waiting.
+    ///
+    /// This fails if tasks are already waiting for the lock.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        if self.raw.try_acquire(true) {
+            Some(RwLockWriteGuard { lock: self })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the data.
+    ///
+    /// This doesn't need to lock, since the mutable borrow guarantees that no guards exist.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+impl<T: Default> Default for RwLock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for RwLock<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for RwLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("RwLock { .. }")
+    }
+}
+
+/// The future returned by [`RwLock::read`].
+///
+/// [`RwLock::read`]: struct.RwLock.html#method.read
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RwLockReadFuture<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+    acquire: Acquire<'a>,
+}
+
+impl<'a, T: ?Sized> Future for RwLockReadFuture<'a, T> {
+    type Output = RwLockReadGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        futures::ready!(self.acquire.poll_unpin(cx));
+        Poll::Ready(RwLockReadGuard { lock: self.lock })
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for RwLockReadFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("RwLockReadFuture { .. }")
+    }
+}
+
+/// The future returned by [`RwLock::write`].
+///
+/// [`RwLock::write`]: struct.RwLock.html#method.write
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RwLockWriteFuture<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+    acquire: Acquire<'a>,
+}
+
+impl<'a, T: ?Sized> Future for RwLockWriteFuture<'a, T> {
+    type Output = RwLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        futures::ready!(self.acquire.poll_unpin(cx));
+        Poll::Ready(RwLockWriteGuard { lock: self.lock })
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for RwLockWriteFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("RwLockWriteFuture { .. }")
+    }
+}
+
+/// A guard giving shared access to the data of an [`RwLock`], which releases it when dropped.
+///
+/// [`RwLock`]: struct.RwLock.html
+pub struct RwLockReadGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T: ?Sized> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safe because the guard holds shared access, so nobody can write to the data.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.raw.release(false);
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// A guard giving exclusive access to the data of an [`RwLock`], which releases it when dropped.
+///
+/// [`RwLock`]: struct.RwLock.html
+pub struct RwLockWriteGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safe because the guard holds exclusive access.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // Safe because the guard holds exclusive access, and is borrowed mutably.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.raw.release(true);
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
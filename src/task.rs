@@ -1,11 +1,39 @@
 //! Types and Functions for working with asynchronous tasks.
 
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::panic::AssertUnwindSafe;
 use std::pin::Pin;
+use std::sync::Mutex;
+use std::thread;
 
-use futures::future::FutureObj;
+use futures::future::{AbortHandle, Abortable, FutureObj};
 use futures::prelude::*;
 use futures::task::{Context, Poll, Spawn, SpawnError};
 
+mod cancel;
+mod concurrent;
+mod handle;
+mod idle;
+mod join_set;
+mod local;
+mod notify;
+mod semaphore;
+
+pub use cancel::{CancellationToken, CancelledFuture};
+pub use concurrent::{for_each_concurrent_spawned, ForEachConcurrentSpawnedFuture};
+pub use handle::Handle;
+pub use idle::{join_all_spawned, JoinAllSpawnedFuture};
+pub use join_set::*;
+pub use local::*;
+pub use notify::{NotifiedFuture, Notify};
+pub use semaphore::*;
+
+#[doc(inline)]
+pub use crate::task_local;
+
 /// A [`Spawn`] handle to runtime's thread pool for spawning futures.
 ///
 /// This allows integrating runtime with libraries based on explicitly passed spawners.
@@ -39,16 +67,29 @@ impl Spawn for Spawner {
     }
 }
 
-impl<'a> Spawn for &'a Spawner {
+impl Spawn for &Spawner {
     fn spawn_obj(&mut self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
-        runtime_raw::current_runtime().spawn_boxed(future.boxed())
+        let fut = async move {
+            // There is no handle to report a panic to, but it mustn't unwind the worker thread.
+            let _ = AssertUnwindSafe(future).catch_unwind().await;
+        };
+        runtime_raw::current_runtime().spawn_boxed(fut.boxed())
     }
 }
 
 /// Spawn a future on the runtime's thread pool.
 ///
+/// The returned [`JoinHandle`] can be awaited to get the future's output. Dropping it detaches the
+/// task, which then keeps running in the background.
+///
+/// If the future panics, the panic is caught instead of unwinding the worker thread that polls it,
+/// and the handle resolves to a [`JoinError`] holding the panic payload.
+///
 /// This function can only be called after a runtime has been initialized.
 ///
+/// [`JoinHandle`]: struct.JoinHandle.html
+/// [`JoinError`]: struct.JoinError.html
+///
 /// # Examples
 ///
 /// ```
@@ -58,44 +99,350 @@ impl<'a> Spawn for &'a Spawner {
 ///         println!("running the future");
 ///         42
 ///     });
-///     assert_eq!(handle.await, 42);
+///     assert_eq!(handle.await.unwrap(), 42);
 /// }
 /// ```
 pub fn spawn<F, T>(fut: F) -> JoinHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    spawn_on(runtime_raw::current_runtime(), fut)
+}
+
+/// Spawn a future on the runtime's thread pool, resolving to the panic payload if it panics.
+///
+/// This is a thin wrapper around [`spawn`] that catches the panic inside the task, so the handle
+/// resolves to `Ok(Err(payload))` instead of a [`JoinError`]. The handle still resolves to a
+/// [`JoinError`] if the task is aborted.
+///
+/// This function can only be called after a runtime has been initialized.
+///
+/// [`spawn`]: fn.spawn.html
+/// [`JoinError`]: struct.JoinError.html
+///
+/// # Examples
+///
+/// ```
+/// #[runtime::main]
+/// async fn main() {
+///     let handle = runtime::task::spawn_catching(async {
+///         panic!("boom");
+///     });
+///     let payload = handle.await.unwrap().unwrap_err();
+///     assert_eq!(*payload.downcast::<&str>().unwrap(), "boom");
+/// }
+/// ```
+pub fn spawn_catching<F, T>(fut: F) -> JoinHandle<Result<T, Box<dyn Any + Send + 'static>>>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    spawn(AssertUnwindSafe(fut).catch_unwind())
+}
+
+/// Spawn a future on `runtime`, returning a handle to its output.
+pub(crate) fn spawn_on<F, T>(runtime: &'static dyn runtime_raw::Runtime, fut: F) -> JoinHandle<T>
 where
     F: Future<Output = T> + Send + 'static,
     T: Send + 'static,
 {
     let (tx, rx) = futures::channel::oneshot::channel();
+    let (abort, registration) = AbortHandle::new_pair();
+    let fut = Abortable::new(AssertUnwindSafe(fut).catch_unwind(), registration);
 
     let fut = async move {
-        let t = fut.await;
-        let _ = tx.send(t);
+        // An aborted future drops `tx`, which the handle reports as cancellation.
+        if let Ok(t) = fut.await {
+            let _ = tx.send(t);
+        }
     };
 
-    runtime_raw::current_runtime()
+    runtime
         .spawn_boxed(fut.boxed())
         .expect("cannot spawn a future");
 
-    JoinHandle { rx }
+    JoinHandle { rx, abort }
+}
+
+/// Get the number of worker threads the current runtime polls futures on.
+///
+/// Returns `None` if the runtime doesn't report it. The number can be configured with the
+/// `threads` option of the [`main`] and [`test`] attributes.
+///
+/// [`main`]: ../attr.main.html
+/// [`test`]: ../attr.test.html
+///
+/// # Examples
+///
+/// ```
+/// #[runtime::main(runtime_tokio::Tokio, threads = 2)]
+/// async fn main() {
+///     assert_eq!(runtime::task::worker_threads(), Some(2));
+/// }
+/// ```
+pub fn worker_threads() -> Option<usize> {
+    runtime_raw::current_runtime().worker_threads()
+}
+
+/// Block the current thread until `fut` has completed.
+///
+/// This makes it possible to use the runtime from synchronous code, such as a plain `fn main()`.
+/// The future runs on the runtime that has been set for the current thread, or on the default
+/// Native runtime if there is none, following the contract of [`runtime::raw::block_on`]: it is
+/// polled on the calling thread, so it doesn't need to be `Send` or `'static`.
+///
+/// Calling this function from inside of a task blocks the executor thread that runs the task.
+///
+/// [`runtime::raw::block_on`]: ../raw/fn.block_on.html
+///
+/// # Examples
+///
+/// ```
+/// let n = runtime::task::block_on(async {
+///     runtime::spawn(async { 42 }).await.unwrap()
+/// });
+/// assert_eq!(n, 42);
+/// ```
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    #[cfg(feature = "native")]
+    let runtime = runtime_raw::try_current_runtime().unwrap_or(&runtime_native::Native);
+    #[cfg(not(feature = "native"))]
+    let runtime = runtime_raw::current_runtime();
+
+    runtime_raw::block_on(runtime, fut)
+}
+
+/// Run a blocking function without blocking the runtime's executor.
+///
+/// Blocking calls such as file IO or CPU-heavy computations prevent other futures from making
+/// progress when run inside of a task. `spawn_blocking` runs them on a separate set of threads
+/// instead, and returns a future that resolves to the function's output.
+///
+/// This function can only be called after a runtime has been initialized.
+///
+/// # Panics
+///
+/// The returned future panics if `f` panics.
+///
+/// # Examples
+///
+/// ```
+/// #[runtime::main]
+/// async fn main() {
+///     let n = runtime::task::spawn_blocking(|| {
+///         // Simulate some blocking work...
+///         std::thread::sleep(std::time::Duration::from_millis(10));
+///         42
+///     })
+///     .await;
+///     assert_eq!(n, 42);
+/// }
+/// ```
+pub fn spawn_blocking<F, T>(f: F) -> SpawnBlockingFuture<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = futures::channel::oneshot::channel();
+
+    let f = move || {
+        let _ = tx.send(f());
+    };
+
+    runtime_raw::current_runtime()
+        .spawn_blocking(Box::new(f))
+        .expect("cannot spawn a blocking function");
+
+    SpawnBlockingFuture { rx }
+}
+
+/// The future returned by [`spawn_blocking`].
+///
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SpawnBlockingFuture<T> {
+    rx: futures::channel::oneshot::Receiver<T>,
+}
+
+impl<T> Future for SpawnBlockingFuture<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.rx
+            .poll_unpin(cx)
+            .map(|res| res.expect("the blocking function has panicked"))
+    }
+}
+
+/// Yield execution back to the runtime.
+///
+/// Tasks that run for a long time without awaiting anything prevent other tasks from running on
+/// the same thread. Awaiting `yield_now` gives the scheduler a chance to run them before the
+/// current task continues.
+///
+/// # Examples
+///
+/// ```
+/// #[runtime::main]
+/// async fn main() {
+///     for i in 0..1_000 {
+///         // Do some work...
+///         if i % 100 == 0 {
+///             runtime::task::yield_now().await;
+///         }
+///     }
+/// }
+/// ```
+pub fn yield_now() -> YieldNowFuture {
+    YieldNowFuture { yielded: false }
+}
+
+/// The future returned by [`yield_now`].
+///
+/// [`yield_now`]: fn.yield_now.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct YieldNowFuture {
+    yielded: bool,
+}
+
+impl Future for YieldNowFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
 }
 
 /// A handle that awaits the result of a [`spawn`]ed future.
 ///
+/// Dropping the handle detaches the task instead of cancelling it. Use [`abort`] to cancel it.
+///
 /// [`spawn`]: fn.spawn.html
+/// [`abort`]: struct.JoinHandle.html#method.abort
 #[derive(Debug)]
 pub struct JoinHandle<T> {
-    pub(crate) rx: futures::channel::oneshot::Receiver<T>,
+    pub(crate) rx: futures::channel::oneshot::Receiver<thread::Result<T>>,
+    pub(crate) abort: AbortHandle,
+}
+
+impl<T> JoinHandle<T> {
+    /// Cancel the task.
+    ///
+    /// The task is dropped the next time it would have been polled, and awaiting the handle
+    /// resolves to a [`JoinError`] for which [`is_cancelled`] returns `true`. Aborting a task that
+    /// has already completed has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[runtime::main]
+    /// async fn main() {
+    ///     let handle = runtime::spawn(runtime::time::sleep(Duration::from_secs(10)));
+    ///     handle.abort();
+    ///     assert!(handle.await.unwrap_err().is_cancelled());
+    /// }
+    /// ```
+    ///
+    /// [`JoinError`]: struct.JoinError.html
+    /// [`is_cancelled`]: struct.JoinError.html#method.is_cancelled
+    pub fn abort(&self) {
+        self.abort.abort();
+    }
 }
 
 impl<T> Future for JoinHandle<T> {
-    type Output = T;
+    type Output = Result<T, JoinError>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         match self.rx.poll_unpin(cx) {
             Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(t)) => Poll::Ready(t),
-            Poll::Ready(Err(_)) => panic!(), // TODO: Is this OK? Print a better error message?
+            Poll::Ready(Ok(Ok(t))) => Poll::Ready(Ok(t)),
+            Poll::Ready(Ok(Err(payload))) => Poll::Ready(Err(JoinError {
+                repr: Repr::Panic(Mutex::new(payload)),
+            })),
+            Poll::Ready(Err(_)) => Poll::Ready(Err(JoinError {
+                repr: Repr::Cancelled,
+            })),
+        }
+    }
+}
+
+/// The error returned when a [`spawn`]ed task didn't run to completion.
+///
+/// [`spawn`]: fn.spawn.html
+pub struct JoinError {
+    repr: Repr,
+}
+
+enum Repr {
+    Cancelled,
+    Panic(Mutex<Box<dyn Any + Send + 'static>>),
+}
+
+impl JoinError {
+    /// Returns `true` if the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        match self.repr {
+            Repr::Cancelled => true,
+            Repr::Panic(_) => false,
         }
     }
+
+    /// Returns `true` if the task panicked.
+    pub fn is_panic(&self) -> bool {
+        match self.repr {
+            Repr::Cancelled => false,
+            Repr::Panic(_) => true,
+        }
+    }
+
+    /// Consume the error, returning the object with which the task panicked.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the task didn't panic. Use [`is_panic`] to check first.
+    ///
+    /// [`is_panic`]: struct.JoinError.html#method.is_panic
+    pub fn into_panic(self) -> Box<dyn Any + Send + 'static> {
+        match self.repr {
+            Repr::Panic(payload) => payload.into_inner().unwrap(),
+            Repr::Cancelled => panic!("the task was cancelled, not panicked"),
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repr {
+            Repr::Cancelled => f.write_str("JoinError::Cancelled"),
+            Repr::Panic(_) => f.write_str("JoinError::Panic(..)"),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repr {
+            Repr::Cancelled => f.write_str("the task was cancelled"),
+            Repr::Panic(_) => f.write_str("the task panicked"),
+        }
+    }
+}
+
+impl Error for JoinError {}
+
+impl From<JoinError> for io::Error {
+    fn from(err: JoinError) -> Self {
+        io::Error::other(err)
+    }
 }
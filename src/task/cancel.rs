@@ -0,0 +1,190 @@
+use futures::prelude::*;
+
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+
+/// A token for asking tasks to stop, cooperatively.
+///
+/// Tasks watch for cancellation by awaiting [`cancelled`], typically raced against their work, and
+/// wind down on their own once it completes. Unlike [`JoinHandle::abort`], which stops a task at
+/// whatever point it's suspended, this lets a task finish what it's doing and clean up first.
+///
+/// Cloning a token returns a new handle to the same token. Tokens created with [`child_token`]
+/// are cancelled along with their parent, but can also be cancelled on their own without
+/// affecting it.
+///
+/// [`cancelled`]: #method.cancelled
+/// [`child_token`]: #method.child_token
+/// [`JoinHandle::abort`]: struct.JoinHandle.html#method.abort
+///
+/// # Examples
+///
+/// ```
+/// use futures::prelude::*;
+/// use runtime::task::CancellationToken;
+/// use std::time::Duration;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let token = CancellationToken::new();
+///
+///     let child = token.child_token();
+///     let handle = runtime::spawn(async move {
+///         let mut ticks = 0;
+///         loop {
+///             let tick = runtime::time::sleep(Duration::from_millis(10));
+///             futures::select! {
+///                 _ = tick.fuse() => ticks += 1,
+///                 _ = child.cancelled().fuse() => return ticks,
+///             }
+///         }
+///     });
+///
+///     runtime::time::sleep(Duration::from_millis(50)).await;
+///     token.cancel();
+///     println!("ticked {} times", handle.await.unwrap());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    cancelled: bool,
+    /// The tasks waiting for cancellation, each identified by the key of its `CancelledFuture`.
+    wakers: Vec<(usize, Waker)>,
+    next_key: usize,
+    children: Vec<Weak<Mutex<State>>>,
+}
+
+impl CancellationToken {
+    /// Create a token that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// Create a token that is cancelled when this one is.
+    ///
+    /// Cancelling the child token doesn't cancel this one. If this token has already been
+    /// cancelled, the child token starts out cancelled.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        let mut state = self.inner.lock().unwrap();
+        if state.cancelled {
+            child.inner.lock().unwrap().cancelled = true;
+        } else {
+            // Forget about the children that have been dropped since the last one was created.
+            state.children.retain(|child| child.strong_count() > 0);
+            state.children.push(Arc::downgrade(&child.inner));
+        }
+        child
+    }
+
+    /// Cancel this token and all of its children, waking up the tasks waiting on them.
+    ///
+    /// Cancelling a token that was already cancelled does nothing.
+    pub fn cancel(&self) {
+        let (wakers, children) = {
+            let mut state = self.inner.lock().unwrap();
+            if state.cancelled {
+                return;
+            }
+            state.cancelled = true;
+            (
+                std::mem::take(&mut state.wakers),
+                std::mem::take(&mut state.children),
+            )
+        };
+
+        for (_, waker) in wakers {
+            waker.wake();
+        }
+        for child in children.iter().filter_map(Weak::upgrade) {
+            CancellationToken { inner: child }.cancel();
+        }
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.lock().unwrap().cancelled
+    }
+
+    /// Wait until this token is cancelled.
+    pub fn cancelled(&self) -> CancelledFuture {
+        CancelledFuture {
+            token: self.clone(),
+            key: None,
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// The future returned by [`CancellationToken::cancelled`].
+///
+/// [`CancellationToken::cancelled`]: struct.CancellationToken.html#method.cancelled
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CancelledFuture {
+    token: CancellationToken,
+    /// The key this future's waker is stored under, once it has had to wait.
+    key: Option<usize>,
+}
+
+impl Future for CancelledFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.token.inner.lock().unwrap();
+        if state.cancelled {
+            // Cancelling the token already took the waker out.
+            this.key = None;
+            return Poll::Ready(());
+        }
+        let stored = this
+            .key
+            .and_then(|key| state.wakers.iter_mut().find(|(k, _)| *k == key));
+        match stored {
+            Some((_, waker)) => {
+                if !waker.will_wake(cx.waker()) {
+                    *waker = cx.waker().clone();
+                }
+            }
+            None => {
+                let key = state.next_key;
+                state.next_key = key.wrapping_add(1);
+                state.wakers.push((key, cx.waker().clone()));
+                this.key = Some(key);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for CancelledFuture {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            let mut state = self.token.inner.lock().unwrap();
+            state.wakers.retain(|(k, _)| *k != key);
+        }
+    }
+}
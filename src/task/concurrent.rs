@@ -0,0 +1,125 @@
+use futures::prelude::*;
+use futures::stream::FuturesUnordered;
+use pin_project::pin_project;
+
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::{spawn, JoinError, JoinHandle};
+
+/// Run a future for every item of a stream, each spawned as its own task, with at most `limit`
+/// of them running at the same time.
+///
+/// Unlike `StreamExt::for_each_concurrent`, which polls every future on the task that awaits it,
+/// the futures are spawned on the runtime's thread pool, so CPU-heavy work is spread across the
+/// worker threads. Items are only taken from the stream while fewer than `limit` tasks are
+/// running. A `limit` of `None` or zero doesn't bound the number of tasks.
+///
+/// The returned future completes once the stream has ended and every task has completed. If a
+/// task panics or is cancelled, it resolves to that task's [`JoinError`] right away; the tasks
+/// that are still running are detached and keep running in the background.
+///
+/// [`JoinError`]: struct.JoinError.html
+///
+/// # Examples
+///
+/// ```
+/// use futures::prelude::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let sum = Arc::new(AtomicUsize::new(0));
+///
+///     let items = stream::iter(0..10);
+///     runtime::task::for_each_concurrent_spawned(items, 4, |i| {
+///         let sum = sum.clone();
+///         async move {
+///             sum.fetch_add(i, Ordering::SeqCst);
+///         }
+///     })
+///     .await
+///     .unwrap();
+///
+///     assert_eq!(sum.load(Ordering::SeqCst), 45);
+/// }
+/// ```
+pub fn for_each_concurrent_spawned<S, F, Fut>(
+    stream: S,
+    limit: impl Into<Option<usize>>,
+    f: F,
+) -> ForEachConcurrentSpawnedFuture<S, F>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    ForEachConcurrentSpawnedFuture {
+        stream: Some(stream),
+        f,
+        limit: limit.into().and_then(NonZeroUsize::new),
+        tasks: FuturesUnordered::new(),
+    }
+}
+
+/// The future returned by [`for_each_concurrent_spawned`].
+///
+/// [`for_each_concurrent_spawned`]: fn.for_each_concurrent_spawned.html
+#[pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ForEachConcurrentSpawnedFuture<S, F> {
+    /// The stream of items, or `None` once it has ended.
+    #[pin]
+    stream: Option<S>,
+    f: F,
+    limit: Option<NonZeroUsize>,
+    tasks: FuturesUnordered<JoinHandle<()>>,
+}
+
+impl<S, F, Fut> Future for ForEachConcurrentSpawnedFuture<S, F>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    type Output = Result<(), JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            while this
+                .limit
+                .is_none_or(|limit| this.tasks.len() < limit.get())
+            {
+                let item = match this.stream.as_mut().as_pin_mut() {
+                    Some(stream) => match stream.poll_next(cx) {
+                        Poll::Ready(item) => item,
+                        Poll::Pending => break,
+                    },
+                    None => break,
+                };
+                match item {
+                    Some(item) => this.tasks.push(spawn((this.f)(item))),
+                    None => this.stream.set(None),
+                }
+            }
+
+            match this.tasks.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(()))) => {}
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Err(err)),
+                Poll::Ready(None) if this.stream.is_none() => return Poll::Ready(Ok(())),
+                // The stream hasn't ended, and has registered the waker for its next item.
+                Poll::Ready(None) | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for ForEachConcurrentSpawnedFuture<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("ForEachConcurrentSpawnedFuture { .. }")
+    }
+}
@@ -0,0 +1,73 @@
+use futures::prelude::*;
+
+use std::fmt;
+
+use super::{spawn_on, JoinHandle};
+
+/// A handle to a runtime, for spawning futures onto it from any thread.
+///
+/// [`spawn`] looks up the runtime that has been set for the calling thread, so it can't be called
+/// from threads the runtime doesn't own, such as the ones running [`spawn_blocking`] functions or
+/// callbacks from other libraries. A `Handle` captures the current runtime when it's created, and
+/// spawns onto that runtime wherever it's used.
+///
+/// [`spawn`]: fn.spawn.html
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+///
+/// # Examples
+///
+/// ```
+/// use runtime::task::Handle;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let handle = Handle::current();
+///     let task = std::thread::spawn(move || handle.spawn(async { 42 }))
+///         .join()
+///         .unwrap();
+///     assert_eq!(task.await.unwrap(), 42);
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Handle {
+    runtime: &'static dyn runtime_raw::Runtime,
+}
+
+impl Handle {
+    /// Returns a handle to the runtime that has been set for the current thread.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no runtime has been set on the current thread. Use
+    /// [`try_current`] to handle that case instead.
+    ///
+    /// [`try_current`]: #method.try_current
+    pub fn current() -> Self {
+        Self::try_current().expect("the runtime has not been set")
+    }
+
+    /// Returns a handle to the runtime that has been set for the current thread, or `None` if
+    /// there is none.
+    pub fn try_current() -> Option<Self> {
+        runtime_raw::try_current_runtime().map(|runtime| Self { runtime })
+    }
+
+    /// Spawn a future on this handle's runtime.
+    ///
+    /// This works like [`spawn`], but can be called from any thread.
+    ///
+    /// [`spawn`]: fn.spawn.html
+    pub fn spawn<F, T>(&self, fut: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        spawn_on(self.runtime, fut)
+    }
+}
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Handle { .. }")
+    }
+}
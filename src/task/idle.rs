@@ -0,0 +1,61 @@
+use futures::future::BoxFuture;
+use futures::prelude::*;
+
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Wait until all tasks spawned on the current runtime have completed.
+///
+/// This covers every task started with [`spawn`] or through a [`Spawner`], including detached
+/// ones, and is useful to shut down gracefully before returning from `main`. Tasks spawned while
+/// waiting are waited on as well. Tasks spawned on other runtimes aren't.
+///
+/// When awaited from inside spawned tasks, those tasks themselves aren't waited on. Runtimes that
+/// don't keep track of their tasks complete this right away.
+///
+/// [`spawn`]: fn.spawn.html
+/// [`Spawner`]: struct.Spawner.html
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     runtime::spawn(async {
+///         runtime::time::sleep(Duration::from_millis(10)).await;
+///         println!("finished in the background");
+///     });
+///
+///     runtime::task::join_all_spawned().await;
+/// }
+/// ```
+pub fn join_all_spawned() -> JoinAllSpawnedFuture {
+    JoinAllSpawnedFuture {
+        inner: runtime_raw::current_runtime().wait_idle(),
+    }
+}
+
+/// The future returned by [`join_all_spawned`].
+///
+/// [`join_all_spawned`]: fn.join_all_spawned.html
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct JoinAllSpawnedFuture {
+    inner: BoxFuture<'static, ()>,
+}
+
+impl fmt::Debug for JoinAllSpawnedFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("JoinAllSpawnedFuture { .. }")
+    }
+}
+
+impl Future for JoinAllSpawnedFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.as_mut().poll(cx)
+    }
+}
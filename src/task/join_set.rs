@@ -0,0 +1,94 @@
+use futures::prelude::*;
+use futures::stream::FuturesUnordered;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::{spawn, JoinError, JoinHandle};
+
+/// A collection of spawned tasks that can be awaited in the order they complete.
+///
+/// Dropping a `JoinSet` detaches the tasks in it, which keep running in the background.
+///
+/// # Examples
+///
+/// ```
+/// use runtime::task::JoinSet;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let mut set = JoinSet::new();
+///     for i in 0..3 {
+///         set.spawn(async move { i * 2 });
+///     }
+///
+///     let mut sum = 0;
+///     while let Some(res) = set.join_next().await {
+///         sum += res.unwrap();
+///     }
+///     assert_eq!(sum, 6);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct JoinSet<T> {
+    tasks: FuturesUnordered<JoinHandle<T>>,
+}
+
+impl<T: Send + 'static> JoinSet<T> {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self {
+            tasks: FuturesUnordered::new(),
+        }
+    }
+
+    /// Spawn a future on the runtime's thread pool and add it to the set.
+    pub fn spawn<F>(&mut self, fut: F)
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        self.tasks.push(spawn(fut));
+    }
+
+    /// Wait for the next task in the set to complete and return its output.
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn join_next(&mut self) -> JoinNextFuture<'_, T> {
+        JoinNextFuture { set: self }
+    }
+
+    /// Returns the number of tasks in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if the set contains no tasks.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+impl<T: Send + 'static> Default for JoinSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The future returned by [`JoinSet::join_next`].
+///
+/// [`JoinSet::join_next`]: struct.JoinSet.html#method.join_next
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct JoinNextFuture<'a, T> {
+    set: &'a mut JoinSet<T>,
+}
+
+impl<T> Future for JoinNextFuture<'_, T> {
+    type Output = Option<Result<T, JoinError>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.set.tasks.poll_next_unpin(cx)
+    }
+}
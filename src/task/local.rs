@@ -0,0 +1,160 @@
+use pin_project::pin_project;
+
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread;
+
+/// Declare a new task-local storage key of type [`LocalKey`].
+///
+/// Task-local values are set for the duration of a future with [`LocalKey::scope`], and can be
+/// read from anywhere inside of that future with [`LocalKey::with`], including from other futures
+/// it awaits.
+///
+/// [`LocalKey`]: task/struct.LocalKey.html
+/// [`LocalKey::scope`]: task/struct.LocalKey.html#method.scope
+/// [`LocalKey::with`]: task/struct.LocalKey.html#method.with
+///
+/// # Examples
+///
+/// ```
+/// runtime::task::task_local! {
+///     static REQUEST_ID: u64;
+/// }
+///
+/// async fn handle() -> u64 {
+///     REQUEST_ID.with(|id| *id)
+/// }
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let id = REQUEST_ID.scope(7, handle()).await;
+///     assert_eq!(id, 7);
+/// }
+/// ```
+#[macro_export]
+macro_rules! task_local {
+    () => {};
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty; $($rest:tt)*) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+        $crate::task_local!($($rest)*);
+    };
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __task_local_inner {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $t:ty) => {
+        $(#[$attr])*
+        $vis static $name: $crate::task::LocalKey<$t> = {
+            std::thread_local! {
+                static __KEY: std::cell::RefCell<Option<$t>> = std::cell::RefCell::new(None);
+            }
+
+            $crate::task::LocalKey { inner: __KEY }
+        };
+    };
+}
+
+/// A key for task-local data.
+///
+/// Keys are created with the [`task_local!`] macro.
+///
+/// [`task_local!`]: ../macro.task_local.html
+pub struct LocalKey<T: 'static> {
+    #[doc(hidden)]
+    pub inner: thread::LocalKey<RefCell<Option<T>>>,
+}
+
+impl<T: 'static> LocalKey<T> {
+    /// Set the value of this key to `value` while `future` is being polled.
+    pub fn scope<F: Future>(&'static self, value: T, future: F) -> TaskLocalFuture<T, F> {
+        TaskLocalFuture {
+            local: self,
+            slot: Some(value),
+            future,
+        }
+    }
+
+    /// Access the value of this key.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called outside of a future passed to [`scope`].
+    ///
+    /// [`scope`]: struct.LocalKey.html#method.scope
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.inner.with(|cell| {
+            let value = cell.borrow();
+            let value = value
+                .as_ref()
+                .expect("cannot access a task-local value outside of `LocalKey::scope`");
+            f(value)
+        })
+    }
+}
+
+impl<T: 'static> fmt::Debug for LocalKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("LocalKey { .. }")
+    }
+}
+
+/// The future returned by [`LocalKey::scope`].
+///
+/// [`LocalKey::scope`]: struct.LocalKey.html#method.scope
+#[pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TaskLocalFuture<T: 'static, F> {
+    local: &'static LocalKey<T>,
+    slot: Option<T>,
+    #[pin]
+    future: F,
+}
+
+impl<T: 'static, F: Future> Future for TaskLocalFuture<T, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Swaps the value back out of the thread-local when dropped, even if polling panics.
+        struct Guard<'a, T: 'static> {
+            local: &'static LocalKey<T>,
+            slot: &'a mut Option<T>,
+        }
+
+        impl<T: 'static> Drop for Guard<'_, T> {
+            fn drop(&mut self) {
+                self.local
+                    .inner
+                    .with(|cell| mem::swap(self.slot, &mut *cell.borrow_mut()));
+            }
+        }
+
+        let this = self.project();
+        this.local
+            .inner
+            .with(|cell| mem::swap(this.slot, &mut *cell.borrow_mut()));
+        let _guard = Guard {
+            local: this.local,
+            slot: this.slot,
+        };
+        this.future.poll(cx)
+    }
+}
+
+impl<T: 'static, F> fmt::Debug for TaskLocalFuture<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("TaskLocalFuture { .. }")
+    }
+}
@@ -0,0 +1,203 @@
+use futures::prelude::*;
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// The waiter hasn't been notified yet.
+const WAITING: usize = 0;
+/// The waiter was picked by `notify_one`.
+const NOTIFIED_ONE: usize = 1;
+/// The waiter was woken up by `notify_waiters`.
+const NOTIFIED_ALL: usize = 2;
+
+/// Notifies tasks waiting for an event, like a condition variable without the lock.
+///
+/// A task waits for a notification by awaiting [`notified`]. [`notify_one`] wakes up a single
+/// waiting task. If no task is waiting, the notification is stored as a permit, and the next call
+/// to `notified` completes right away; at most one permit is stored. [`notify_waiters`] wakes up
+/// every task that is waiting at the time, and doesn't store a permit.
+///
+/// Cloning a `Notify` returns a new handle to the same set of waiters.
+///
+/// [`notified`]: #method.notified
+/// [`notify_one`]: #method.notify_one
+/// [`notify_waiters`]: #method.notify_waiters
+///
+/// # Examples
+///
+/// ```
+/// use runtime::task::Notify;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let notify = Notify::new();
+///
+///     let waiter = notify.clone();
+///     let handle = runtime::spawn(async move {
+///         waiter.notified().await;
+///         println!("received a notification");
+///     });
+///
+///     notify.notify_one();
+///     handle.await.unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Notify {
+    inner: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    permit: bool,
+    waiters: VecDeque<Arc<Waiter>>,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    notified: AtomicUsize,
+    waker: Mutex<Waker>,
+}
+
+impl Waiter {
+    fn wake(&self) {
+        self.waker.lock().unwrap().wake_by_ref();
+    }
+}
+
+impl State {
+    /// Hand a `notify_one` notification to the first waiter, returning it so it can be woken up
+    /// once the lock is released, or store a permit if there is no waiter.
+    fn notify_one(&mut self) -> Option<Arc<Waiter>> {
+        match self.waiters.pop_front() {
+            Some(waiter) => {
+                waiter.notified.store(NOTIFIED_ONE, Ordering::SeqCst);
+                Some(waiter)
+            }
+            None => {
+                self.permit = true;
+                None
+            }
+        }
+    }
+}
+
+impl Notify {
+    /// Create a `Notify` with no waiters and no stored permit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wake up one waiting task, or store a permit for the next one if no task is waiting.
+    pub fn notify_one(&self) {
+        let waiter = self.inner.lock().unwrap().notify_one();
+        if let Some(waiter) = waiter {
+            waiter.wake();
+        }
+    }
+
+    /// Wake up all tasks that are currently waiting.
+    ///
+    /// Unlike [`notify_one`], this doesn't store a permit: a task that starts waiting afterwards
+    /// isn't notified.
+    ///
+    /// [`notify_one`]: #method.notify_one
+    pub fn notify_waiters(&self) {
+        let waiters = std::mem::take(&mut self.inner.lock().unwrap().waiters);
+        for waiter in &waiters {
+            waiter.notified.store(NOTIFIED_ALL, Ordering::SeqCst);
+        }
+        for waiter in waiters {
+            waiter.wake();
+        }
+    }
+
+    /// Wait for a notification.
+    ///
+    /// The returned future is registered as a waiter the first time it's polled, and completes
+    /// right away if a permit is stored at that point.
+    pub fn notified(&self) -> NotifiedFuture {
+        NotifiedFuture {
+            notify: self.clone(),
+            waiter: None,
+        }
+    }
+}
+
+/// The future returned by [`Notify::notified`].
+///
+/// [`Notify::notified`]: struct.Notify.html#method.notified
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct NotifiedFuture {
+    notify: Notify,
+    waiter: Option<Arc<Waiter>>,
+}
+
+impl Future for NotifiedFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let waiter = match &self.waiter {
+            Some(waiter) => waiter.clone(),
+            None => {
+                let mut state = self.notify.inner.lock().unwrap();
+                if state.permit {
+                    state.permit = false;
+                    return Poll::Ready(());
+                }
+                let waiter = Arc::new(Waiter {
+                    notified: AtomicUsize::new(WAITING),
+                    waker: Mutex::new(cx.waker().clone()),
+                });
+                state.waiters.push_back(waiter.clone());
+                drop(state);
+                self.waiter = Some(waiter);
+                return Poll::Pending;
+            }
+        };
+
+        {
+            let mut waker = waiter.waker.lock().unwrap();
+            if !waker.will_wake(cx.waker()) {
+                *waker = cx.waker().clone();
+            }
+        }
+        // The waker is updated before checking for a notification, so a notification that comes
+        // in between wakes up the new waker.
+        if waiter.notified.load(Ordering::SeqCst) == WAITING {
+            return Poll::Pending;
+        }
+        self.waiter = None;
+        Poll::Ready(())
+    }
+}
+
+impl Drop for NotifiedFuture {
+    fn drop(&mut self) {
+        let waiter = match self.waiter.take() {
+            Some(waiter) => waiter,
+            None => return,
+        };
+
+        // Notifications are handed out while holding the lock, so this can't miss one.
+        let mut state = self.notify.inner.lock().unwrap();
+        let next = match waiter.notified.load(Ordering::SeqCst) {
+            WAITING => {
+                state.waiters.retain(|w| !Arc::ptr_eq(w, &waiter));
+                None
+            }
+            // This future was picked by `notify_one` but will never observe it, so pass the
+            // notification on to the next waiter.
+            NOTIFIED_ONE => state.notify_one(),
+            _ => None,
+        };
+        drop(state);
+        if let Some(next) = next {
+            next.wake();
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use futures::prelude::*;
+use futures::ready;
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use super::{spawn, JoinHandle};
+
+/// A counting semaphore, limiting how many tasks can do something at the same time.
+///
+/// A permit is acquired with [`acquire`], and given back to the semaphore when it's dropped.
+/// [`spawn_with_permit`] waits for a permit before spawning a task, which holds on to it until it
+/// completes. This caps the number of tasks running at once, for example to bound the number of
+/// connections a server handles concurrently.
+///
+/// Cloning a `Semaphore` returns a new handle to the same set of permits. Waiting tasks aren't
+/// guaranteed to acquire permits in the order they started waiting.
+///
+/// [`acquire`]: #method.acquire
+/// [`spawn_with_permit`]: #method.spawn_with_permit
+///
+/// # Examples
+///
+/// ```
+/// use runtime::task::Semaphore;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let semaphore = Semaphore::new(2);
+///     let mut handles = Vec::new();
+///     for i in 0..10 {
+///         // No more than two of these tasks run at the same time.
+///         handles.push(semaphore.spawn_with_permit(async move { i * 2 }).await);
+///     }
+///
+///     for handle in handles {
+///         handle.await.unwrap();
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Semaphore {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    permits: AtomicUsize,
+    waiters: Mutex<Waiters>,
+}
+
+/// The tasks waiting for a permit, each identified by the key of its `AcquireFuture`.
+#[derive(Debug, Default)]
+struct Waiters {
+    next_key: usize,
+    queue: VecDeque<(usize, Waker)>,
+}
+
+impl Semaphore {
+    /// Create a semaphore with the given number of permits.
+    pub fn new(permits: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                permits: AtomicUsize::new(permits),
+                waiters: Mutex::new(Waiters::default()),
+            }),
+        }
+    }
+
+    /// Returns the number of permits that can currently be acquired.
+    pub fn available_permits(&self) -> usize {
+        self.inner.permits.load(Ordering::SeqCst)
+    }
+
+    /// Acquire a permit if one is available, without waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit> {
+        let mut permits = self.inner.permits.load(Ordering::SeqCst);
+        loop {
+            if permits == 0 {
+                return None;
+            }
+            match self.inner.permits.compare_exchange_weak(
+                permits,
+                permits - 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => {
+                    return Some(SemaphorePermit {
+                        semaphore: self.clone(),
+                    })
+                }
+                Err(actual) => permits = actual,
+            }
+        }
+    }
+
+    /// Wait until a permit is available, and acquire it.
+    pub fn acquire(&self) -> AcquireFuture {
+        AcquireFuture {
+            semaphore: self.clone(),
+            key: None,
+        }
+    }
+
+    /// Wait until a permit is available, then spawn a future that holds on to it until it
+    /// completes.
+    ///
+    /// The returned future resolves to the spawned task's [`JoinHandle`] once the task has been
+    /// spawned.
+    ///
+    /// [`JoinHandle`]: struct.JoinHandle.html
+    pub fn spawn_with_permit<F, T>(&self, fut: F) -> SpawnWithPermitFuture<F>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        SpawnWithPermitFuture {
+            acquire: self.acquire(),
+            fut: Some(fut),
+        }
+    }
+
+    fn release(&self) {
+        self.inner.permits.fetch_add(1, Ordering::SeqCst);
+        self.wake_one();
+    }
+
+    fn wake_one(&self) {
+        let waiter = self.inner.waiters.lock().unwrap().queue.pop_front();
+        if let Some((_, waker)) = waiter {
+            waker.wake();
+        }
+    }
+}
+
+/// A permit acquired from a [`Semaphore`], which is given back when it's dropped.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+#[derive(Debug)]
+pub struct SemaphorePermit {
+    semaphore: Semaphore,
+}
+
+impl Drop for SemaphorePermit {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
+
+/// The future returned by [`Semaphore::acquire`].
+///
+/// [`Semaphore::acquire`]: struct.Semaphore.html#method.acquire
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct AcquireFuture {
+    semaphore: Semaphore,
+    /// The key this future is queued under, once it has had to wait.
+    key: Option<usize>,
+}
+
+impl AcquireFuture {
+    /// Take this future off the queue of waiting tasks, returning whether it was still queued.
+    fn dequeue(&mut self) -> bool {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return false,
+        };
+        let mut waiters = self.semaphore.inner.waiters.lock().unwrap();
+        match waiters.queue.iter().position(|(k, _)| *k == key) {
+            Some(index) => {
+                waiters.queue.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Future for AcquireFuture {
+    type Output = SemaphorePermit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(permit) = self.semaphore.try_acquire() {
+            self.dequeue();
+            return Poll::Ready(permit);
+        }
+
+        {
+            let this = &mut *self;
+            let mut waiters = this.semaphore.inner.waiters.lock().unwrap();
+            let queued = this
+                .key
+                .and_then(|key| waiters.queue.iter_mut().find(|(k, _)| *k == key));
+            match queued {
+                Some((_, waker)) => {
+                    if !waker.will_wake(cx.waker()) {
+                        *waker = cx.waker().clone();
+                    }
+                }
+                None => {
+                    let key = waiters.next_key;
+                    waiters.next_key = key.wrapping_add(1);
+                    waiters.queue.push_back((key, cx.waker().clone()));
+                    this.key = Some(key);
+                }
+            }
+        }
+
+        // A permit may have been released before the waker was queued, in which case nobody is
+        // going to wake this task up.
+        match self.semaphore.try_acquire() {
+            Some(permit) => {
+                self.dequeue();
+                Poll::Ready(permit)
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for AcquireFuture {
+    fn drop(&mut self) {
+        // A future that has been taken off the queue was woken up for a released permit it will
+        // never acquire, so the wake-up is passed on to the next waiting task.
+        let woken = self.key.is_some() && !self.dequeue();
+        if woken && self.semaphore.available_permits() > 0 {
+            self.semaphore.wake_one();
+        }
+    }
+}
+
+/// The future returned by [`Semaphore::spawn_with_permit`].
+///
+/// [`Semaphore::spawn_with_permit`]: struct.Semaphore.html#method.spawn_with_permit
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SpawnWithPermitFuture<F> {
+    acquire: AcquireFuture,
+    fut: Option<F>,
+}
+
+// The spawned future is moved, never pinned.
+impl<F> Unpin for SpawnWithPermitFuture<F> {}
+
+impl<F, T> Future for SpawnWithPermitFuture<F>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    type Output = JoinHandle<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let permit = ready!(self.acquire.poll_unpin(cx));
+        let fut = self
+            .fut
+            .take()
+            .expect("SpawnWithPermitFuture polled after completion");
+        Poll::Ready(spawn(async move {
+            let _permit = permit;
+            fut.await
+        }))
+    }
+}
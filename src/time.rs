@@ -4,24 +4,32 @@
 //!
 //! # Organization
 //!
+//! * [`sleep`] and [`sleep_until`] pause the current task for a duration or until an instant.
 //! * [`Delay`] and [`Interval`] provide functionality for setting delays and intervals.
+//! * [`DelayQueue`] yields many values, each once its own deadline has been reached.
 //! * [`FutureExt`] extends Futures with the ability to time-out.
+//! * [`Stopwatch`] measures elapsed time, and [`timed`] reports how long a future took.
 //! * Other types are return or parameter types for various methods in this module
 //!
+//! [`sleep`]: fn.sleep.html
+//! [`sleep_until`]: fn.sleep_until.html
 //! [`Delay`]: struct.Delay.html
+//! [`DelayQueue`]: struct.DelayQueue.html
 //! [`Interval`]: struct.Interval.html
 //! [`FutureExt`]: trait.FutureExt.html
+//! [`Stopwatch`]: struct.Stopwatch.html
+//! [`timed`]: fn.timed.html
 //!
 //! ## Examples
 //! __Delay execution for three seconds__
 //! ```no_run
 //! # #[runtime::main]
 //! # async fn main() {
-//! use runtime::time::Delay;
+//! use runtime::time::sleep;
 //! use std::time::{Duration, Instant};
 //!
 //! let start = Instant::now();
-//! let now = Delay::new(Duration::from_secs(3)).await;
+//! let now = sleep(Duration::from_secs(3)).await;
 //!
 //! let elapsed = now - start;
 //! println!("elapsed: {}s", elapsed.as_secs());
@@ -47,9 +55,24 @@
 //! ```
 
 mod delay;
+mod delay_queue;
 mod ext;
 mod interval;
+mod stopwatch;
 
 pub use delay::*;
+pub use delay_queue::*;
 pub use ext::*;
 pub use interval::*;
+pub use stopwatch::*;
+
+use std::time::Instant;
+
+/// Get the current time according to the current runtime's clock.
+///
+/// This is `Instant::now()` unless the runtime has a clock of its own, such as a test clock that
+/// only moves forward when it's advanced. Delays and intervals measure time against this clock,
+/// so code that compares instants with them should get the time from here too.
+pub fn now() -> Instant {
+    runtime_raw::current_runtime().now()
+}
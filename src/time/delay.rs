@@ -1,14 +1,67 @@
 use futures::prelude::*;
+use futures::ready;
 
 use std::fmt;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
+/// Wait until `dur` has elapsed.
+///
+/// This is a shorthand for [`Delay::new`].
+///
+/// [`Delay::new`]: struct.Delay.html#method.new
+///
+/// ## Examples
+/// ```
+/// use runtime::time::sleep;
+/// use std::time::{Duration, Instant};
+///
+/// # #[runtime::main]
+/// # async fn main () {
+/// let start = Instant::now();
+/// sleep(Duration::from_millis(10)).await;
+///
+/// assert!(start.elapsed() >= Duration::from_millis(10));
+/// # }
+/// ```
+#[inline]
+pub fn sleep(dur: Duration) -> Delay {
+    Delay::new(dur)
+}
+
+/// Wait until `at` has been reached.
+///
+/// This is a shorthand for [`Delay::new_at`].
+///
+/// [`Delay::new_at`]: struct.Delay.html#method.new_at
+///
+/// ## Examples
+/// ```
+/// use runtime::time::sleep_until;
+/// use std::time::{Duration, Instant};
+///
+/// # #[runtime::main]
+/// # async fn main () {
+/// let at = Instant::now() + Duration::from_millis(10);
+/// sleep_until(at).await;
+///
+/// assert!(Instant::now() >= at);
+/// # }
+/// ```
+#[inline]
+pub fn sleep_until(at: Instant) -> Delay {
+    Delay::new_at(at)
+}
+
 /// A future representing the notification that an elapsed duration has occurred.
+///
+/// Once it has completed, polling it again returns the same instant right away, so a `Delay` can
+/// be polled repeatedly from a loop or `select!` without having to be fused.
 #[must_use = "futures do nothing unless awaited"]
 pub struct Delay {
     inner: Pin<Box<dyn runtime_raw::Delay>>,
+    elapsed: Option<Instant>,
 }
 
 impl Delay {
@@ -29,8 +82,12 @@ impl Delay {
     /// ```
     #[inline]
     pub fn new(dur: Duration) -> Self {
-        let inner = runtime_raw::current_runtime().new_delay(dur);
-        Self { inner }
+        let runtime = runtime_raw::current_runtime();
+        let inner = runtime.new_delay_at(runtime.now() + dur);
+        Self {
+            inner,
+            elapsed: None,
+        }
     }
 
     /// Continue execution after the given instant.
@@ -51,7 +108,34 @@ impl Delay {
     #[inline]
     pub fn new_at(at: Instant) -> Self {
         let inner = runtime_raw::current_runtime().new_delay_at(at);
-        Self { inner }
+        Self {
+            inner,
+            elapsed: None,
+        }
+    }
+
+    /// Returns `true` if the delay has completed.
+    ///
+    /// This only checks whether a previous poll returned `Ready`, it doesn't poll the delay, so it
+    /// stays `false` past the deadline until the delay is polled again.
+    ///
+    /// ## Examples
+    /// ```
+    /// use runtime::time::Delay;
+    /// use std::time::Duration;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main () -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    /// let mut delay = Delay::new(Duration::from_millis(40));
+    /// assert!(!delay.is_elapsed());
+    ///
+    /// (&mut delay).await;
+    /// assert!(delay.is_elapsed());
+    /// # Ok(())}
+    /// ```
+    #[inline]
+    pub fn is_elapsed(&self) -> bool {
+        self.elapsed.is_some()
     }
 }
 
@@ -66,6 +150,11 @@ impl Future for Delay {
 
     #[inline]
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.inner.poll_unpin(cx)
+        if let Some(at) = self.elapsed {
+            return Poll::Ready(at);
+        }
+        let at = ready!(self.inner.poll_unpin(cx));
+        self.elapsed = Some(at);
+        Poll::Ready(at)
     }
 }
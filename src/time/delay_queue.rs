@@ -0,0 +1,165 @@
+use futures::prelude::*;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use super::Delay;
+
+/// A queue of values that are yielded once their deadline has been reached.
+///
+/// All entries share a single timer that tracks the nearest deadline, which makes this a cheaper
+/// alternative to spawning one [`Delay`] per value.
+///
+/// The stream resolves to `None` once the queue is empty. It can be polled again after inserting
+/// new values.
+///
+/// [`Delay`]: struct.Delay.html
+///
+/// ## Examples
+/// ```
+/// # use futures::prelude::*;
+/// use runtime::time::DelayQueue;
+/// use std::time::Duration;
+///
+/// # #[runtime::main]
+/// # async fn main () {
+/// let mut queue = DelayQueue::new();
+/// queue.insert("later", Duration::from_millis(20));
+/// queue.insert("sooner", Duration::from_millis(10));
+///
+/// assert_eq!(queue.next().await, Some("sooner"));
+/// assert_eq!(queue.next().await, Some("later"));
+/// assert_eq!(queue.next().await, None);
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct DelayQueue<T> {
+    entries: HashMap<u64, T>,
+    deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+    next_key: u64,
+    delay: Option<(Instant, Delay)>,
+    waker: Option<Waker>,
+}
+
+/// A handle to a value in a [`DelayQueue`], used to remove it before its deadline.
+///
+/// [`DelayQueue`]: struct.DelayQueue.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key(u64);
+
+impl<T> DelayQueue<T> {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            deadlines: BinaryHeap::new(),
+            next_key: 0,
+            delay: None,
+            waker: None,
+        }
+    }
+
+    /// Insert a value that is yielded once `dur` has elapsed.
+    #[inline]
+    pub fn insert(&mut self, value: T, dur: Duration) -> Key {
+        self.insert_at(value, super::now() + dur)
+    }
+
+    /// Insert a value that is yielded once `at` has been reached.
+    pub fn insert_at(&mut self, value: T, at: Instant) -> Key {
+        let key = self.next_key;
+        self.next_key += 1;
+        self.entries.insert(key, value);
+        self.deadlines.push(Reverse((at, key)));
+
+        // Wake up the task if the new value is due before the deadline it is waiting on.
+        let earlier = match &self.delay {
+            Some((deadline, _)) => at < *deadline,
+            None => true,
+        };
+        if earlier {
+            if let Some(waker) = self.waker.take() {
+                waker.wake();
+            }
+        }
+        Key(key)
+    }
+
+    /// Remove a value from the queue before its deadline has been reached.
+    ///
+    /// Returns `None` if the value has already been yielded or removed.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        let value = self.entries.remove(&key.0)?;
+        // Deadlines of removed values are skipped when they come up, but once they make up most
+        // of the heap they're dropped so that it doesn't keep growing.
+        if self.deadlines.len() > 2 * self.entries.len() {
+            let entries = &self.entries;
+            self.deadlines
+                .retain(|Reverse((_, key))| entries.contains_key(key));
+        }
+        Some(value)
+    }
+
+    /// Returns the number of values in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the queue contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Values are never pinned, so the queue can be moved freely.
+impl<T> Unpin for DelayQueue<T> {}
+
+impl<T> Stream for DelayQueue<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            // Entries that were removed are left in the heap and skipped here.
+            let (deadline, key) = match this.deadlines.peek() {
+                Some(Reverse(entry)) => *entry,
+                None => {
+                    this.delay = None;
+                    return Poll::Ready(None);
+                }
+            };
+            if !this.entries.contains_key(&key) {
+                this.deadlines.pop();
+                continue;
+            }
+
+            if deadline > super::now() {
+                match &mut this.delay {
+                    Some((at, _)) if *at == deadline => {}
+                    delay => *delay = Some((deadline, Delay::new_at(deadline))),
+                }
+                let (_, delay) = this.delay.as_mut().unwrap();
+                if delay.poll_unpin(cx).is_pending() {
+                    this.waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+
+            this.deadlines.pop();
+            this.delay = None;
+            return Poll::Ready(this.entries.remove(&key));
+        }
+    }
+}
@@ -1,16 +1,41 @@
 //! Extensions for Futures types.
 
 use pin_project::pin_project;
+use std::error::Error;
+use std::fmt;
 use std::future::Future;
 use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
-use futures::{AsyncRead, Stream};
+use futures::{AsyncRead, AsyncWrite, Stream};
 
 use super::Delay;
 
+/// The error returned when a [`Timeout`] future has elapsed before the inner
+/// future completed.
+///
+/// This can be converted into an `io::Error` of kind `TimedOut`.
+///
+/// [`Timeout`]: struct.Timeout.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("future timed out")
+    }
+}
+
+impl Error for Elapsed {}
+
+impl From<Elapsed> for io::Error {
+    fn from(err: Elapsed) -> Self {
+        io::Error::new(io::ErrorKind::TimedOut, err)
+    }
+}
+
 /// A future returned by methods in the [`FutureExt`] trait.
 ///
 /// [`FutureExt.timeout`]: trait.FutureExt.html
@@ -24,7 +49,7 @@ pub struct Timeout<F: Future> {
 }
 
 impl<F: Future> Future for Timeout<F> {
-    type Output = Result<F::Output, io::Error>;
+    type Output = Result<F::Output, Elapsed>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
@@ -33,9 +58,37 @@ impl<F: Future> Future for Timeout<F> {
             return Poll::Ready(Ok(t));
         }
 
-        this.delay
+        this.delay.poll(cx).map(|_| Err(Elapsed(())))
+    }
+}
+
+/// A future returned by [`FutureExt::timeout_or_return`].
+///
+/// [`FutureExt::timeout_or_return`]: trait.FutureExt.html#method.timeout_or_return
+#[derive(Debug)]
+pub struct TimeoutOrReturn<F: Future + Unpin> {
+    future: Option<F>,
+    delay: Delay,
+}
+
+impl<F: Future + Unpin> Future for TimeoutOrReturn<F> {
+    type Output = Result<F::Output, F>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let future = this
+            .future
+            .as_mut()
+            .expect("TimeoutOrReturn polled after completion");
+
+        if let Poll::Ready(t) = Pin::new(future).poll(cx) {
+            this.future = None;
+            return Poll::Ready(Ok(t));
+        }
+
+        Pin::new(&mut this.delay)
             .poll(cx)
-            .map(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "future timed out")))
+            .map(|_| Err(this.future.take().unwrap()))
     }
 }
 
@@ -49,8 +102,10 @@ pub trait FutureExt: Future + Sized {
     /// specified (relative to when this function is called).
     ///
     /// If the future completes before `dur` elapses then the future will
-    /// resolve with that item. Otherwise the future will resolve to an error
-    /// once `dur` has elapsed.
+    /// resolve with that item. Otherwise the future will resolve to an
+    /// [`Elapsed`] error once `dur` has elapsed.
+    ///
+    /// [`Elapsed`]: struct.Elapsed.html
     ///
     /// # Examples
     /// ```
@@ -60,7 +115,7 @@ pub trait FutureExt: Future + Sized {
     ///
     /// async fn long_future(dur: Duration) {
     ///     // Simulate some network operations...
-    ///     runtime::time::Delay::new(dur).await;
+    ///     runtime::time::sleep(dur).await;
     /// }
     ///
     /// #[runtime::main]
@@ -128,6 +183,43 @@ pub trait FutureExt: Future + Sized {
             future: self,
         }
     }
+
+    /// Creates a new future which will take at most `dur` time to resolve, and
+    /// hands back the unfinished future if it doesn't.
+    ///
+    /// This method is otherwise equivalent to the [`timeout`] method except that
+    /// instead of an error, the still-pending future is returned once `dur` has
+    /// elapsed. This makes it possible to keep polling it later on, for example
+    /// with a longer timeout.
+    ///
+    /// [`timeout`]: trait.FutureExt.html#method.timeout
+    ///
+    /// # Examples
+    /// ```
+    /// use runtime::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// #[runtime::main]
+    /// async fn main() {
+    ///     let future = runtime::time::sleep(Duration::from_millis(200));
+    ///
+    ///     let future = match future.timeout_or_return(Duration::from_millis(10)).await {
+    ///         Ok(_) => panic!("completed too early"),
+    ///         Err(future) => future,
+    ///     };
+    ///
+    ///     assert!(future.timeout(Duration::from_secs(5)).await.is_ok());
+    /// }
+    /// ```
+    fn timeout_or_return(self, dur: Duration) -> TimeoutOrReturn<Self>
+    where
+        Self: Unpin,
+    {
+        TimeoutOrReturn {
+            delay: Delay::new(dur),
+            future: Some(self),
+        }
+    }
 }
 
 impl<T: Future> FutureExt for T {}
@@ -143,6 +235,7 @@ pub struct TimeoutStream<S: Stream> {
     dur: Duration,
     #[pin]
     stream: S,
+    done: bool,
 }
 
 impl<S: Stream> Stream for TimeoutStream<S> {
@@ -151,8 +244,16 @@ impl<S: Stream> Stream for TimeoutStream<S> {
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
+        // Once the inner stream has ended, neither it nor the timer is polled again.
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
         if let Poll::Ready(s) = this.stream.as_mut().poll_next(cx) {
-            this.timeout.set(Delay::new(*this.dur));
+            match s {
+                Some(_) => this.timeout.set(Delay::new(*this.dur)),
+                None => *this.done = true,
+            }
             return Poll::Ready(Ok(s).transpose());
         }
 
@@ -166,7 +267,40 @@ impl<S: Stream> Stream for TimeoutStream<S> {
     }
 }
 
-/// Extend `Stream` with methods to time out execution.
+/// A stream returned by [`StreamExt::throttle`].
+///
+/// [`StreamExt::throttle`]: trait.StreamExt.html#method.throttle
+#[pin_project]
+#[derive(Debug)]
+pub struct Throttle<S: Stream> {
+    delay: Option<Delay>,
+    dur: Duration,
+    #[pin]
+    stream: S,
+}
+
+impl<S: Stream> Stream for Throttle<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if let Some(delay) = this.delay {
+            if Pin::new(delay).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            *this.delay = None;
+        }
+
+        let item = futures::ready!(this.stream.poll_next(cx));
+        if item.is_some() {
+            *this.delay = Some(Delay::new(*this.dur));
+        }
+        Poll::Ready(item)
+    }
+}
+
+/// Extend `Stream` with methods to time out and throttle execution.
 pub trait StreamExt: Stream + Sized {
     /// Creates a new stream which will take at most `dur` time to yield each
     /// item of the stream.
@@ -180,6 +314,9 @@ pub trait StreamExt: Stream + Sized {
     /// reset for the next item. If the timeout elapses, however, then an error
     /// will be yielded on the stream and the timer will be reset.
     ///
+    /// The returned stream is fused: once the inner stream has ended, it keeps
+    /// yielding `None` without polling the inner stream or the timer again.
+    ///
     /// ## Examples
     /// ```
     /// # use futures::prelude::*;
@@ -204,6 +341,40 @@ pub trait StreamExt: Stream + Sized {
             timeout: Delay::new(dur),
             dur,
             stream: self,
+            done: false,
+        }
+    }
+
+    /// Creates a new stream which waits at least `dur` between yielding
+    /// items.
+    ///
+    /// The first item is yielded as soon as it's ready. After that, the
+    /// underlying stream isn't polled again until `dur` has elapsed since the
+    /// previous item was yielded. Items are never dropped, only delayed.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use futures::prelude::*;
+    /// use runtime::time::StreamExt as _;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # #[runtime::main]
+    /// # async fn main () {
+    /// let start = Instant::now();
+    /// let items: Vec<_> = stream::iter(0..3)
+    ///     .throttle(Duration::from_millis(10))
+    ///     .collect()
+    ///     .await;
+    ///
+    /// assert_eq!(items, [0, 1, 2]);
+    /// assert!(start.elapsed() >= Duration::from_millis(20));
+    /// # }
+    /// ```
+    fn throttle(self, dur: Duration) -> Throttle<Self> {
+        Throttle {
+            delay: None,
+            dur,
+            stream: self,
         }
     }
 }
@@ -283,3 +454,100 @@ pub trait AsyncReadExt: AsyncRead + Sized {
 }
 
 impl<S: AsyncRead> AsyncReadExt for S {}
+
+/// A writer returned by methods in the [`AsyncWriteExt`] trait.
+///
+/// [`AsyncWriteExt`]: trait.AsyncWriteExt.html
+#[pin_project]
+#[derive(Debug)]
+pub struct TimeoutAsyncWrite<S: AsyncWrite> {
+    #[pin]
+    timeout: Delay,
+    dur: Duration,
+    #[pin]
+    stream: S,
+}
+
+impl<S: AsyncWrite> TimeoutAsyncWrite<S> {
+    /// Poll an operation on the inner writer, resetting the timer whenever it makes progress.
+    fn poll_timeout<T>(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        op: impl FnOnce(Pin<&mut S>, &mut Context<'_>) -> Poll<io::Result<T>>,
+    ) -> Poll<io::Result<T>> {
+        let mut this = self.project();
+
+        if let Poll::Ready(s) = op(this.stream.as_mut(), cx) {
+            this.timeout.set(Delay::new(*this.dur));
+            return Poll::Ready(s);
+        }
+
+        this.timeout.as_mut().poll(cx).map(|_| {
+            this.timeout.set(Delay::new(*this.dur));
+            Err(io::Error::new(io::ErrorKind::TimedOut, "future timed out"))
+        })
+    }
+}
+
+impl<S: AsyncWrite> AsyncWrite for TimeoutAsyncWrite<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        self.poll_timeout(cx, |stream, cx| stream.poll_write(cx, buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        self.poll_timeout(cx, |stream, cx| stream.poll_flush(cx))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        self.poll_timeout(cx, |stream, cx| stream.poll_close(cx))
+    }
+}
+
+/// Extend `AsyncWrite` with methods to time out execution.
+///
+/// This trait isn't part of the prelude, since its `timeout` method would be ambiguous with
+/// [`AsyncReadExt::timeout`] for types that implement both `AsyncRead` and `AsyncWrite`.
+///
+/// [`AsyncReadExt::timeout`]: trait.AsyncReadExt.html#method.timeout
+pub trait AsyncWriteExt: AsyncWrite + Sized {
+    /// Creates a new writer which will take at most `dur` time to complete each
+    /// write.
+    ///
+    /// This combinator creates a new writer which wraps the receiving writer
+    /// in a timeout-per-operation. Each write, flush, or close will resolve in
+    /// at most `dur` time. The first operation's timer starts when this method
+    /// is called.
+    ///
+    /// If an operation completes before `dur` elapses then the timer will be
+    /// reset for the next one. If the timeout elapses, however, then an error
+    /// of kind `TimedOut` will be returned and the timer will be reset.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// # #[runtime::main]
+    /// # async fn main () -> Result<(), Box<dyn std::error::Error + 'static + Send + Sync>> {
+    /// use futures::prelude::*;
+    /// use runtime::net::TcpStream;
+    /// use runtime::time::AsyncWriteExt as _;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let mut stream = stream.timeout(Duration::from_millis(100));
+    /// stream.write_all(b"hello world").await?;
+    /// # Ok(())}
+    /// ```
+    fn timeout(self, dur: Duration) -> TimeoutAsyncWrite<Self> {
+        TimeoutAsyncWrite {
+            timeout: Delay::new(dur),
+            dur,
+            stream: self,
+        }
+    }
+}
+
+impl<S: AsyncWrite> AsyncWriteExt for S {}
@@ -1,11 +1,20 @@
 use futures::prelude::*;
 
 use std::fmt;
+use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
+pub use runtime_raw::{InstantTick, MissedTickBehavior};
+
 /// A stream representing notifications at a fixed interval.
+///
+/// The stream never ends on its own, unless the runtime's timer fails, for example because it has
+/// shut down. A failed interval ends the stream instead of panicking, and never ticks again:
+/// [`tick`] resolves to the timer's error from then on.
+///
+/// [`tick`]: #method.tick
 #[must_use = "streams do nothing unless polled"]
 pub struct Interval {
     inner: Pin<Box<dyn runtime_raw::Interval>>,
@@ -36,6 +45,98 @@ impl Interval {
         let inner = runtime_raw::current_runtime().new_interval(dur);
         Self { inner }
     }
+
+    /// Create a stream that fires its first event at `at`, and then every `dur` after that.
+    ///
+    /// ## Examples
+    /// ```
+    /// # use futures::prelude::*;
+    /// use runtime::time::Interval;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # #[runtime::main]
+    /// # async fn main () {
+    /// let at = Instant::now() + Duration::from_millis(50);
+    /// let mut interval = Interval::new_at(at, Duration::from_millis(10));
+    ///
+    /// let first = interval.next().await.unwrap();
+    /// assert!(first >= at);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn new_at(at: Instant, dur: Duration) -> Self {
+        let inner = runtime_raw::current_runtime().new_interval_at(at, dur);
+        Self { inner }
+    }
+
+    /// Set the behavior used when the stream falls behind by one or more ticks.
+    ///
+    /// Defaults to [`MissedTickBehavior::Burst`].
+    ///
+    /// [`MissedTickBehavior::Burst`]: enum.MissedTickBehavior.html#variant.Burst
+    ///
+    /// ## Examples
+    /// ```
+    /// use runtime::time::{Interval, MissedTickBehavior};
+    /// use std::time::Duration;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main () {
+    /// let mut interval = Interval::new(Duration::from_millis(10));
+    /// interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.inner.as_mut().set_missed_tick_behavior(behavior)
+    }
+
+    /// Reset the interval so that the next tick fires one period from now.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.inner.as_mut().reset()
+    }
+
+    /// Wait for the next tick, and report how many deadlines were missed before it fired.
+    ///
+    /// This advances the interval like [`next`] does, but resolves to an [`InstantTick`] instead
+    /// of an `Instant`. The tick's `missed` field counts the periods that went by without the
+    /// interval being polled, which lets a lagging consumer tell how far behind it is.
+    ///
+    /// [`next`]: https://docs.rs/futures-preview/0.3.0-alpha.19/futures/stream/trait.StreamExt.html#method.next
+    /// [`InstantTick`]: struct.InstantTick.html
+    ///
+    /// Resolves to an error if the runtime's timer has failed, in which case the stream has ended.
+    ///
+    /// ## Examples
+    /// ```
+    /// use runtime::time::Interval;
+    /// use std::time::Duration;
+    ///
+    /// # #[runtime::main]
+    /// # async fn main () -> std::io::Result<()> {
+    /// let mut interval = Interval::new(Duration::from_millis(10));
+    /// let tick = interval.tick().await?;
+    /// if tick.missed > 0 {
+    ///     println!("fell behind by {} ticks", tick.missed);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn tick(&mut self) -> TickFuture<'_> {
+        TickFuture { interval: self }
+    }
+
+    /// Poll for the next tick, reporting how many deadlines were missed before it fired.
+    ///
+    /// This is the polling counterpart of [`tick`].
+    ///
+    /// [`tick`]: #method.tick
+    #[inline]
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<InstantTick>> {
+        self.inner.as_mut().poll_tick(cx)
+    }
 }
 
 impl Stream for Interval {
@@ -52,3 +153,20 @@ impl fmt::Debug for Interval {
         fmt::Debug::fmt(&self.inner, f)
     }
 }
+
+/// The future returned by [`Interval::tick`].
+///
+/// [`Interval::tick`]: struct.Interval.html#method.tick
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TickFuture<'a> {
+    interval: &'a mut Interval,
+}
+
+impl Future for TickFuture<'_> {
+    type Output = io::Result<InstantTick>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.interval.poll_tick(cx)
+    }
+}
@@ -0,0 +1,95 @@
+use pin_project::pin_project;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Measures the time elapsed since it was started.
+///
+/// Time is measured against the current runtime's clock, like delays and intervals, so it keeps
+/// working when the runtime controls time itself. Since a stopwatch is a plain value, it can be
+/// kept across `.await` points and moved between tasks.
+///
+/// # Examples
+/// ```
+/// use runtime::time::{sleep, Stopwatch};
+/// use std::time::Duration;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let stopwatch = Stopwatch::start();
+///     sleep(Duration::from_millis(10)).await;
+///     assert!(stopwatch.elapsed() >= Duration::from_millis(10));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    start: Instant,
+}
+
+impl Stopwatch {
+    /// Start a new stopwatch.
+    pub fn start() -> Self {
+        Self {
+            start: super::now(),
+        }
+    }
+
+    /// Returns the time elapsed since the stopwatch was started or last reset.
+    pub fn elapsed(&self) -> Duration {
+        super::now() - self.start
+    }
+
+    /// Start measuring from now again.
+    pub fn reset(&mut self) {
+        self.start = super::now();
+    }
+}
+
+/// Wrap a future to report how long it took to complete.
+///
+/// The returned future resolves to the output of `future` along with the time elapsed between
+/// calling this function and the future completing. Like [`Stopwatch`], time is measured against
+/// the current runtime's clock.
+///
+/// [`Stopwatch`]: struct.Stopwatch.html
+///
+/// # Examples
+/// ```
+/// use runtime::time::{sleep, timed};
+/// use std::time::Duration;
+///
+/// #[runtime::main]
+/// async fn main() {
+///     let (_, elapsed) = timed(sleep(Duration::from_millis(10))).await;
+///     println!("slept for {}ms", elapsed.as_millis());
+/// }
+/// ```
+pub fn timed<F: Future>(future: F) -> Timed<F> {
+    Timed {
+        future,
+        stopwatch: Stopwatch::start(),
+    }
+}
+
+/// The future returned by [`timed`].
+///
+/// [`timed`]: fn.timed.html
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Timed<F: Future> {
+    #[pin]
+    future: F,
+    stopwatch: Stopwatch,
+}
+
+impl<F: Future> Future for Timed<F> {
+    type Output = (F::Output, Duration);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = futures::ready!(this.future.poll(cx));
+        Poll::Ready((output, this.stopwatch.elapsed()))
+    }
+}
@@ -0,0 +1,55 @@
+//! Every combination of runtime argument and options accepted by the attributes.
+
+use std::time::Duration;
+
+#[runtime::test]
+async fn no_arguments() {
+    runtime::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[runtime::test(runtime_native::Native)]
+async fn runtime_only() {
+    runtime::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[runtime::test(timeout_ms = 5000)]
+async fn options_only() {
+    runtime::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[runtime::test(runtime_native::Native, timeout_ms = 5000)]
+async fn runtime_and_options() {
+    runtime::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[runtime::test(runtime_native::Native, threads = 2, timeout_ms = 5000)]
+async fn runtime_and_multiple_options() {
+    assert_eq!(runtime::task::worker_threads(), Some(2));
+    runtime::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[runtime::test(threads = 3)]
+async fn options_size_their_own_runtime() {
+    assert_eq!(runtime::task::worker_threads(), Some(3));
+    let spawned = runtime::spawn(async { runtime::task::worker_threads() });
+    assert_eq!(spawned.await.unwrap(), Some(3));
+}
+
+#[runtime::test]
+async fn result_ok() -> std::io::Result<()> {
+    runtime::time::sleep(Duration::from_millis(1)).await;
+    Ok(())
+}
+
+#[runtime::test]
+#[should_panic(expected = "the test failed")]
+async fn result_err() -> Result<(), String> {
+    runtime::time::sleep(Duration::from_millis(1)).await;
+    Err("the test failed".to_string())
+}
+
+#[runtime::test(timeout_ms = 5000)]
+#[should_panic(expected = "the test failed")]
+async fn result_err_with_timeout() -> Result<(), String> {
+    Err("the test failed".to_string())
+}
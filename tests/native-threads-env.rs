@@ -0,0 +1,29 @@
+use runtime_native::Native;
+
+use std::env;
+use std::sync::{Arc, Barrier};
+
+// The environment variable is read when the thread pool starts, so this test lives in its own
+// binary.
+#[test]
+fn runtime_threads_env() {
+    env::set_var("RUNTIME_THREADS", "3");
+
+    runtime::raw::enter(Native, async {
+        assert_eq!(runtime::task::worker_threads(), Some(3));
+
+        // Each task blocks its worker thread until all of them are running at the same time.
+        let barrier = Arc::new(Barrier::new(3));
+        let tasks: Vec<_> = (0..3)
+            .map(|_| {
+                let barrier = barrier.clone();
+                runtime::spawn(async move {
+                    barrier.wait();
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+    });
+}
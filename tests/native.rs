@@ -1,3 +1,4 @@
+use runtime::net::{TcpListener, TcpStream, UdpSocket};
 use runtime_native::Native;
 
 #[runtime::test(Native)]
@@ -6,5 +7,2022 @@ async fn spawn() {
         println!("hello planet from Native");
         42
     });
-    assert_eq!(handle.await, 42);
+    assert_eq!(handle.await.unwrap(), 42);
+}
+
+#[runtime::test(Native)]
+async fn capabilities() {
+    assert_eq!(runtime::capabilities(), runtime::Capabilities::all());
+}
+
+#[runtime::test(Native)]
+async fn fs_file() -> std::io::Result<()> {
+    use futures::prelude::*;
+    use runtime::fs::File;
+
+    let path = std::env::temp_dir().join(format!("runtime-file-{}", std::process::id()));
+
+    let mut file = File::create(&path).await?;
+    file.write_all(b"hello world").await?;
+    file.flush().await?;
+
+    let mut file = File::open(&path).await?;
+    assert_eq!(file.metadata().await?.len(), 11);
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents).await?;
+    assert_eq!(contents, b"hello world");
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn fs_file_seek() -> std::io::Result<()> {
+    use futures::prelude::*;
+    use runtime::fs::File;
+    use std::io::SeekFrom;
+
+    let path = std::env::temp_dir().join(format!("runtime-file-seek-{}", std::process::id()));
+
+    let mut file = File::create(&path).await?;
+    file.write_all(b"0123456789").await?;
+    file.flush().await?;
+
+    let mut file = File::open(&path).await?;
+    assert_eq!(file.seek(SeekFrom::Start(5)).await?, 5);
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents).await?;
+    assert_eq!(contents, b"56789");
+
+    // Seeking relative to the current position accounts for bytes that were read ahead.
+    assert_eq!(file.seek(SeekFrom::Start(0)).await?, 0);
+    let mut prefix = [0; 2];
+    file.read_exact(&mut prefix).await?;
+    assert_eq!(file.seek(SeekFrom::Current(3)).await?, 5);
+    contents.clear();
+    file.read_to_end(&mut contents).await?;
+    assert_eq!(contents, b"56789");
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn fs_read_write() -> std::io::Result<()> {
+    let path = std::env::temp_dir().join(format!("runtime-read-write-{}", std::process::id()));
+
+    runtime::fs::write(&path, "hello world").await?;
+    assert_eq!(runtime::fs::read_to_string(&path).await?, "hello world");
+    assert_eq!(runtime::fs::read(&path).await?, b"hello world");
+
+    std::fs::remove_file(&path)?;
+    let err = runtime::fs::read_to_string(&path).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn fs_read_dir() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let dir = std::env::temp_dir().join(format!("runtime-read-dir-{}", std::process::id()));
+    std::fs::create_dir(&dir)?;
+    for name in &["a", "b", "c"] {
+        runtime::fs::write(dir.join(name), name).await?;
+    }
+
+    let mut names = Vec::new();
+    let mut entries = runtime::fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next().await {
+        let entry = entry?;
+        assert!(entry.metadata().await?.is_file());
+        names.push(entry.file_name().into_string().unwrap());
+    }
+    names.sort();
+    assert_eq!(names, ["a", "b", "c"]);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn duplex() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let (mut client, server) = runtime::io::duplex(64);
+
+    let echo = runtime::spawn(async move {
+        let (reader, writer) = &mut server.split();
+        reader.copy_into(writer).await
+    });
+
+    client.write_all(b"hello world").await?;
+    let mut buf = [0u8; 11];
+    client.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"hello world");
+
+    client.close().await?;
+    assert_eq!(echo.await??, 11);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn duplex_backpressure() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let (mut a, mut b) = runtime::io::duplex(4);
+
+    // Only four bytes fit in the buffer, so the write can't complete until they've been read.
+    let mut write = a.write_all(b"hello");
+    assert!(futures::poll!(&mut write).is_pending());
+
+    let mut buf = [0u8; 4];
+    b.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"hell");
+    write.await?;
+
+    drop(a);
+    let mut rest = Vec::new();
+    b.read_to_end(&mut rest).await?;
+    assert_eq!(rest, b"o");
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn copy_with_capacity() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let (mut a, b) = runtime::io::duplex(4);
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+    // With a single byte of capacity, every byte is read and written on its own.
+    let copy = runtime::spawn(async move {
+        let mut received = Vec::new();
+        let bytes = runtime::io::copy_with_capacity(b, &mut received, 1).await?;
+        Ok::<_, std::io::Error>((bytes, received))
+    });
+
+    a.write_all(&data).await?;
+    drop(a);
+    let (bytes, received) = copy.await??;
+    assert_eq!(bytes, 1000);
+    assert_eq!(received, data);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn copy_bidirectional() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let (mut client, mut proxy_client) = runtime::io::duplex(64);
+    let (mut proxy_server, mut server) = runtime::io::duplex(64);
+
+    let proxy = runtime::spawn(async move {
+        runtime::io::copy_bidirectional(&mut proxy_client, &mut proxy_server).await
+    });
+
+    // Closing the client's side is forwarded to the server, which can still respond.
+    client.write_all(b"ping").await?;
+    client.close().await?;
+    let mut buf = Vec::new();
+    server.read_to_end(&mut buf).await?;
+    assert_eq!(buf, b"ping");
+
+    server.write_all(b"pong pong").await?;
+    server.close().await?;
+    let mut buf = Vec::new();
+    client.read_to_end(&mut buf).await?;
+    assert_eq!(buf, b"pong pong");
+
+    assert_eq!(proxy.await??, (4, 9));
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_echo() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let handle = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (reader, mut writer) = stream.split();
+        reader.copy_into(&mut writer).await
+    });
+
+    let mut stream = TcpStream::connect(addr).await?;
+    stream.write_all(b"hello").await?;
+    stream.flush().await?;
+    let mut buf = [0; 5];
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"hello");
+
+    stream.shutdown(std::net::Shutdown::Write)?;
+    assert_eq!(handle.await??, 5);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_flush() -> std::io::Result<()> {
+    use futures::prelude::*;
+    use futures::task::Poll;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut stream = TcpStream::connect(listener.local_addr()?).await?;
+    let (mut accepted, _) = listener.accept().await?;
+
+    // Writes go straight to the socket, so there's nothing to wait for when flushing.
+    stream.write_all(b"hello").await?;
+    match futures::poll!(stream.flush()) {
+        Poll::Ready(res) => res?,
+        Poll::Pending => panic!("flushing a writable socket should complete immediately"),
+    }
+
+    let mut buf = [0; 5];
+    accepted.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"hello");
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_nodelay() -> std::io::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+
+    stream.set_nodelay(true)?;
+    assert!(stream.nodelay()?);
+    stream.set_nodelay(false)?;
+    assert!(!stream.nodelay()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_ttl() -> std::io::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+
+    stream.set_ttl(42)?;
+    assert_eq!(stream.ttl()?, 42);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_try_clone() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let server = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (reader, writer) = &mut stream.split();
+        reader.copy_into(writer).await
+    });
+
+    let mut writer = TcpStream::connect(addr).await?;
+    let mut reader = writer.try_clone()?;
+
+    writer.write_all(b"hello world").await?;
+    let mut buf = [0u8; 11];
+    reader.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"hello world");
+
+    drop((reader, writer));
+    assert_eq!(server.await??, 11);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_connect_timeout() -> std::io::Result<()> {
+    use runtime::time::FutureExt;
+    use std::time::{Duration, Instant};
+
+    // A listener that never accepts stops completing handshakes once its queue is full, so
+    // connecting to it hangs until the SYN is retransmitted, which takes at least a second.
+    let full = TcpListener::bind_with_backlog("127.0.0.1:0", 0)?;
+    let addr = full.local_addr()?;
+    let mut queued = Vec::new();
+    for _ in 0..4 {
+        let connect = TcpStream::connect(addr);
+        match connect.timeout(Duration::from_millis(100)).await {
+            Ok(stream) => queued.push(stream?),
+            Err(_) => break,
+        }
+    }
+
+    let start = Instant::now();
+    let err = TcpStream::connect_timeout(addr, Duration::from_millis(100))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    assert!(start.elapsed() < Duration::from_millis(900));
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_into_split() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let server = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (reader, writer) = &mut stream.split();
+        reader.copy_into(writer).await
+    });
+
+    let stream = TcpStream::connect(addr).await?;
+    let (mut reader, mut writer) = stream.into_split();
+
+    let write = runtime::spawn(async move {
+        // Dropping the write half at the end of the task shuts down the write direction.
+        writer.write_all(b"hello world").await
+    });
+    let read = runtime::spawn(async move {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.map(|_| buf)
+    });
+
+    write.await??;
+    assert_eq!(read.await??, b"hello world");
+    assert_eq!(server.await??, 11);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_read_eof() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let handle = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        stream.shutdown(std::net::Shutdown::Write)?;
+        Ok::<_, std::io::Error>(stream)
+    });
+
+    let mut stream = TcpStream::connect(addr).await?;
+    let _peer = handle.await??;
+    assert!(!stream.read_eof());
+    let mut buf = [0; 16];
+    assert_eq!(stream.read(&mut buf).await?, 0);
+    assert!(stream.read_eof());
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_shutdown_on_drop() -> std::io::Result<()> {
+    use futures::prelude::*;
+    use std::net::Shutdown;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut stream = TcpStream::connect(listener.local_addr()?).await?;
+    let (mut peer, _) = listener.accept().await?;
+
+    assert_eq!(stream.shutdown_on_drop(), None);
+    stream.set_shutdown_on_drop(Some(Shutdown::Write));
+    assert_eq!(stream.shutdown_on_drop(), Some(Shutdown::Write));
+    stream.write_all(b"goodbye").await?;
+    drop(stream);
+
+    let mut buf = Vec::new();
+    peer.read_to_end(&mut buf).await?;
+    assert_eq!(buf, b"goodbye");
+    assert!(peer.read_eof());
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_split() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let server = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (reader, writer) = &mut stream.split();
+        reader.copy_into(writer).await
+    });
+
+    let mut stream = TcpStream::connect(addr).await?;
+    let (mut reader, mut writer) = TcpStream::split(&mut stream);
+
+    // Read the echo on one half while writing on the other, from within the same task.
+    let mut buf = vec![0u8; 11];
+    let (written, read) = futures::join!(
+        writer.write_all(b"hello world"),
+        reader.read_exact(&mut buf),
+    );
+    written?;
+    read?;
+    assert_eq!(buf, b"hello world");
+
+    drop((reader, writer));
+    drop(stream);
+    assert_eq!(server.await??, 11);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_buf_reader_lines() -> std::io::Result<()> {
+    use futures::prelude::*;
+    use runtime::io::{AsyncBufReadExt, BufReader, BufWriter};
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let server = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let mut writer = BufWriter::new(stream);
+        writer.write_all(b"hello\n").await?;
+        writer.write_all(b"world\n").await?;
+        writer.flush().await
+    });
+
+    let stream = TcpStream::connect(addr).await?;
+    let lines: Vec<String> = BufReader::new(stream).lines().try_collect().await?;
+    assert_eq!(lines, ["hello", "world"]);
+    server.await??;
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_write_timeout() -> std::io::Result<()> {
+    use futures::prelude::*;
+    use runtime::time::AsyncWriteExt as _;
+    use std::time::Duration;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let stream = TcpStream::connect(addr).await?;
+    // Accept the connection but never read from it, so the send buffer eventually fills up.
+    let (_peer, _) = listener.accept().await?;
+
+    let mut stream = stream.timeout(Duration::from_millis(100));
+    let buf = vec![0u8; 64 * 1024 * 1024];
+    let err = stream.write_all(&buf).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_readiness() -> std::io::Result<()> {
+    use futures::future;
+    use futures::prelude::*;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut stream = TcpStream::connect(listener.local_addr()?).await?;
+    let (mut peer, _) = listener.accept().await?;
+
+    future::poll_fn(|cx| stream.poll_write_ready(cx)).await?;
+
+    peer.write_all(b"ping").await?;
+    future::poll_fn(|cx| stream.poll_read_ready(cx)).await?;
+    let mut buf = [0u8; 4];
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"ping");
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_linger() -> std::io::Result<()> {
+    use std::time::Duration;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+
+    stream.set_linger(Some(Duration::from_secs(1)))?;
+    assert_eq!(stream.linger()?, Some(Duration::from_secs(1)));
+    stream.set_linger(None)?;
+    assert_eq!(stream.linger()?, None);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_take_error() -> std::io::Result<()> {
+    use std::time::Duration;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+    assert!(stream.take_error()?.is_none());
+
+    // Closing with a zero linger makes the peer send a reset.
+    let (peer, _) = listener.accept().await?;
+    peer.set_linger(Some(Duration::from_secs(0)))?;
+    drop(peer);
+
+    runtime::time::Delay::new(Duration::from_millis(50)).await;
+    let err = stream.take_error()?.expect("expected a socket error");
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionReset);
+    assert!(stream.take_error()?.is_none());
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_listener_from_std() -> std::io::Result<()> {
+    let std_listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let mut listener = TcpListener::from_std(std_listener)?;
+    let addr = listener.local_addr()?;
+
+    let stream = TcpStream::connect(addr).await?;
+    let (accepted, _) = listener.accept().await?;
+    assert_eq!(accepted.peer_addr()?, stream.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_listener_stream() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    // The listener is moved into the stream combinator, and dropped once it's done.
+    let accepted = runtime::spawn(listener.take(2).try_collect::<Vec<_>>());
+
+    let first = TcpStream::connect(addr).await?;
+    let second = TcpStream::connect(addr).await?;
+    let accepted = accepted.await??;
+    assert_eq!(accepted.len(), 2);
+    assert_eq!(accepted[0].peer_addr()?, first.local_addr()?);
+    assert_eq!(accepted[1].peer_addr()?, second.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_listener_ttl() -> std::io::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+
+    listener.set_ttl(42)?;
+    assert_eq!(listener.ttl()?, 42);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_accept_peer_addr() -> std::io::Result<()> {
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+
+    let (_, addr) = listener.accept().await?;
+    assert_eq!(addr, stream.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_cached_addrs() -> std::io::Result<()> {
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+    let (accepted, _) = listener.accept().await?;
+
+    let peer_addr = stream.peer_addr()?;
+    assert_eq!(peer_addr, listener.local_addr()?);
+    assert_eq!(stream.local_addr()?, accepted.peer_addr()?);
+    assert_eq!(accepted.local_addr()?, peer_addr);
+    for _ in 0..10 {
+        assert_eq!(stream.peer_addr()?, peer_addr);
+    }
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_listener_bind_addr() -> std::io::Result<()> {
+    use std::net::SocketAddr;
+
+    let listener = TcpListener::bind_addr(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+    let addr = listener.local_addr()?;
+    assert!(addr.ip().is_loopback());
+    assert_ne!(addr.port(), 0);
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[runtime::test(Native)]
+async fn tcp_listener_bind_device() -> std::io::Result<()> {
+    // Kernels before 5.7 require `CAP_NET_RAW`, which the tests may not run with.
+    let mut listener = match TcpListener::bind_device("127.0.0.1:0", "lo") {
+        Err(ref err) if err.kind() == std::io::ErrorKind::PermissionDenied => return Ok(()),
+        listener => listener?,
+    };
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+
+    let (_, addr) = listener.accept().await?;
+    assert_eq!(addr, stream.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_listener_bind_with_backlog() -> std::io::Result<()> {
+    let mut listener = TcpListener::bind_with_backlog("127.0.0.1:0", 1024)?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+
+    let (_, addr) = listener.accept().await?;
+    assert_eq!(addr, stream.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn lookup_host() -> std::io::Result<()> {
+    let mut addrs = runtime::net::lookup_host("localhost:0").await?;
+    assert!(addrs.next().is_some());
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn lookup_host_literal() -> std::io::Result<()> {
+    // IP address literals are converted without a lookup, and can be borrowed.
+    let addr = String::from("127.0.0.1:8080");
+    let addrs: Vec<_> = runtime::net::lookup_host(&addr).await?.collect();
+    assert_eq!(addrs, vec![addr.parse().unwrap()]);
+
+    let addrs: Vec<_> = runtime::net::lookup_host(("::1", 8080)).await?.collect();
+    assert_eq!(addrs, vec!["[::1]:8080".parse().unwrap()]);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_connect_host_name() -> std::io::Result<()> {
+    // `localhost` usually resolves to `::1` as well, which refuses the connection since the
+    // listener is only bound to the IPv4 loopback address.
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let stream = TcpStream::connect(("localhost", port)).await?;
+
+    let (_, addr) = listener.accept().await?;
+    assert_eq!(addr, stream.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_connect_detailed() -> std::io::Result<()> {
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let good = listener.local_addr()?;
+    // Nothing listens on the address of a dropped listener, so connecting to it is refused.
+    let bad = TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+
+    let stream = TcpStream::connect_detailed(&[bad, good][..]).await.unwrap();
+    let (_, addr) = listener.accept().await?;
+    assert_eq!(addr, stream.local_addr()?);
+
+    let err = TcpStream::connect_detailed(&[bad, bad][..])
+        .await
+        .unwrap_err();
+    assert_eq!(err.attempts().len(), 2);
+    for (addr, err) in err.attempts() {
+        assert_eq!(*addr, bad);
+        assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+    }
+    let err: std::io::Error = err.into();
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn tcp_connect_happy_eyeballs() -> std::io::Result<()> {
+    use runtime::time::FutureExt;
+    use std::time::{Duration, Instant};
+
+    // A listener that never accepts stops completing handshakes once its queue is full, so
+    // connecting to it hangs until the SYN is retransmitted, which takes at least a second.
+    let full = TcpListener::bind_with_backlog("127.0.0.1:0", 0)?;
+    let slow = full.local_addr()?;
+    let mut queued = Vec::new();
+    for _ in 0..4 {
+        let connect = TcpStream::connect(slow);
+        match connect.timeout(Duration::from_millis(100)).await {
+            Ok(stream) => queued.push(stream?),
+            Err(_) => break,
+        }
+    }
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let fast = listener.local_addr()?;
+    let start = Instant::now();
+    let stream = TcpStream::connect(&[slow, fast][..]).await?;
+    assert!(start.elapsed() >= Duration::from_millis(250));
+    assert!(start.elapsed() < Duration::from_millis(900));
+    assert_eq!(stream.peer_addr()?, fast);
+    listener.accept().await?;
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_echo() -> std::io::Result<()> {
+    let mut server = UdpSocket::bind("127.0.0.1:0")?;
+    let server_addr = server.local_addr()?;
+
+    let handle = runtime::spawn(async move {
+        let mut buf = [0u8; 16];
+        let (len, peer) = server.recv_from(&mut buf).await?;
+        server.send_to(&buf[..len], peer).await?;
+        Ok::<(), std::io::Error>(())
+    });
+
+    let mut client = UdpSocket::bind("127.0.0.1:0")?;
+    assert_eq!(client.send_to(b"hello", server_addr).await?, 5);
+    let mut buf = [0u8; 16];
+    let (len, peer) = client.recv_from(&mut buf).await?;
+    assert_eq!(&buf[..len], b"hello");
+    assert_eq!(peer, server_addr);
+
+    handle.await??;
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_split() -> std::io::Result<()> {
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    let addr = socket.local_addr()?;
+    let (mut send, mut recv) = socket.split();
+
+    let received = runtime::spawn(async move {
+        let mut buf = [0u8; 16];
+        let (len, peer) = recv.recv_from(&mut buf).await?;
+        Ok::<_, std::io::Error>((buf[..len].to_vec(), peer))
+    });
+
+    assert_eq!(send.send_to(b"hello", addr).await?, 5);
+    let (buf, peer) = received.await??;
+    assert_eq!(buf, b"hello");
+    assert_eq!(peer, addr);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_connect() -> std::io::Result<()> {
+    let mut a = UdpSocket::bind("127.0.0.1:0")?;
+    let mut b = UdpSocket::bind("127.0.0.1:0")?;
+    a.connect(b.local_addr()?)?;
+    b.connect(a.local_addr()?)?;
+
+    assert_eq!(a.send(b"hello").await?, 5);
+    let mut buf = [0; 16];
+    let recv = b.recv(&mut buf).await?;
+    assert_eq!(&buf[..recv], b"hello");
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_peek_from() -> std::io::Result<()> {
+    let mut a = UdpSocket::bind("127.0.0.1:0")?;
+    let mut b = UdpSocket::bind("127.0.0.1:0")?;
+    a.send_to(b"hello", b.local_addr()?).await?;
+
+    let mut peeked = [0; 16];
+    let (peek_len, peek_addr) = b.peek_from(&mut peeked).await?;
+    let mut received = [0; 16];
+    let (recv_len, recv_addr) = b.recv_from(&mut received).await?;
+
+    assert_eq!(&peeked[..peek_len], &received[..recv_len]);
+    assert_eq!(peek_addr, recv_addr);
+    assert_eq!(recv_addr, a.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_peek_from_pending() -> std::io::Result<()> {
+    use std::time::Duration;
+
+    let mut a = UdpSocket::bind("127.0.0.1:0")?;
+    let mut b = UdpSocket::bind("127.0.0.1:0")?;
+    let addr = b.local_addr()?;
+    let sender = runtime::spawn(async move {
+        runtime::time::sleep(Duration::from_millis(10)).await;
+        a.send_to(b"hello", addr).await
+    });
+
+    // Peeking waits for a datagram, and only looks at as much of it as fits.
+    let mut peeked = [0; 2];
+    let (peek_len, _) = b.peek_from(&mut peeked).await?;
+    assert_eq!(&peeked[..peek_len], b"he");
+    sender.await.unwrap()?;
+
+    let mut received = [0; 16];
+    let (recv_len, _) = b.recv_from(&mut received).await?;
+    assert_eq!(&received[..recv_len], b"hello");
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_vectored() -> std::io::Result<()> {
+    use std::io::{IoSlice, IoSliceMut};
+
+    let mut a = UdpSocket::bind("127.0.0.1:0")?;
+    let mut b = UdpSocket::bind("127.0.0.1:0")?;
+
+    let bufs = [IoSlice::new(b"hello "), IoSlice::new(b"world")];
+    assert_eq!(a.send_to_vectored(&bufs, b.local_addr()?).await?, 11);
+    let mut received = [0; 16];
+    let (len, addr) = b.recv_from(&mut received).await?;
+    assert_eq!(&received[..len], b"hello world");
+    assert_eq!(addr, a.local_addr()?);
+
+    a.send_to(b"hello world", b.local_addr()?).await?;
+    let (mut head, mut tail) = ([0; 4], [0; 16]);
+    let mut bufs = [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)];
+    let (len, _) = b.recv_from_vectored(&mut bufs).await?;
+    assert_eq!(len, 11);
+    assert_eq!(&head, b"hell");
+    assert_eq!(&tail[..7], b"o world");
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[runtime::test(Native)]
+async fn udp_batch() -> std::io::Result<()> {
+    let mut a = UdpSocket::bind("127.0.0.1:0")?;
+    let mut b = UdpSocket::bind("127.0.0.1:0")?;
+
+    let peer = b.local_addr()?;
+    let packets = [
+        (&b"one"[..], peer),
+        (&b"two"[..], peer),
+        (&b"three"[..], peer),
+        (&b"four"[..], peer),
+    ];
+    assert_eq!(a.send_batch(&packets).await?, 4);
+
+    let mut storage = [[0; 16]; 4];
+    let mut received = Vec::new();
+    while received.len() < 4 {
+        let mut bufs: Vec<&mut [u8]> = storage[received.len()..]
+            .iter_mut()
+            .map(|buf| &mut buf[..])
+            .collect();
+        received.extend(b.recv_batch(&mut bufs).await?);
+    }
+
+    for (i, (len, addr)) in received.into_iter().enumerate() {
+        assert_eq!(&storage[i][..len], packets[i].0);
+        assert_eq!(addr, a.local_addr()?);
+    }
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_peer_addr() -> std::io::Result<()> {
+    let a = UdpSocket::bind("127.0.0.1:0")?;
+    let b = UdpSocket::bind("127.0.0.1:0")?;
+    assert_eq!(
+        a.peer_addr().unwrap_err().kind(),
+        std::io::ErrorKind::NotConnected
+    );
+
+    a.connect(b.local_addr()?)?;
+    assert_eq!(a.peer_addr()?, b.local_addr()?);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_multicast_if_v4() -> std::io::Result<()> {
+    use std::net::Ipv4Addr;
+
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    socket.set_multicast_if_v4(&Ipv4Addr::UNSPECIFIED)?;
+    assert_eq!(socket.multicast_if_v4()?, Ipv4Addr::UNSPECIFIED);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_bind_addr() -> std::io::Result<()> {
+    use std::net::SocketAddr;
+
+    let socket = UdpSocket::bind_addr(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+    let addr = socket.local_addr()?;
+    assert!(addr.ip().is_loopback());
+    assert_ne!(addr.port(), 0);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_tos() -> std::io::Result<()> {
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    socket.set_tos(0x10)?;
+    assert_eq!(socket.tos()?, 0x10);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[runtime::test(Native)]
+async fn udp_bind_reuseport() -> std::io::Result<()> {
+    let first = UdpSocket::bind_reuseport("127.0.0.1:0")?;
+    let addr = first.local_addr()?;
+    let second = UdpSocket::bind_reuseport(addr)?;
+    assert_eq!(second.local_addr()?, addr);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_socket_from_std() -> std::io::Result<()> {
+    let std_socket = std::net::UdpSocket::bind("127.0.0.1:0")?;
+    let mut socket = UdpSocket::from_std(std_socket)?;
+    let addr = socket.local_addr()?;
+
+    socket.send_to(b"hello", addr).await?;
+    let mut buf = [0; 16];
+    let (recv, peer) = socket.recv_from(&mut buf).await?;
+    assert_eq!(&buf[..recv], b"hello");
+    assert_eq!(peer, addr);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn udp_buffer_sizes() -> std::io::Result<()> {
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+
+    // The OS may double the requested size, so only check the lower bound.
+    socket.set_recv_buffer_size(64 * 1024)?;
+    assert!(socket.recv_buffer_size()? >= 64 * 1024);
+    socket.set_send_buffer_size(64 * 1024)?;
+    assert!(socket.send_buffer_size()? >= 64 * 1024);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[runtime::test(Native)]
+async fn unix_stream_echo() -> std::io::Result<()> {
+    use futures::prelude::*;
+    use runtime::os::unix::net::{UnixListener, UnixStream};
+
+    let path = std::env::temp_dir().join(format!("runtime-echo-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut listener = UnixListener::bind(&path)?;
+
+    let handle = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (reader, mut writer) = stream.split();
+        reader.copy_into(&mut writer).await?;
+        Ok::<(), std::io::Error>(())
+    });
+
+    let mut stream = UnixStream::connect(&path).await?;
+    stream.write_all(b"hello").await?;
+    let mut buf = [0; 5];
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"hello");
+
+    stream.shutdown(std::net::Shutdown::Write)?;
+    handle.await??;
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[runtime::test(Native)]
+async fn unix_datagram_pair() -> std::io::Result<()> {
+    use runtime::os::unix::net::UnixDatagram;
+
+    let (mut a, mut b) = UnixDatagram::pair()?;
+    assert_eq!(a.send(b"hello").await?, 5);
+    let mut buf = [0; 16];
+    let recv = b.recv(&mut buf).await?;
+    assert_eq!(&buf[..recv], b"hello");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[runtime::test(Native)]
+async fn unix_datagram_connect() -> std::io::Result<()> {
+    use runtime::os::unix::net::UnixDatagram;
+
+    let dir = std::env::temp_dir();
+    let a_path = dir.join(format!("runtime-dgram-a-{}.sock", std::process::id()));
+    let b_path = dir.join(format!("runtime-dgram-b-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&a_path);
+    let _ = std::fs::remove_file(&b_path);
+
+    let mut a = UnixDatagram::bind(&a_path)?;
+    let mut b = UnixDatagram::bind(&b_path)?;
+    a.connect(&b_path)?;
+    assert_eq!(a.peer_addr()?.as_pathname(), Some(b_path.as_path()));
+
+    assert_eq!(a.send(b"hello").await?, 5);
+    let mut buf = [0; 16];
+    let recv = b.recv(&mut buf).await?;
+    assert_eq!(&buf[..recv], b"hello");
+
+    std::fs::remove_file(&a_path)?;
+    std::fs::remove_file(&b_path)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[runtime::test(Native)]
+async fn unix_datagram_shutdown() -> std::io::Result<()> {
+    use runtime::os::unix::net::UnixDatagram;
+
+    let (mut a, _b) = UnixDatagram::pair()?;
+    a.shutdown(std::net::Shutdown::Write)?;
+
+    let err = a.send(b"hello").await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+
+    // Shutting down the read half wakes up a pending `recv`, which then reports end-of-file. The
+    // socket is shut down through a clone, since the `recv` future borrows it mutably.
+    let (std_socket, _peer) = std::os::unix::net::UnixDatagram::pair()?;
+    let clone = std_socket.try_clone()?;
+    let mut socket = UnixDatagram::from_std(std_socket)?;
+    let mut buf = [0; 16];
+    let mut recv = socket.recv(&mut buf);
+    assert!(futures::poll!(&mut recv).is_pending());
+    clone.shutdown(std::net::Shutdown::Read)?;
+    assert_eq!(recv.await?, 0);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[runtime::test(Native)]
+async fn unix_datagram_from_std() -> std::io::Result<()> {
+    use runtime::os::unix::net::UnixDatagram;
+    use std::os::unix::io::AsRawFd;
+
+    let path = std::env::temp_dir().join(format!("runtime-from-std-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut peer = UnixDatagram::bind(&path)?;
+
+    let std_socket = std::os::unix::net::UnixDatagram::unbound()?;
+    let fd = std_socket.as_raw_fd();
+    let mut socket = UnixDatagram::from_std(std_socket)?;
+    assert_eq!(socket.as_raw_fd(), fd);
+
+    assert_eq!(socket.send_to(b"hello", &path).await?, 5);
+    let mut buf = [0; 16];
+    let (recv, _) = peer.recv_from(&mut buf).await?;
+    assert_eq!(&buf[..recv], b"hello");
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn interval_skip_missed_ticks() {
+    use futures::prelude::*;
+    use runtime::time::{Interval, MissedTickBehavior};
+    use std::time::{Duration, Instant};
+
+    let period = Duration::from_millis(100);
+    let mut interval = Interval::new(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    interval.next().await;
+
+    // Stall the task for several periods so that ticks are missed.
+    std::thread::sleep(period * 3 + period / 2);
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        interval.next().await;
+    }
+    assert!(Instant::now() - start >= period);
+}
+
+#[runtime::test(Native)]
+async fn interval_tick_reports_missed_ticks() -> std::io::Result<()> {
+    use runtime::time::Interval;
+    use std::time::Duration;
+
+    let period = Duration::from_millis(50);
+    let mut interval = Interval::new(period);
+    assert_eq!(interval.tick().await?.missed, 0);
+
+    // Stall the task for several periods so that ticks are missed.
+    std::thread::sleep(period * 3 + period / 2);
+
+    assert!(interval.tick().await?.missed >= 2);
+    Ok(())
+}
+
+#[runtime::test(Native)]
+async fn interval_new_at() {
+    use futures::prelude::*;
+    use runtime::time::Interval;
+    use std::time::{Duration, Instant};
+
+    let at = Instant::now() + Duration::from_millis(50);
+    let mut interval = Interval::new_at(at, Duration::from_millis(100));
+    let first = interval.next().await.unwrap();
+    assert!(first >= at);
+    assert!(first - at < Duration::from_millis(50));
+}
+
+#[runtime::test(Native)]
+async fn timeout_or_return() {
+    use runtime::time::FutureExt;
+    use std::time::Duration;
+
+    let future = runtime::time::sleep(Duration::from_millis(200));
+    let future = match future.timeout_or_return(Duration::from_millis(10)).await {
+        Ok(_) => panic!("the future should have timed out"),
+        Err(future) => future,
+    };
+    assert!(future.timeout(Duration::from_secs(5)).await.is_ok());
+}
+
+#[runtime::test(Native)]
+async fn timeout_elapsed() {
+    use runtime::time::{Elapsed, FutureExt};
+    use std::time::Duration;
+
+    let res = runtime::time::sleep(Duration::from_secs(5))
+        .timeout(Duration::from_millis(10))
+        .await;
+    match res {
+        Err(Elapsed { .. }) => {}
+        Ok(_) => panic!("the future should have timed out"),
+    }
+
+    let err: std::io::Error = res.unwrap_err().into();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+}
+
+#[runtime::test(Native)]
+async fn delay_debug() {
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Entry {
+        delay: runtime::time::Delay,
+    }
+
+    let entry = Entry {
+        delay: runtime::time::sleep(Duration::from_millis(10)),
+    };
+    assert!(format!("{:?}", entry).contains("Delay"));
+}
+
+#[runtime::test(Native)]
+async fn delay_is_elapsed() {
+    use std::time::Duration;
+
+    let mut delay = runtime::time::sleep(Duration::from_millis(10));
+    assert!(!delay.is_elapsed());
+
+    let at = (&mut delay).await;
+    assert!(delay.is_elapsed());
+    // Polling again returns the same instant instead of waiting on the timer.
+    assert_eq!((&mut delay).await, at);
+}
+
+#[runtime::test(Native)]
+async fn timed_delay() {
+    use runtime::time::{timed, Delay, Stopwatch};
+    use std::time::Duration;
+
+    let dur = Duration::from_millis(20);
+    let stopwatch = Stopwatch::start();
+    let (_, elapsed) = timed(Delay::new(dur)).await;
+    assert!(elapsed >= dur);
+    assert!(stopwatch.elapsed() >= elapsed);
+}
+
+#[runtime::test(Native)]
+async fn delay_queue_order() {
+    use futures::prelude::*;
+    use runtime::time::DelayQueue;
+    use std::time::Duration;
+
+    let mut queue = DelayQueue::new();
+    queue.insert(3, Duration::from_millis(60));
+    queue.insert(1, Duration::from_millis(20));
+    let removed = queue.insert(4, Duration::from_millis(30));
+    queue.insert(2, Duration::from_millis(40));
+    assert_eq!(queue.remove(removed), Some(4));
+
+    let values: Vec<_> = queue.collect().await;
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[runtime::test(Native)]
+async fn delay_queue_remove_many() {
+    use futures::prelude::*;
+    use runtime::time::DelayQueue;
+    use std::time::Duration;
+
+    // Removing most values compacts the queue's deadlines without losing the ones left.
+    let mut queue = DelayQueue::new();
+    let keys: Vec<_> = (0..100)
+        .map(|i| queue.insert(i, Duration::from_millis(10 + i)))
+        .collect();
+    for (i, key) in keys.into_iter().enumerate() {
+        if i % 25 != 0 {
+            assert_eq!(queue.remove(key), Some(i as u64));
+        }
+    }
+    assert_eq!(queue.len(), 4);
+
+    let values: Vec<_> = queue.collect().await;
+    assert_eq!(values, vec![0, 25, 50, 75]);
+}
+
+#[runtime::test(Native)]
+async fn spawn_from_handle_on_other_thread() {
+    let handle = runtime::task::Handle::current();
+    let task = std::thread::spawn(move || {
+        assert!(runtime::task::Handle::try_current().is_none());
+        handle.spawn(async { 42 })
+    })
+    .join()
+    .unwrap();
+    assert_eq!(task.await.unwrap(), 42);
+}
+
+#[runtime::test(Native)]
+async fn compat_spawn01() {
+    use futures01::Future;
+
+    let (tx, rx) = futures::channel::oneshot::channel();
+    runtime::compat::spawn01(futures01::future::ok::<(), ()>(()).map(move |()| {
+        let _ = tx.send(42);
+    }));
+    assert_eq!(rx.await.unwrap(), 42);
+}
+
+#[runtime::test(Native)]
+async fn spawn_detach() {
+    use std::time::Duration;
+
+    let (tx, rx) = futures::channel::oneshot::channel();
+    drop(runtime::spawn(async move {
+        runtime::time::sleep(Duration::from_millis(10)).await;
+        tx.send(42).unwrap();
+    }));
+    assert_eq!(rx.await.unwrap(), 42);
+}
+
+#[runtime::test(Native)]
+async fn spawn_abort() {
+    use std::time::Duration;
+
+    let handle = runtime::spawn(async {
+        runtime::time::sleep(Duration::from_secs(10)).await;
+        42
+    });
+    handle.abort();
+    let err = handle.await.unwrap_err();
+    assert!(err.is_cancelled());
+    assert!(!err.is_panic());
+}
+
+#[runtime::test(Native)]
+async fn spawn_panic() {
+    let handle = runtime::spawn(async {
+        panic!("boom");
+    });
+    let err = handle.await.unwrap_err();
+    assert!(err.is_panic());
+    assert_eq!(*err.into_panic().downcast::<&str>().unwrap(), "boom");
+
+    // The worker that polled the panicking future keeps running tasks.
+    let handles: Vec<_> = (0..8).map(|i| runtime::spawn(async move { i })).collect();
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.await.unwrap(), i);
+    }
+}
+
+#[runtime::test(Native)]
+async fn spawn_catching() {
+    let handle = runtime::task::spawn_catching(async {
+        panic!("boom");
+    });
+    let payload = handle.await.unwrap().unwrap_err();
+    assert_eq!(*payload.downcast::<&str>().unwrap(), "boom");
+
+    let handle = runtime::task::spawn_catching(async { 42 });
+    assert_eq!(handle.await.unwrap().unwrap(), 42);
+}
+
+#[runtime::test(Native)]
+async fn metrics_count_spawned_tasks() {
+    let before = runtime::metrics().num_spawned_tasks.unwrap();
+
+    let handles: Vec<_> = (0..10).map(|i| runtime::spawn(async move { i })).collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    // Other tests spawn tasks on the same runtime, so the count can only be checked from below.
+    let metrics = runtime::metrics();
+    assert!(metrics.num_spawned_tasks.unwrap() >= before + 10);
+    assert!(metrics.num_pending_tasks.is_some());
+}
+
+#[runtime::test(Native)]
+async fn for_each_concurrent_spawned() {
+    use futures::prelude::*;
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let threads = Arc::new(Mutex::new(HashSet::new()));
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    let items = stream::iter(0..100);
+    runtime::task::for_each_concurrent_spawned(items, 8, |i| {
+        let threads = threads.clone();
+        let processed = processed.clone();
+        async move {
+            // Keep the worker busy, so that other workers pick up the next tasks.
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(2) {}
+            threads.lock().unwrap().insert(thread::current().id());
+            processed.lock().unwrap().push(i);
+        }
+    })
+    .await
+    .unwrap();
+
+    let mut processed = processed.lock().unwrap().clone();
+    processed.sort();
+    assert_eq!(processed, (0..100).collect::<Vec<_>>());
+    // With a single worker thread, there's nothing to spread the tasks across.
+    let workers = runtime::task::worker_threads().unwrap_or_else(num_cpus::get);
+    if workers > 1 {
+        assert!(threads.lock().unwrap().len() > 1);
+    }
+}
+
+#[runtime::test(Native)]
+async fn spawn_blocking() {
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let blocking = runtime::task::spawn_blocking(|| {
+        std::thread::sleep(Duration::from_millis(200));
+        42
+    });
+
+    // Other tasks keep making progress while the blocking function runs.
+    runtime::spawn(runtime::time::sleep(Duration::from_millis(10)))
+        .await
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_millis(200));
+
+    assert_eq!(blocking.await, 42);
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[runtime::test(Native)]
+async fn yield_now() {
+    use runtime::time::FutureExt;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let done = Arc::new(AtomicBool::new(false));
+    let busy = runtime::spawn({
+        let done = done.clone();
+        async move {
+            while !done.load(Ordering::SeqCst) {
+                runtime::task::yield_now().await;
+            }
+        }
+    });
+
+    let res = runtime::spawn(async { 42 })
+        .timeout(Duration::from_secs(5))
+        .await;
+    done.store(true, Ordering::SeqCst);
+
+    assert_eq!(res.unwrap().unwrap(), 42);
+    busy.await.unwrap();
+}
+
+runtime::task::task_local! {
+    static TASK_ID: u32;
+}
+
+#[runtime::test(Native)]
+async fn task_local_scope() {
+    async fn nested() -> u32 {
+        runtime::task::yield_now().await;
+        TASK_ID.with(|id| *id)
+    }
+
+    let tasks: Vec<_> = (0..2)
+        .map(|id| {
+            runtime::spawn(TASK_ID.scope(id, async move {
+                runtime::task::yield_now().await;
+                assert_eq!(nested().await, id);
+                TASK_ID.with(|id| *id)
+            }))
+        })
+        .collect();
+
+    for (id, task) in tasks.into_iter().enumerate() {
+        assert_eq!(task.await.unwrap(), id as u32);
+    }
+}
+
+#[test]
+fn task_block_on() {
+    let n = runtime::task::block_on(async { runtime::spawn(async { 42 }).await.unwrap() });
+    assert_eq!(n, 42);
+}
+
+#[runtime::test(Native)]
+async fn join_set_completion_order() {
+    use runtime::task::JoinSet;
+    use std::time::Duration;
+
+    let mut set = JoinSet::new();
+    for &ms in &[60, 20, 40] {
+        set.spawn(async move {
+            runtime::time::sleep(Duration::from_millis(ms)).await;
+            ms
+        });
+    }
+    assert_eq!(set.len(), 3);
+
+    let mut order = Vec::new();
+    while let Some(res) = set.join_next().await {
+        order.push(res.unwrap());
+    }
+    assert_eq!(order, vec![20, 40, 60]);
+    assert!(set.is_empty());
+}
+
+// The test runs on its own pool, so that the tasks of other tests aren't waited on.
+#[runtime::test(Native, threads = 2)]
+async fn join_all_spawned() {
+    use futures::prelude::*;
+    use runtime::raw::Runtime;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    // A task on another runtime doesn't hold up this one.
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    Native.spawn_boxed(rx.map(drop).boxed()).unwrap();
+
+    let completed = Arc::new(AtomicUsize::new(0));
+    for ms in 1..=5 {
+        let completed = completed.clone();
+        drop(runtime::spawn(async move {
+            runtime::time::sleep(Duration::from_millis(ms * 10)).await;
+            completed.fetch_add(1, Ordering::SeqCst);
+        }));
+    }
+
+    runtime::task::join_all_spawned().await;
+    assert_eq!(completed.load(Ordering::SeqCst), 5);
+    drop(tx);
+}
+
+#[runtime::test(Native, threads = 2)]
+async fn join_all_spawned_from_several_tasks() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let slept = Arc::new(AtomicBool::new(false));
+    let sleeper = {
+        let slept = slept.clone();
+        runtime::spawn(async move {
+            runtime::time::sleep(Duration::from_millis(20)).await;
+            slept.store(true, Ordering::SeqCst);
+        })
+    };
+
+    // The test itself runs as a spawned task too, so three tasks end up waiting on each other.
+    let waiters: Vec<_> = (0..2)
+        .map(|_| {
+            let slept = slept.clone();
+            runtime::spawn(async move {
+                runtime::task::join_all_spawned().await;
+                slept.load(Ordering::SeqCst)
+            })
+        })
+        .collect();
+    runtime::task::join_all_spawned().await;
+
+    for waiter in waiters {
+        assert!(waiter.await.unwrap());
+    }
+    sleeper.await.unwrap();
+}
+
+#[runtime::test(Native)]
+async fn semaphore_spawn_with_permit() {
+    use runtime::task::Semaphore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let semaphore = Semaphore::new(2);
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+    let mut handles = Vec::new();
+    for _ in 0..10 {
+        let in_flight = in_flight.clone();
+        let max_in_flight = max_in_flight.clone();
+        let handle = semaphore
+            .spawn_with_permit(async move {
+                let n = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(n, Ordering::SeqCst);
+                runtime::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            })
+            .await;
+        handles.push(handle);
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    assert_eq!(semaphore.available_permits(), 2);
+}
+
+#[runtime::test(Native)]
+async fn semaphore_cancel_acquire() {
+    use runtime::task::Semaphore;
+    use runtime::time::FutureExt;
+    use std::time::Duration;
+
+    let semaphore = Semaphore::new(1);
+    let permit = semaphore.acquire().await;
+
+    // Queue up an acquire, then cancel it once another task is waiting behind it.
+    let mut cancelled = semaphore.acquire();
+    assert!(futures::poll!(&mut cancelled).is_pending());
+    let waiting = runtime::spawn({
+        let semaphore = semaphore.clone();
+        async move {
+            let _permit = semaphore.acquire().await;
+        }
+    });
+    runtime::time::sleep(Duration::from_millis(10)).await;
+    drop(cancelled);
+
+    drop(permit);
+    waiting
+        .timeout(Duration::from_secs(1))
+        .await
+        .expect("the waiting task wasn't woken up")
+        .unwrap();
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[runtime::test(Native)]
+async fn mpsc_backpressure() {
+    use futures::prelude::*;
+    use runtime::sync::mpsc::{self, TrySendError};
+
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.send(1).await.unwrap();
+    assert_eq!(tx.capacity(), 0);
+    assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+
+    let mut send = tx.send(2);
+    assert!(futures::poll!(&mut send).is_pending());
+    assert_eq!(rx.next().await, Some(1));
+    send.await.unwrap();
+    assert_eq!(rx.next().await, Some(2));
+    assert_eq!(tx.capacity(), 1);
+
+    drop(rx);
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(3).await.unwrap_err().0, 3);
+}
+
+#[runtime::test(Native)]
+async fn mpsc_cancel_send() {
+    use futures::prelude::*;
+    use runtime::sync::mpsc;
+    use runtime::time::FutureExt;
+    use std::time::Duration;
+
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.send(1).await.unwrap();
+
+    // Wait to send, then cancel once another sender is waiting behind it.
+    let mut cancelled = tx.send(2);
+    assert!(futures::poll!(&mut cancelled).is_pending());
+    let waiting = runtime::spawn({
+        let tx = tx.clone();
+        async move { tx.send(3).await.unwrap() }
+    });
+    runtime::time::sleep(Duration::from_millis(10)).await;
+    drop(cancelled);
+
+    assert_eq!(rx.next().await, Some(1));
+    waiting
+        .timeout(Duration::from_secs(1))
+        .await
+        .expect("the waiting sender wasn't woken up")
+        .unwrap();
+    assert_eq!(rx.next().await, Some(3));
+}
+
+#[runtime::test(Native)]
+async fn mpsc_close_when_senders_drop() {
+    use futures::prelude::*;
+    use runtime::sync::mpsc;
+
+    let (tx, rx) = mpsc::channel(2);
+    for i in 0..2 {
+        let tx = tx.clone();
+        runtime::spawn(async move {
+            for j in 0..5 {
+                tx.send(i * 5 + j).await.unwrap();
+            }
+        });
+    }
+    drop(tx);
+
+    let mut received: Vec<_> = rx.collect().await;
+    received.sort();
+    assert_eq!(received, (0..10).collect::<Vec<_>>());
+}
+
+#[runtime::test(Native)]
+async fn barrier() {
+    use runtime::sync::Barrier;
+    use std::time::{Duration, Instant};
+
+    let barrier = Barrier::new(3);
+    let start = Instant::now();
+    let handles = (0..3)
+        .map(|i| {
+            let barrier = barrier.clone();
+            runtime::spawn(async move {
+                runtime::time::sleep(Duration::from_millis(i * 50)).await;
+                let res = barrier.wait().await;
+                (res.is_leader(), Instant::now())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for handle in handles {
+        results.push(handle.await.unwrap());
+    }
+    assert_eq!(results.iter().filter(|(leader, _)| *leader).count(), 1);
+    for (_, released) in &results {
+        assert!(*released >= start + Duration::from_millis(100));
+    }
+    let first = results.iter().map(|(_, released)| *released).min().unwrap();
+    let last = results.iter().map(|(_, released)| *released).max().unwrap();
+    assert!(last - first < Duration::from_millis(50));
+
+    // The barrier can be used again by the next group.
+    let mut waiters = (0..2).map(|_| barrier.wait()).collect::<Vec<_>>();
+    for waiter in &mut waiters {
+        assert!(futures::poll!(waiter).is_pending());
+    }
+    let res = barrier.wait().await;
+    assert!(res.is_leader());
+    for waiter in waiters {
+        assert!(!waiter.await.is_leader());
+    }
+}
+
+#[runtime::test(Native)]
+async fn mutex_contention() {
+    use runtime::sync::Mutex;
+    use std::sync::Arc;
+
+    let counter = Arc::new(Mutex::new(0));
+    let handles = (0..10)
+        .map(|_| {
+            let counter = counter.clone();
+            runtime::spawn(async move {
+                for _ in 0..100 {
+                    // Holding the guard across an await point still excludes the other tasks.
+                    let mut guard = counter.lock().await;
+                    let value = *guard;
+                    runtime::task::yield_now().await;
+                    *guard = value + 1;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    assert_eq!(*counter.lock().await, 1000);
+
+    let guard = counter.try_lock().unwrap();
+    assert!(counter.try_lock().is_none());
+    drop(guard);
+    assert!(counter.try_lock().is_some());
+}
+
+#[runtime::test(Native)]
+async fn mutex_fairness() {
+    use runtime::sync::Mutex;
+
+    let mutex = Mutex::new(Vec::new());
+    let guard = mutex.lock().await;
+    let mut waiters = (0..3)
+        .map(|i| {
+            let mutex = &mutex;
+            Box::pin(async move { mutex.lock().await.push(i) })
+        })
+        .collect::<Vec<_>>();
+    for waiter in &mut waiters {
+        assert!(futures::poll!(waiter).is_pending());
+    }
+    // A task that starts waiting later doesn't overtake the queue, even once it's unlocked.
+    drop(guard);
+    assert!(mutex.try_lock().is_none());
+    futures::future::join_all(waiters.into_iter().rev()).await;
+    assert_eq!(*mutex.lock().await, vec![0, 1, 2]);
+}
+
+#[runtime::test(Native)]
+async fn rwlock_readers_in_parallel() {
+    use runtime::sync::RwLock;
+    use runtime::time::FutureExt;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let lock = Arc::new(RwLock::new(0));
+    let reader = lock.read().await;
+
+    // Another task can read while the lock is held for reading...
+    let other = lock.clone();
+    runtime::spawn(async move { *other.read().await })
+        .timeout(Duration::from_secs(1))
+        .await
+        .expect("readers are not exclusive")
+        .unwrap();
+
+    // ...but a writer has to wait for the readers, and readers that come after it wait for it.
+    let writer = lock.clone();
+    let mut write = runtime::spawn(async move { *writer.write().await += 1 });
+    runtime::time::sleep(Duration::from_millis(50)).await;
+    assert!(futures::poll!(&mut write).is_pending());
+    assert!(lock.try_read().is_none());
+
+    drop(reader);
+    write.await.unwrap();
+    assert_eq!(*lock.read().await, 1);
+}
+
+#[runtime::test(Native)]
+async fn oneshot_send_recv() {
+    use runtime::sync::oneshot::{self, TryRecvError};
+
+    let (tx, mut rx) = oneshot::channel();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    assert!(!tx.is_closed());
+    runtime::spawn(async move { tx.send("pong").unwrap() });
+    assert_eq!(rx.await, Ok("pong"));
+
+    let (tx, mut rx) = oneshot::channel();
+    tx.send(1).unwrap();
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[runtime::test(Native)]
+async fn oneshot_sender_dropped() {
+    use runtime::sync::oneshot::{self, RecvError};
+
+    let (tx, rx) = oneshot::channel::<()>();
+    runtime::spawn(async move { drop(tx) });
+    assert_eq!(rx.await, Err(RecvError));
+}
+
+#[runtime::test(Native)]
+async fn oneshot_is_closed() {
+    use futures::future;
+    use runtime::sync::oneshot;
+
+    let (mut tx, rx) = oneshot::channel();
+    let handle = runtime::spawn(async move {
+        future::poll_fn(|cx| tx.poll_closed(cx)).await;
+        assert!(tx.is_closed());
+        tx.send(1).unwrap_err()
+    });
+    drop(rx);
+    assert_eq!(handle.await.unwrap(), 1);
+
+    let (tx, mut rx) = oneshot::channel::<()>();
+    rx.close();
+    assert!(tx.is_closed());
+}
+
+#[runtime::test(Native)]
+async fn notify_before_wait() {
+    use runtime::task::Notify;
+
+    let notify = Notify::new();
+    notify.notify_one();
+    notify.notify_one();
+    notify.notified().await;
+
+    // Only one permit is stored.
+    let mut notified = notify.notified();
+    assert!(futures::poll!(&mut notified).is_pending());
+}
+
+#[runtime::test(Native)]
+async fn notify_after_wait() {
+    use runtime::task::Notify;
+
+    let notify = Notify::new();
+    let mut notified = notify.notified();
+    assert!(futures::poll!(&mut notified).is_pending());
+
+    let notifier = notify.clone();
+    runtime::spawn(async move { notifier.notify_one() });
+    notified.await;
+}
+
+#[runtime::test(Native)]
+async fn notify_waiters() {
+    use runtime::task::Notify;
+
+    let notify = Notify::new();
+    let mut handles = Vec::new();
+    for _ in 0..3 {
+        let mut notified = notify.notified();
+        assert!(futures::poll!(&mut notified).is_pending());
+        handles.push(runtime::spawn(notified));
+    }
+
+    notify.notify_waiters();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    // No permit is stored for later waiters.
+    let mut notified = notify.notified();
+    assert!(futures::poll!(&mut notified).is_pending());
+}
+
+#[runtime::test(Native)]
+async fn cancellation_token() {
+    use runtime::task::CancellationToken;
+
+    let token = CancellationToken::new();
+    let handles: Vec<_> = (0..2)
+        .map(|i| {
+            let child = token.child_token();
+            runtime::spawn(async move {
+                child.cancelled().await;
+                i
+            })
+        })
+        .collect();
+
+    let unrelated = token.child_token();
+    unrelated.child_token().cancel();
+    assert!(!unrelated.is_cancelled());
+
+    token.cancel();
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.await.unwrap(), i);
+    }
+    assert!(unrelated.is_cancelled());
+    assert!(token.child_token().is_cancelled());
+}
+
+#[test]
+fn cancellation_token_drop_cancelled() {
+    use futures::prelude::*;
+    use futures::task::{waker, ArcWake, Context};
+    use runtime::task::CancellationToken;
+    use std::sync::Arc;
+
+    struct NoopWake;
+
+    impl ArcWake for NoopWake {
+        fn wake_by_ref(_: &Arc<Self>) {}
+    }
+
+    let token = CancellationToken::new();
+    let wake = Arc::new(NoopWake);
+    let waker = waker(wake.clone());
+    let mut cx = Context::from_waker(&waker);
+    for _ in 0..3 {
+        let mut cancelled = token.cancelled();
+        assert!(cancelled.poll_unpin(&mut cx).is_pending());
+    }
+
+    // The token doesn't hold on to the wakers of futures that have been dropped.
+    drop(waker);
+    assert_eq!(Arc::strong_count(&wake), 1);
+}
+
+#[runtime::test(Native, timeout_ms = 50)]
+#[should_panic(expected = "test timed out after 50ms")]
+async fn test_timeout() {
+    futures::future::pending::<()>().await;
+}
+
+#[runtime::test(Native)]
+async fn stream_throttle() {
+    use futures::prelude::*;
+    use runtime::time::StreamExt as _;
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let items: Vec<_> = stream::iter(vec![1, 2, 3])
+        .throttle(Duration::from_millis(50))
+        .collect()
+        .await;
+    assert_eq!(items, [1, 2, 3]);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[runtime::test(Native)]
+async fn stream_timeout_fused() {
+    use futures::prelude::*;
+    use runtime::time::StreamExt as _;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    // Yields one item and ends, then stays pending if it's polled again.
+    let mut polls = 0;
+    let inner = stream::poll_fn(move |_| {
+        polls += 1;
+        match polls {
+            1 => Poll::Ready(Some(1)),
+            2 => Poll::Ready(None),
+            _ => Poll::Pending,
+        }
+    });
+
+    let mut stream = inner.timeout(Duration::from_millis(10));
+    assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+    assert!(stream.next().await.is_none());
+
+    runtime::time::sleep(Duration::from_millis(20)).await;
+    assert!(stream.next().await.is_none());
+}
+
+#[test]
+fn test_clock_advance() {
+    use runtime_native::TestClock;
+    use std::time::{Duration, Instant};
+
+    let clock = TestClock::new();
+    let handle = clock.clone();
+    runtime::raw::enter(clock, async move {
+        let start = runtime::time::now();
+        let delay = runtime::spawn(runtime::time::sleep(Duration::from_secs(10)));
+
+        let real_start = Instant::now();
+        handle.advance(Duration::from_secs(10));
+        delay.await.unwrap();
+        assert!(real_start.elapsed() < Duration::from_secs(10));
+        assert_eq!(runtime::time::now() - start, Duration::from_secs(10));
+    });
+}
+
+#[runtime::test(start_paused = true)]
+async fn start_paused() {
+    use std::time::{Duration, Instant};
+
+    let start = runtime::time::now();
+    let real_start = Instant::now();
+    runtime::time::sleep(Duration::from_secs(60 * 60)).await;
+    assert_eq!(runtime::time::now() - start, Duration::from_secs(60 * 60));
+    assert!(real_start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn no_current_runtime() {
+    std::thread::spawn(|| {
+        assert!(runtime::raw::try_current_runtime().is_none());
+
+        let err = TcpListener::bind("127.0.0.1:0").unwrap_err();
+        assert_eq!(err.to_string(), "the runtime has not been set");
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn connect_without_runtime() {
+    use futures::executor::block_on;
+    use runtime::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    std::thread::spawn(|| {
+        // Connecting doesn't panic, and the error is reported by the returned future instead.
+        let connect = TcpStream::connect("127.0.0.1:8080");
+        let err = block_on(connect).unwrap_err();
+        assert_eq!(err.to_string(), "the runtime has not been set");
+
+        let connect = TcpStream::connect_timeout("127.0.0.1:8080", Duration::from_secs(1));
+        let err = block_on(connect).unwrap_err();
+        assert_eq!(err.to_string(), "the runtime has not been set");
+
+        let err = block_on(runtime::net::lookup_host("localhost:8080")).unwrap_err();
+        assert_eq!(err.to_string(), "the runtime has not been set");
+
+        let err = block_on(UnixStream::connect("/tmp/runtime-no-such-socket")).unwrap_err();
+        assert_eq!(err.to_string(), "the runtime has not been set");
+    })
+    .join()
+    .unwrap();
 }
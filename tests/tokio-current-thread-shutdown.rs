@@ -0,0 +1,18 @@
+use runtime_tokio::TokioCurrentThread;
+
+// Shutting down the runtime would interfere with other tests, so this test lives in its own binary.
+#[test]
+fn shutdown() {
+    let answer = runtime::raw::enter(TokioCurrentThread, async {
+        runtime::spawn(async { 42 }).await.unwrap()
+    });
+    assert_eq!(answer, 42);
+
+    assert!(TokioCurrentThread::shutdown());
+    assert!(!TokioCurrentThread::shutdown());
+
+    // The runtime starts again on the next spawn.
+    let answer = runtime::raw::enter(TokioCurrentThread, async { 42 });
+    assert_eq!(answer, 42);
+    assert!(TokioCurrentThread::shutdown());
+}
@@ -4,5 +4,5 @@ async fn spawn() {
         println!("hello planet from Tokio current-thread");
         42
     });
-    assert_eq!(handle.await, 42);
+    assert_eq!(handle.await.unwrap(), 42);
 }
@@ -0,0 +1,21 @@
+use runtime_tokio::Tokio;
+
+// The thread pool is configured when it's first used, so this test lives in its own binary.
+#[runtime::test(Tokio, threads = 2)]
+async fn worker_threads() {
+    assert_eq!(runtime::task::worker_threads(), Some(2));
+}
+
+#[runtime::test(Tokio::with_threads(2))]
+async fn with_threads() {
+    assert_eq!(runtime::task::worker_threads(), Some(2));
+
+    let tasks: Vec<_> = (0..64u64)
+        .map(|i| runtime::spawn(async move { i * 2 }))
+        .collect();
+    let mut sum = 0;
+    for task in tasks {
+        sum += task.await.unwrap();
+    }
+    assert_eq!(sum, 4032);
+}
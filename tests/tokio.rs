@@ -1,3 +1,4 @@
+use runtime::net::{TcpListener, TcpStream};
 use runtime_tokio::Tokio;
 
 #[runtime::test(Tokio)]
@@ -6,5 +7,166 @@ async fn spawn() {
         println!("hello planet from Tokio");
         42
     });
-    assert_eq!(handle.await, 42);
+    assert_eq!(handle.await.unwrap(), 42);
+}
+
+#[runtime::test(Tokio)]
+async fn capabilities() {
+    assert_eq!(runtime::capabilities(), runtime::Capabilities::all());
+}
+
+#[runtime::test(Tokio)]
+async fn tcp_nodelay() -> std::io::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let stream = TcpStream::connect(listener.local_addr()?).await?;
+
+    stream.set_nodelay(true)?;
+    assert!(stream.nodelay()?);
+    stream.set_nodelay(false)?;
+    assert!(!stream.nodelay()?);
+    Ok(())
+}
+
+#[runtime::test(Tokio)]
+async fn tcp_try_clone() -> std::io::Result<()> {
+    use futures::prelude::*;
+
+    let mut listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let server = runtime::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (reader, writer) = &mut stream.split();
+        reader.copy_into(writer).await
+    });
+
+    let mut writer = TcpStream::connect(addr).await?;
+    let mut reader = writer.try_clone()?;
+
+    writer.write_all(b"hello world").await?;
+    let mut buf = [0u8; 11];
+    reader.read_exact(&mut buf).await?;
+    assert_eq!(&buf, b"hello world");
+
+    drop((reader, writer));
+    assert_eq!(server.await??, 11);
+    Ok(())
+}
+
+#[runtime::test(Tokio)]
+async fn udp_peek_from() -> std::io::Result<()> {
+    use runtime::net::UdpSocket;
+
+    let mut a = UdpSocket::bind("127.0.0.1:0")?;
+    let mut b = UdpSocket::bind("127.0.0.1:0")?;
+    let addr = b.local_addr()?;
+    let sender = runtime::spawn(async move { a.send_to(b"hello", addr).await });
+
+    let mut peeked = [0; 2];
+    let (peek_len, _) = b.peek_from(&mut peeked).await?;
+    assert_eq!(&peeked[..peek_len], b"he");
+    sender.await.unwrap()?;
+
+    let mut received = [0; 16];
+    let (recv_len, _) = b.recv_from(&mut received).await?;
+    assert_eq!(&received[..recv_len], b"hello");
+    Ok(())
+}
+
+#[test]
+fn block_on_not_send() {
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    // `Rc` isn't `Send`, so this future can only run on the thread that blocks on it.
+    let answer = Rc::new(42);
+    let res = runtime::raw::block_on(&Tokio, async move {
+        runtime::time::sleep(Duration::from_millis(10)).await;
+        let spawned = runtime::spawn(async { 1 }).await.unwrap();
+        *answer + spawned
+    });
+    assert_eq!(res, 43);
+}
+
+#[test]
+fn block_on_resources_outlive_call() {
+    use runtime::raw::Runtime;
+    use runtime_tokio::TokioCurrentThread;
+    use std::time::Duration;
+
+    // The listener is registered with the runtime's reactor rather than one that only lives for
+    // the duration of the first call.
+    for rt in [&Tokio as &'static dyn Runtime, &TokioCurrentThread] {
+        let mut listener =
+            runtime::raw::block_on(rt, async { TcpListener::bind("127.0.0.1:0") }).unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime::raw::block_on(rt, async {
+            let client = runtime::spawn(TcpStream::connect(addr));
+            listener.accept().await.unwrap();
+            client.await.unwrap().unwrap();
+            runtime::time::sleep(Duration::from_millis(10)).await;
+        });
+    }
+}
+
+#[test]
+fn capabilities_current_thread() {
+    use runtime::raw::Runtime;
+    use runtime_tokio::TokioCurrentThread;
+
+    assert_eq!(
+        TokioCurrentThread.capabilities(),
+        runtime::Capabilities::all()
+    );
+}
+
+#[test]
+fn set_runtime_scoped() {
+    use runtime::raw::{set_runtime_scoped, try_current_runtime};
+    use runtime_tokio::TokioCurrentThread;
+
+    let pool: &'static _ = Box::leak(Box::new(Tokio::with_threads(3)));
+    let worker_threads = || try_current_runtime().and_then(|rt| rt.worker_threads());
+
+    assert!(try_current_runtime().is_none());
+    let outer = set_runtime_scoped(&TokioCurrentThread);
+    assert_eq!(worker_threads(), Some(1));
+
+    let inner = set_runtime_scoped(pool);
+    assert_eq!(worker_threads(), Some(3));
+    drop(inner);
+
+    assert_eq!(worker_threads(), Some(1));
+    drop(outer);
+    assert!(try_current_runtime().is_none());
+}
+
+#[test]
+fn interval_timer_error() {
+    use futures::prelude::*;
+    use runtime::raw::Runtime;
+    use std::time::Duration;
+
+    // Outside of a Tokio runtime there's no timer to register with, so polling the interval fails.
+    // It should end the stream instead of panicking.
+    let mut interval = Tokio.new_interval(Duration::from_millis(1));
+    assert_eq!(futures::executor::block_on(interval.next()), None);
+    assert_eq!(futures::executor::block_on(interval.next()), None);
+
+    // Waiting for a tick reports the error, every time.
+    let _guard = runtime::raw::set_runtime_scoped(&Tokio);
+    let mut interval = runtime::time::Interval::new(Duration::from_millis(1));
+    assert!(futures::executor::block_on(interval.tick()).is_err());
+    assert!(futures::executor::block_on(interval.tick()).is_err());
+}
+
+#[test]
+fn delay_timer_error() {
+    use runtime::raw::Runtime;
+    use std::time::{Duration, Instant};
+
+    // Without a timer to register with, the delay still fires once its deadline has passed.
+    let start = Instant::now();
+    futures::executor::block_on(Tokio.new_delay(Duration::from_millis(10)));
+    assert!(start.elapsed() >= Duration::from_millis(10));
 }
@@ -0,0 +1,3 @@
+#[runtime::main((runtime::native::Native))]
+#[deny(unused_parens)]
+async fn main() {}